@@ -0,0 +1,5 @@
+0b1010
+
+/*===
+~ERROR
+===*/
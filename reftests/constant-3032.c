@@ -0,0 +1,8 @@
+#pragma c23
+u8'x'
+
+/*===
+Constant
+    Character "x"
+        CharacterEncoding Utf8
+===*/
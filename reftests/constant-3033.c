@@ -0,0 +1,5 @@
+u8'x'
+
+/*===
+~ERROR
+===*/
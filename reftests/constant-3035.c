@@ -0,0 +1,11 @@
+0.1DD
+
+/*===
+Constant
+    Float "0.1"
+        FloatBase Decimal
+        FloatSuffix false
+            FloatFormat
+                TS18661FloatType 64
+                    TS18661FloatFormat DecimalInterchange
+===*/
@@ -0,0 +1,6 @@
+#pragma no_decimal_floats
+1.5df
+
+/*===
+~ERROR
+===*/
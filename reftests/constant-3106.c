@@ -0,0 +1,5 @@
+5uwb
+
+/*===
+~ERROR
+===*/
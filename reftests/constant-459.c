@@ -3,4 +3,6 @@
 
 /*===
 Constant
+    Character "\\n"
+        CharacterEncoding Plain
 ===*/
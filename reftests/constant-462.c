@@ -3,4 +3,6 @@
 
 /*===
 Constant
+    Character "\\1"
+        CharacterEncoding Plain
 ===*/
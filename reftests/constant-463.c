@@ -3,4 +3,6 @@
 
 /*===
 Constant
+    Character "\\02"
+        CharacterEncoding Plain
 ===*/
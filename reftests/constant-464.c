@@ -3,4 +3,6 @@
 
 /*===
 Constant
+    Character "\\027"
+        CharacterEncoding Plain
 ===*/
@@ -3,4 +3,6 @@
 
 /*===
 Constant
+    Character "\\xde"
+        CharacterEncoding Plain
 ===*/
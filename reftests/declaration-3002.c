@@ -0,0 +1,31 @@
+#pragma gnu
+__attribute__((noreturn, cold)) static __attribute__((unused)) inline void f(void);
+
+/*===
+Declaration
+    DeclarationSpecifier
+        Extension
+            Attribute "noreturn"
+    DeclarationSpecifier
+        Extension
+            Attribute "cold"
+    DeclarationSpecifier
+        StorageClassSpecifier Static
+    DeclarationSpecifier
+        Extension
+            Attribute "unused"
+    DeclarationSpecifier
+        FunctionSpecifier Inline
+    DeclarationSpecifier
+        TypeSpecifier Void
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "f"
+            DerivedDeclarator
+                FunctionDeclarator
+                    ParameterDeclaration
+                        DeclarationSpecifier
+                            TypeSpecifier Void
+                    Ellipsis None
+===*/
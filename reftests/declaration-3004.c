@@ -0,0 +1,30 @@
+#pragma gnu
+#pragma keyword_alias __my_const const
+#pragma keyword_alias __u unsigned
+#pragma keyword_alias _asm asm
+#pragma keyword_alias __forceinline inline
+__forceinline static __my_const __u f(void) _asm("g");
+
+/*===
+Declaration
+    DeclarationSpecifier
+        FunctionSpecifier Inline
+    DeclarationSpecifier
+        StorageClassSpecifier Static
+    DeclarationSpecifier
+        TypeQualifier Const
+    DeclarationSpecifier
+        TypeSpecifier Unsigned
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "f"
+            DerivedDeclarator
+                FunctionDeclarator
+                    ParameterDeclaration
+                        DeclarationSpecifier
+                            TypeSpecifier Void
+                    Ellipsis None
+            Extension
+                StringLiteral ["\"g\""]
+===*/
@@ -0,0 +1,10 @@
+#pragma keyword_alias __my_const const
+int __my_const;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Int
+    DeclarationSpecifier
+        TypeQualifier Const
+===*/
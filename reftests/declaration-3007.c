@@ -0,0 +1,35 @@
+#pragma c23
+[[deprecated, nodiscard("reason")]] int * [[gnu::nonnull]] f [[maybe_unused]] (int x);
+
+/*===
+Declaration
+    DeclarationSpecifier
+        Extension
+            StandardAttribute "deprecated"
+    DeclarationSpecifier
+        Extension
+            StandardAttribute "nodiscard"
+                Expression
+                    StringLiteral ["\"reason\""]
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "f"
+            DerivedDeclarator
+                PointerQualifier
+                    Extension
+                        StandardAttribute "gnu::nonnull"
+            DerivedDeclarator
+                FunctionDeclarator
+                    ParameterDeclaration
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        Declarator
+                            DeclaratorKind
+                                Identifier "x"
+                    Ellipsis None
+            Extension
+                StandardAttribute "maybe_unused"
+===*/
@@ -0,0 +1,38 @@
+#pragma c23
+struct s { [[deprecated]] int a; int b [[gnu::aligned(8)]]; };
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier
+            StructType
+                StructKind Struct
+                Identifier "s"
+                StructDeclaration
+                    StructField
+                        SpecifierQualifier
+                            Extension
+                                StandardAttribute "deprecated"
+                        SpecifierQualifier
+                            TypeSpecifier Int
+                        StructDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                StructDeclaration
+                    StructField
+                        SpecifierQualifier
+                            TypeSpecifier Int
+                        StructDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "b"
+                                Extension
+                                    StandardAttribute "gnu::aligned"
+                                        Expression
+                                            Constant
+                                                Integer "8"
+                                                    IntegerBase Decimal
+                                                    IntegerSuffix false false
+                                                        IntegerSize Int
+===*/
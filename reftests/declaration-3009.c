@@ -0,0 +1,22 @@
+#pragma c23
+enum e { A [[deprecated]] = 1, B };
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier
+            EnumType
+                Identifier "e"
+                Enumerator
+                    Identifier "A"
+                    Expression
+                        Constant
+                            Integer "1"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+                    Extension
+                        StandardAttribute "deprecated"
+                Enumerator
+                    Identifier "B"
+===*/
@@ -0,0 +1,5 @@
+[[deprecated]] int x;
+
+/*===
+~ERROR
+===*/
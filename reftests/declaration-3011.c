@@ -0,0 +1,38 @@
+#pragma c23
+unsigned _BitInt(128) x = 5uwb, y = 0x7fWBU;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Unsigned
+    DeclarationSpecifier
+        TypeSpecifier BitInt
+            Expression
+                Constant
+                    Integer "128"
+                        IntegerBase Decimal
+                        IntegerSuffix false false
+                            IntegerSize Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+        Initializer
+            Expression
+                Constant
+                    Integer "5"
+                        IntegerBase Decimal
+                        IntegerSuffix true false
+                            IntegerSize BitInt
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "y"
+        Initializer
+            Expression
+                Constant
+                    Integer "7f"
+                        IntegerBase Hexadecimal
+                        IntegerSuffix true false
+                            IntegerSize BitInt
+===*/
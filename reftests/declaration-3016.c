@@ -0,0 +1,21 @@
+#pragma c23
+constexpr int n = 5;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        StorageClassSpecifier Constexpr
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "n"
+        Initializer
+            Expression
+                Constant
+                    Integer "5"
+                        IntegerBase Decimal
+                        IntegerSuffix false false
+                            IntegerSize Int
+===*/
@@ -0,0 +1,5 @@
+constexpr int n = 5;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,18 @@
+#pragma c23
+typeof_unqual(const int) x;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier TypeOfUnqual
+            TypeOf
+                TypeName
+                    SpecifierQualifier
+                        TypeQualifier Const
+                    SpecifierQualifier
+                        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+===*/
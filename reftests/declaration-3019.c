@@ -0,0 +1,28 @@
+#pragma c23
+typeof(1 + 2) x;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier
+            TypeOf
+                Expression
+                    BinaryOperatorExpression
+                        Expression
+                            Constant
+                                Integer "1"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+                        Expression
+                            Constant
+                                Integer "2"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+                        BinaryOperator Plus
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+===*/
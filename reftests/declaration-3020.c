@@ -0,0 +1,15 @@
+#pragma gnu
+__typeof_unqual__(x) y;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier TypeOfUnqual
+            TypeOf
+                Expression
+                    Identifier "x"
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "y"
+===*/
@@ -0,0 +1,5 @@
+typeof(int) x;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,26 @@
+#pragma c23
+enum E : const unsigned short { A, B = 2 };
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier
+            EnumType
+                Identifier "E"
+                SpecifierQualifier
+                    TypeQualifier Const
+                SpecifierQualifier
+                    TypeSpecifier Unsigned
+                SpecifierQualifier
+                    TypeSpecifier Short
+                Enumerator
+                    Identifier "A"
+                Enumerator
+                    Identifier "B"
+                    Expression
+                        Constant
+                            Integer "2"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+===*/
@@ -0,0 +1,5 @@
+enum E : short { A };
+
+/*===
+~ERROR
+===*/
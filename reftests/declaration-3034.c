@@ -0,0 +1,22 @@
+_Decimal128 x = 1.5dl;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier
+            TS18661FloatType 128
+                TS18661FloatFormat DecimalInterchange
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+        Initializer
+            Expression
+                Constant
+                    Float "1.5"
+                        FloatBase Decimal
+                        FloatSuffix false
+                            FloatFormat
+                                TS18661FloatType 128
+                                    TS18661FloatFormat DecimalInterchange
+===*/
@@ -0,0 +1,6 @@
+#pragma no_decimal_floats
+_Decimal32 x;
+
+/*===
+~ERROR
+===*/
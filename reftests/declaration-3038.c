@@ -0,0 +1,16 @@
+#pragma c23
+struct S s = {};
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier
+            StructType
+                StructKind Struct
+                Identifier "S"
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "s"
+        Initializer
+===*/
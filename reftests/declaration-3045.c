@@ -0,0 +1,15 @@
+#pragma c23
+int f(...);
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "f"
+            DerivedDeclarator
+                FunctionDeclarator
+                    Ellipsis Some
+===*/
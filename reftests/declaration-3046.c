@@ -0,0 +1,18 @@
+#pragma c23
+void (*p)(...);
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Void
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Declarator
+                    DeclaratorKind
+                        Identifier "p"
+                    DerivedDeclarator
+            DerivedDeclarator
+                FunctionDeclarator
+                    Ellipsis Some
+===*/
@@ -0,0 +1,6 @@
+#pragma gnu
+int f(...);
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,14 @@
+#pragma gnu
+unsigned __int128 x;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Unsigned
+    DeclarationSpecifier
+        TypeSpecifier Int128
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+===*/
@@ -0,0 +1,5 @@
+__int128 x;
+
+/*===
+~ERROR
+===*/
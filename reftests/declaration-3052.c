@@ -0,0 +1,21 @@
+#pragma gnu
+__float128 x = 1.5f128;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Float128
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+        Initializer
+            Expression
+                Constant
+                    Float "1.5"
+                        FloatBase Decimal
+                        FloatSuffix false
+                            FloatFormat
+                                TS18661FloatType 128
+                                    TS18661FloatFormat BinaryInterchange
+===*/
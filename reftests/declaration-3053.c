@@ -0,0 +1,23 @@
+#pragma gnu
+_Float16x h = 0.5f16;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier
+            TS18661FloatType 16
+                TS18661FloatFormat BinaryExtended
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "h"
+        Initializer
+            Expression
+                Constant
+                    Float "0.5"
+                        FloatBase Decimal
+                        FloatSuffix false
+                            FloatFormat
+                                TS18661FloatType 16
+                                    TS18661FloatFormat BinaryInterchange
+===*/
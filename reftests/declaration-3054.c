@@ -0,0 +1,5 @@
+__float128 x;
+
+/*===
+~ERROR
+===*/
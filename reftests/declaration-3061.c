@@ -0,0 +1,43 @@
+#pragma gnu
+int x = ({ __label__ out; int r = 0; goto out; out: r; });
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+        Initializer
+            Expression
+                Statement Compound
+                    BlockItem LocalLabels
+                        Identifier "out"
+                    BlockItem
+                        Declaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            InitDeclarator
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "r"
+                                Initializer
+                                    Expression
+                                        Constant
+                                            Integer "0"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+                    BlockItem
+                        Statement Goto
+                            Identifier "out"
+                    BlockItem
+                        Statement
+                            LabeledStatement
+                                Label
+                                    Identifier "out"
+                                Statement
+                                    Expression
+                                        Identifier "r"
+===*/
@@ -0,0 +1,33 @@
+#pragma sal
+struct s { _Field_size_(n) int *p; int n; } _Inout_ *q;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier
+            StructType
+                StructKind Struct
+                Identifier "s"
+                StructDeclaration
+                    StructField
+                        SpecifierQualifier
+                            TypeSpecifier Int
+                        StructDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "p"
+                                DerivedDeclarator
+                StructDeclaration
+                    StructField
+                        SpecifierQualifier
+                            TypeSpecifier Int
+                        StructDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "n"
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "q"
+            DerivedDeclarator
+===*/
@@ -0,0 +1,30 @@
+#pragma gnu
+register long sp asm("rsp"), *bp __asm__("rbp") = 0;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        StorageClassSpecifier Register
+    DeclarationSpecifier
+        TypeSpecifier Long
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "sp"
+            Extension AsmRegister
+                StringLiteral ["\"rsp\""]
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "bp"
+            DerivedDeclarator
+            Extension AsmRegister
+                StringLiteral ["\"rbp\""]
+        Initializer
+            Expression
+                Constant
+                    Integer "0"
+                        IntegerBase Decimal
+                        IntegerSuffix false false
+                            IntegerSize Int
+===*/
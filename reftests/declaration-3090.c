@@ -0,0 +1,18 @@
+#pragma gnu
+static int counter asm("count") __attribute__((used));
+
+/*===
+Declaration
+    DeclarationSpecifier
+        StorageClassSpecifier Static
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "counter"
+            Extension
+                StringLiteral ["\"count\""]
+            Extension
+                Attribute "used"
+===*/
@@ -0,0 +1,19 @@
+#pragma gnu
+__auto_type x = 1;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier AutoType
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "x"
+        Initializer
+            Expression
+                Constant
+                    Integer "1"
+                        IntegerBase Decimal
+                        IntegerSuffix false false
+                            IntegerSize Int
+===*/
@@ -0,0 +1,6 @@
+#pragma gnu
+__auto_type int x = 1;
+
+/*===
+~ERROR
+===*/
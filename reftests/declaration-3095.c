@@ -0,0 +1,16 @@
+#pragma gnu
+static __thread int counter;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        StorageClassSpecifier Static
+    DeclarationSpecifier
+        StorageClassSpecifier ThreadLocal
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "counter"
+===*/
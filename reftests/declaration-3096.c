@@ -0,0 +1,5 @@
+__thread int counter;
+
+/*===
+~ERROR
+===*/
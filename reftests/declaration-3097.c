@@ -0,0 +1,17 @@
+#pragma clang
+extern __thread int *current;
+
+/*===
+Declaration
+    DeclarationSpecifier
+        StorageClassSpecifier Extern
+    DeclarationSpecifier
+        StorageClassSpecifier ThreadLocal
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "current"
+            DerivedDeclarator
+===*/
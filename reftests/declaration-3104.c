@@ -0,0 +1,138 @@
+// C11 5.2.4.1: 63 nesting levels of parenthesized declarators
+int (((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((x)))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))));
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Declarator
+                    DeclaratorKind
+                        Declarator
+                            DeclaratorKind
+                                Declarator
+                                    DeclaratorKind
+                                        Declarator
+                                            DeclaratorKind
+                                                Declarator
+                                                    DeclaratorKind
+                                                        Declarator
+                                                            DeclaratorKind
+                                                                Declarator
+                                                                    DeclaratorKind
+                                                                        Declarator
+                                                                            DeclaratorKind
+                                                                                Declarator
+                                                                                    DeclaratorKind
+                                                                                        Declarator
+                                                                                            DeclaratorKind
+                                                                                                Declarator
+                                                                                                    DeclaratorKind
+                                                                                                        Declarator
+                                                                                                            DeclaratorKind
+                                                                                                                Declarator
+                                                                                                                    DeclaratorKind
+                                                                                                                        Declarator
+                                                                                                                            DeclaratorKind
+                                                                                                                                Declarator
+                                                                                                                                    DeclaratorKind
+                                                                                                                                        Declarator
+                                                                                                                                            DeclaratorKind
+                                                                                                                                                Declarator
+                                                                                                                                                    DeclaratorKind
+                                                                                                                                                        Declarator
+                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                Declarator
+                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                        Declarator
+                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                Declarator
+                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                        Declarator
+                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Declarator
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    DeclaratorKind
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Identifier "x"
+===*/
@@ -0,0 +1,5 @@
+unsigned _BitInt(8) x;
+
+/*===
+~ERROR
+===*/
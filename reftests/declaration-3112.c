@@ -0,0 +1,6 @@
+// 130 nesting levels of structs, past the default limit
+struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { struct { int x; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } y; } s;
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,6 @@
+// 130 nesting levels of parameter declarations, past the default limit
+void f(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(void (*)(int)))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))));
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,7 @@
+#pragma gnu
+// 130 nesting levels of typeof specifiers, past the default limit
+typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(typeof(1)))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))) x;
+
+/*===
+~ERROR
+===*/
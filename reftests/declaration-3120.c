@@ -0,0 +1,21 @@
+#pragma c23
+struct [[deprecated]] S { int a; };
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier
+            StructType
+                StructKind Struct
+                Extension
+                    StandardAttribute "deprecated"
+                Identifier "S"
+                StructDeclaration
+                    StructField
+                        SpecifierQualifier
+                            TypeSpecifier Int
+                        StructDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+===*/
@@ -0,0 +1,20 @@
+#pragma c23
+int f(void) [[deprecated]];
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "f"
+            DerivedDeclarator
+                FunctionDeclarator
+                    ParameterDeclaration
+                        DeclarationSpecifier
+                            TypeSpecifier Void
+                    Ellipsis None
+                    Extension
+                        StandardAttribute "deprecated"
+===*/
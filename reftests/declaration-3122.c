@@ -0,0 +1,23 @@
+#pragma c23
+int a[3] [[gnu::unused]];
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier Int
+    InitDeclarator
+        Declarator
+            DeclaratorKind
+                Identifier "a"
+            DerivedDeclarator
+                ArrayDeclarator
+                    ArraySize VariableExpression
+                        Expression
+                            Constant
+                                Integer "3"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+                    Extension
+                        StandardAttribute "gnu::unused"
+===*/
@@ -0,0 +1,14 @@
+#pragma c23
+enum [[deprecated]] E { A };
+
+/*===
+Declaration
+    DeclarationSpecifier
+        TypeSpecifier
+            EnumType
+                Extension
+                    StandardAttribute "deprecated"
+                Identifier "E"
+                Enumerator
+                    Identifier "A"
+===*/
@@ -0,0 +1,46 @@
+#pragma gnu
+#pragma typedef T
+__builtin_offsetof(T, a . b [1][i + 1] -> c.d [ 0 ])
+
+/*===
+Expression
+    OffsetOfExpression
+        TypeName
+            SpecifierQualifier
+                TypeSpecifier TypedefName
+                    Identifier "T"
+        OffsetDesignator
+            Identifier "a"
+            OffsetMember Member
+                Identifier "b"
+            OffsetMember
+                Expression
+                    Constant
+                        Integer "1"
+                            IntegerBase Decimal
+                            IntegerSuffix false false
+                                IntegerSize Int
+            OffsetMember
+                Expression
+                    BinaryOperatorExpression
+                        Expression
+                            Identifier "i"
+                        Expression
+                            Constant
+                                Integer "1"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+                        BinaryOperator Plus
+            OffsetMember IndirectMember
+                Identifier "c"
+            OffsetMember Member
+                Identifier "d"
+            OffsetMember
+                Expression
+                    Constant
+                        Integer "0"
+                            IntegerBase Decimal
+                            IntegerSuffix false false
+                                IntegerSize Int
+===*/
@@ -0,0 +1,31 @@
+#pragma c23
+(_BitInt(3 + 30)) 1wb
+
+/*===
+Expression
+    CastExpression
+        TypeName
+            SpecifierQualifier
+                TypeSpecifier BitInt
+                    Expression
+                        BinaryOperatorExpression
+                            Expression
+                                Constant
+                                    Integer "3"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                            Expression
+                                Constant
+                                    Integer "30"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                            BinaryOperator Plus
+        Expression
+            Constant
+                Integer "1"
+                    IntegerBase Decimal
+                    IntegerSuffix false false
+                        IntegerSize BitInt
+===*/
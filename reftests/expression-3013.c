@@ -0,0 +1,5 @@
+1wbl
+
+/*===
+~ERROR
+===*/
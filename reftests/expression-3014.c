@@ -0,0 +1,7 @@
+#pragma c23
+nullptr
+
+/*===
+Expression
+    Constant Nullptr
+===*/
@@ -0,0 +1,14 @@
+(void *) nullptr
+
+/*===
+Expression
+    CastExpression
+        TypeName
+            SpecifierQualifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind Abstract
+                DerivedDeclarator
+        Expression
+            Identifier "nullptr"
+===*/
@@ -0,0 +1,10 @@
+#pragma c23
+!false
+
+/*===
+Expression
+    UnaryOperatorExpression
+        UnaryOperator Negate
+        Expression
+            Constant Bool false
+===*/
@@ -0,0 +1,30 @@
+L'a' + u'b' + U'\x41' + *L"s"
+
+/*===
+Expression
+    BinaryOperatorExpression
+        Expression
+            BinaryOperatorExpression
+                Expression
+                    BinaryOperatorExpression
+                        Expression
+                            Constant
+                                Character "a"
+                                    CharacterEncoding Wide
+                        Expression
+                            Constant
+                                Character "b"
+                                    CharacterEncoding Utf16
+                        BinaryOperator Plus
+                Expression
+                    Constant
+                        Character "\\x41"
+                            CharacterEncoding Utf32
+                BinaryOperator Plus
+        Expression
+            UnaryOperatorExpression
+                UnaryOperator Indirection
+                Expression
+                    StringLiteral ["L\"s\""]
+        BinaryOperator Plus
+===*/
@@ -0,0 +1,10 @@
+#pragma c23
+(int){}
+
+/*===
+Expression
+    CompoundLiteral
+        TypeName
+            SpecifierQualifier
+                TypeSpecifier Int
+===*/
@@ -0,0 +1,17 @@
+#pragma gnu
+f((struct S){})
+
+/*===
+Expression
+    CallExpression
+        Expression
+            Identifier "f"
+        Expression
+            CompoundLiteral
+                TypeName
+                    SpecifierQualifier
+                        TypeSpecifier
+                            StructType
+                                StructKind Struct
+                                Identifier "S"
+===*/
@@ -0,0 +1,14 @@
+#pragma c23
+sizeof(int (...))
+
+/*===
+Expression
+    TypeName
+        SpecifierQualifier
+            TypeSpecifier Int
+        Declarator
+            DeclaratorKind Abstract
+            DerivedDeclarator
+                FunctionDeclarator
+                    Ellipsis Some
+===*/
@@ -0,0 +1,12 @@
+#pragma gnu
+a && &&b
+
+/*===
+Expression
+    BinaryOperatorExpression
+        Expression
+            Identifier "a"
+        Expression LabelAddress
+            Identifier "b"
+        BinaryOperator LogicalAnd
+===*/
@@ -0,0 +1,15 @@
+#pragma gnu
+x ?: y ?: z
+
+/*===
+Expression
+    ConditionalExpression
+        Expression
+            Identifier "x"
+        Expression
+            ConditionalExpression
+                Expression
+                    Identifier "y"
+                Expression
+                    Identifier "z"
+===*/
@@ -0,0 +1,5 @@
+a ?: b
+
+/*===
+~ERROR
+===*/
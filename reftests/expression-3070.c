@@ -0,0 +1,24 @@
+#pragma gnu
+f(p ? : q, (a, b) ?: c)
+
+/*===
+Expression
+    CallExpression
+        Expression
+            Identifier "f"
+        Expression
+            ConditionalExpression
+                Expression
+                    Identifier "p"
+                Expression
+                    Identifier "q"
+        Expression
+            ConditionalExpression
+                Expression
+                    Expression
+                        Identifier "a"
+                    Expression
+                        Identifier "b"
+                Expression
+                    Identifier "c"
+===*/
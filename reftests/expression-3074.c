@@ -0,0 +1,29 @@
+#pragma gnu
+__builtin_types_compatible_p(const int, unsigned long *) + 1
+
+/*===
+Expression
+    BinaryOperatorExpression
+        Expression
+            TypesCompatibleExpression
+                TypeName
+                    SpecifierQualifier
+                        TypeQualifier Const
+                    SpecifierQualifier
+                        TypeSpecifier Int
+                TypeName
+                    SpecifierQualifier
+                        TypeSpecifier Unsigned
+                    SpecifierQualifier
+                        TypeSpecifier Long
+                    Declarator
+                        DeclaratorKind Abstract
+                        DerivedDeclarator
+        Expression
+            Constant
+                Integer "1"
+                    IntegerBase Decimal
+                    IntegerSuffix false false
+                        IntegerSize Int
+        BinaryOperator Plus
+===*/
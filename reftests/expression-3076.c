@@ -0,0 +1,32 @@
+#pragma gnu
+__builtin_choose_expr(sizeof(long) == 8, f(x), g)(y)
+
+/*===
+Expression
+    CallExpression
+        Expression
+            ChooseExpression
+                Expression
+                    BinaryOperatorExpression
+                        Expression
+                            TypeName
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                        Expression
+                            Constant
+                                Integer "8"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+                        BinaryOperator Equals
+                Expression
+                    CallExpression
+                        Expression
+                            Identifier "f"
+                        Expression
+                            Identifier "x"
+                Expression
+                    Identifier "g"
+        Expression
+            Identifier "y"
+===*/
@@ -0,0 +1,18 @@
+__builtin_choose_expr(1, a, b)
+
+/*===
+Expression
+    CallExpression
+        Expression
+            Identifier "__builtin_choose_expr"
+        Expression
+            Constant
+                Integer "1"
+                    IntegerBase Decimal
+                    IntegerSuffix false false
+                        IntegerSize Int
+        Expression
+            Identifier "a"
+        Expression
+            Identifier "b"
+===*/
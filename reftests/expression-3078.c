@@ -0,0 +1,27 @@
+#pragma gnu
+__real__ z * 2 + __imag z
+
+/*===
+Expression
+    BinaryOperatorExpression
+        Expression
+            BinaryOperatorExpression
+                Expression
+                    UnaryOperatorExpression
+                        UnaryOperator Real
+                        Expression
+                            Identifier "z"
+                Expression
+                    Constant
+                        Integer "2"
+                            IntegerBase Decimal
+                            IntegerSuffix false false
+                                IntegerSize Int
+                BinaryOperator Multiply
+        Expression
+            UnaryOperatorExpression
+                UnaryOperator Imag
+                Expression
+                    Identifier "z"
+        BinaryOperator Plus
+===*/
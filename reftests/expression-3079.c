@@ -0,0 +1,15 @@
+#pragma gnu
+__imag__ (double)x
+
+/*===
+Expression
+    UnaryOperatorExpression
+        UnaryOperator Imag
+        Expression
+            CastExpression
+                TypeName
+                    SpecifierQualifier
+                        TypeSpecifier Double
+                Expression
+                    Identifier "x"
+===*/
@@ -0,0 +1,23 @@
+#pragma gnu
+__alignof__(s.member[1])
+
+/*===
+Expression
+    UnaryOperatorExpression
+        UnaryOperator AlignOf
+        Expression
+            BinaryOperatorExpression
+                Expression
+                    MemberExpression
+                        MemberOperator Direct
+                        Expression
+                            Identifier "s"
+                        Identifier "member"
+                Expression
+                    Constant
+                        Integer "1"
+                            IntegerBase Decimal
+                            IntegerSuffix false false
+                                IntegerSize Int
+                BinaryOperator Index
+===*/
@@ -0,0 +1,22 @@
+#pragma gnu
+#pragma typedef T
+__alignof__ *p + __alignof(T)
+
+/*===
+Expression
+    BinaryOperatorExpression
+        Expression
+            UnaryOperatorExpression
+                UnaryOperator AlignOf
+                Expression
+                    UnaryOperatorExpression
+                        UnaryOperator Indirection
+                        Expression
+                            Identifier "p"
+        Expression
+            TypeName
+                SpecifierQualifier
+                    TypeSpecifier TypedefName
+                        Identifier "T"
+        BinaryOperator Plus
+===*/
@@ -0,0 +1,10 @@
+__alignof__(x)
+
+/*===
+Expression
+    CallExpression
+        Expression
+            Identifier "__alignof__"
+        Expression
+            Identifier "x"
+===*/
@@ -0,0 +1,30 @@
+#pragma gnu
+__extension__ (long long)1 + 2
+
+/*===
+Expression
+    BinaryOperatorExpression
+        Expression
+            UnaryOperatorExpression
+                UnaryOperator Extension
+                Expression
+                    CastExpression
+                        TypeName
+                            SpecifierQualifier
+                                TypeSpecifier Long
+                            SpecifierQualifier
+                                TypeSpecifier Long
+                        Expression
+                            Constant
+                                Integer "1"
+                                    IntegerBase Decimal
+                                    IntegerSuffix false false
+                                        IntegerSize Int
+        Expression
+            Constant
+                Integer "2"
+                    IntegerBase Decimal
+                    IntegerSuffix false false
+                        IntegerSize Int
+        BinaryOperator Plus
+===*/
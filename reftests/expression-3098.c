@@ -0,0 +1,25 @@
+#pragma builtin_macros
+#pragma round_trip
+offsetof(struct s, a.b[1])
+
+/*===
+Expression
+    OffsetOfExpression
+        TypeName
+            SpecifierQualifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "s"
+        OffsetDesignator
+            Identifier "a"
+            OffsetMember Member
+                Identifier "b"
+            OffsetMember
+                Expression
+                    Constant
+                        Integer "1"
+                            IntegerBase Decimal
+                            IntegerSuffix false false
+                                IntegerSize Int
+===*/
@@ -0,0 +1,13 @@
+#pragma builtin_macros
+#pragma round_trip
+va_arg(ap, int)
+
+/*===
+Expression
+    VaArgExpression
+        Expression
+            Identifier "ap"
+        TypeName
+            SpecifierQualifier
+                TypeSpecifier Int
+===*/
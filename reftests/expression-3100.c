@@ -0,0 +1,12 @@
+offsetof(x, y)
+
+/*===
+Expression
+    CallExpression
+        Expression
+            Identifier "offsetof"
+        Expression
+            Identifier "x"
+        Expression
+            Identifier "y"
+===*/
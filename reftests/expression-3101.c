@@ -0,0 +1,13 @@
+#pragma builtin_macros
+va_start(ap, n)
+
+/*===
+Expression
+    CallExpression
+        Expression
+            Identifier "va_start"
+        Expression
+            Identifier "ap"
+        Expression
+            Identifier "n"
+===*/
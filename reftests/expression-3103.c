@@ -0,0 +1,11 @@
+// C11 5.2.4.1: 63 nesting levels of parenthesized expressions
+(((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((((1)))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))))
+
+/*===
+Expression
+    Constant
+        Integer "1"
+            IntegerBase Decimal
+            IntegerSuffix false false
+                IntegerSize Int
+===*/
@@ -0,0 +1,22 @@
+#pragma gnu
+(_BitInt(4)) 1WB
+
+/*===
+Expression
+    CastExpression
+        TypeName
+            SpecifierQualifier
+                TypeSpecifier BitInt
+                    Expression
+                        Constant
+                            Integer "4"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+        Expression
+            Constant
+                Integer "1"
+                    IntegerBase Decimal
+                    IntegerSuffix false false
+                        IntegerSize BitInt
+===*/
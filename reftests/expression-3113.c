@@ -0,0 +1,7 @@
+#pragma gnu
+// 50 nesting levels of statement expressions, past the default limit
+({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ ({ 1; }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); }); })
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,6 @@
+// 130 nesting levels of compound literals, past the default limit
+(int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ (int){ 1 } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } } }
+
+/*===
+~ERROR
+===*/
@@ -3,5 +3,5 @@
 
 /*===
 Expression
-    StringLiteral ["\"\\\'\\\"\""]
+    StringLiteral ["\"\\'\\\"\""]
 ===*/
@@ -4,7 +4,7 @@ __asm ("pmovmskb %1, %0" : "=r" (__m) : "x" (__x));
 /*===
 Statement
     AsmStatement
-        GnuExtendedAsmStatement
+        GnuExtendedAsmStatement false
             StringLiteral ["\"pmovmskb %1, %0\""]
             GnuAsmOperand
                 StringLiteral ["\"=r\""]
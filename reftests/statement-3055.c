@@ -0,0 +1,14 @@
+#pragma gnu
+goto *(x ? &&a : &&b);
+
+/*===
+Statement GotoIndirect
+    Expression
+        ConditionalExpression
+            Expression
+                Identifier "x"
+            Expression LabelAddress
+                Identifier "a"
+            Expression LabelAddress
+                Identifier "b"
+===*/
@@ -0,0 +1,31 @@
+#pragma gnu
+{ __label__ retry, done; retry: if (f()) goto retry; goto done; done: ; }
+
+/*===
+Statement Compound
+    BlockItem LocalLabels
+        Identifier "retry"
+        Identifier "done"
+    BlockItem
+        Statement
+            LabeledStatement
+                Label
+                    Identifier "retry"
+                Statement
+                    IfStatement
+                        Expression
+                            CallExpression
+                                Expression
+                                    Identifier "f"
+                        Statement Goto
+                            Identifier "retry"
+    BlockItem
+        Statement Goto
+            Identifier "done"
+    BlockItem
+        Statement
+            LabeledStatement
+                Label
+                    Identifier "done"
+                Statement
+===*/
@@ -0,0 +1,5 @@
+{ __label__ retry; retry: ; }
+
+/*===
+~ERROR
+===*/
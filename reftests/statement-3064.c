@@ -0,0 +1,64 @@
+#pragma gnu
+{ auto void g(void); int n = 1; void g(void) { n++; } g(); }
+
+/*===
+Statement Compound
+    BlockItem
+        Declaration
+            DeclarationSpecifier
+                StorageClassSpecifier Auto
+            DeclarationSpecifier
+                TypeSpecifier Void
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "g"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                            Ellipsis None
+    BlockItem
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "n"
+                Initializer
+                    Expression
+                        Constant
+                            Integer "1"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+    BlockItem
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "g"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Statement
+                        Expression
+                            UnaryOperatorExpression
+                                Expression
+                                    Identifier "n"
+                                UnaryOperator PostIncrement
+    BlockItem
+        Statement
+            Expression
+                CallExpression
+                    Expression
+                        Identifier "g"
+===*/
@@ -0,0 +1,24 @@
+#pragma comments
+{ x = a /* half */ / 2; /*@ assert x > 0; */ }
+
+/*===
+Statement Compound
+    BlockItem
+        Statement
+            Expression
+                BinaryOperatorExpression
+                    Expression
+                        Identifier "x"
+                    Expression
+                        BinaryOperatorExpression
+                            Expression
+                                Identifier "a"
+                            Expression
+                                Constant
+                                    Integer "2"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                            BinaryOperator Divide
+                    BinaryOperator Assign
+===*/
@@ -0,0 +1,5 @@
+{ x = 1; /* one */ }
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,16 @@
+#pragma gnu
+asm goto ("jmp %l0" : : "r" (x) : "cc" : out, err);
+
+/*===
+Statement
+    AsmStatement
+        GnuExtendedAsmStatement true
+            StringLiteral ["\"jmp %l0\""]
+            GnuAsmOperand
+                StringLiteral ["\"r\""]
+                Expression
+                    Identifier "x"
+            StringLiteral ["\"cc\""]
+            Identifier "out"
+            Identifier "err"
+===*/
@@ -0,0 +1,11 @@
+#pragma gnu
+asm volatile goto ("" :::: l);
+
+/*===
+Statement
+    AsmStatement
+        GnuExtendedAsmStatement true
+            TypeQualifier Volatile
+            StringLiteral ["\"\""]
+            Identifier "l"
+===*/
@@ -0,0 +1,6 @@
+#pragma gnu
+asm ("" :::: l);
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,258 @@
+// C11 5.2.4.1: 127 nesting levels of blocks
+{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{{}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}
+
+/*===
+Statement Compound
+    BlockItem
+        Statement Compound
+            BlockItem
+                Statement Compound
+                    BlockItem
+                        Statement Compound
+                            BlockItem
+                                Statement Compound
+                                    BlockItem
+                                        Statement Compound
+                                            BlockItem
+                                                Statement Compound
+                                                    BlockItem
+                                                        Statement Compound
+                                                            BlockItem
+                                                                Statement Compound
+                                                                    BlockItem
+                                                                        Statement Compound
+                                                                            BlockItem
+                                                                                Statement Compound
+                                                                                    BlockItem
+                                                                                        Statement Compound
+                                                                                            BlockItem
+                                                                                                Statement Compound
+                                                                                                    BlockItem
+                                                                                                        Statement Compound
+                                                                                                            BlockItem
+                                                                                                                Statement Compound
+                                                                                                                    BlockItem
+                                                                                                                        Statement Compound
+                                                                                                                            BlockItem
+                                                                                                                                Statement Compound
+                                                                                                                                    BlockItem
+                                                                                                                                        Statement Compound
+                                                                                                                                            BlockItem
+                                                                                                                                                Statement Compound
+                                                                                                                                                    BlockItem
+                                                                                                                                                        Statement Compound
+                                                                                                                                                            BlockItem
+                                                                                                                                                                Statement Compound
+                                                                                                                                                                    BlockItem
+                                                                                                                                                                        Statement Compound
+                                                                                                                                                                            BlockItem
+                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                    BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                        Statement Compound
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                            BlockItem
+                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                                Statement Compound
+===*/
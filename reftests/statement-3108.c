@@ -0,0 +1,11 @@
+#pragma gnu
+__asm__ goto __volatile__ ("" :::: l);
+
+/*===
+Statement
+    AsmStatement
+        GnuExtendedAsmStatement true
+            TypeQualifier Volatile
+            StringLiteral ["\"\""]
+            Identifier "l"
+===*/
@@ -0,0 +1,6 @@
+#pragma gnu
+asm volatile goto volatile ("" :::: l);
+
+/*===
+~ERROR
+===*/
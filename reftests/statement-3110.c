@@ -0,0 +1,6 @@
+#pragma gnu
+asm goto goto ("" :::: l);
+
+/*===
+~ERROR
+===*/
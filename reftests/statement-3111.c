@@ -0,0 +1,49 @@
+#pragma builtin_macros
+#pragma round_trip
+#pragma typedef va_list
+{ va_list aq; va_copy(aq, ap); sum += va_arg(aq, long); va_end(aq); }
+
+/*===
+Statement Compound
+    BlockItem
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier TypedefName
+                    Identifier "va_list"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "aq"
+    BlockItem
+        Statement
+            Expression
+                CallExpression
+                    Expression
+                        Identifier "va_copy"
+                    Expression
+                        Identifier "aq"
+                    Expression
+                        Identifier "ap"
+    BlockItem
+        Statement
+            Expression
+                BinaryOperatorExpression
+                    Expression
+                        Identifier "sum"
+                    Expression
+                        VaArgExpression
+                            Expression
+                                Identifier "aq"
+                            TypeName
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                    BinaryOperator AssignPlus
+    BlockItem
+        Statement
+            Expression
+                CallExpression
+                    Expression
+                        Identifier "va_end"
+                    Expression
+                        Identifier "aq"
+===*/
@@ -0,0 +1,52 @@
+#pragma c23
+#pragma round_trip
+switch (x) { case 1: f(); [[fallthrough]]; case 2: [[fallthrough]]; default: break; }
+
+/*===
+Statement
+    SwitchStatement
+        Expression
+            Identifier "x"
+        Statement Compound
+            BlockItem
+                Statement
+                    LabeledStatement
+                        Label
+                            Expression
+                                Constant
+                                    Integer "1"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                        Statement
+                            Expression
+                                CallExpression
+                                    Expression
+                                        Identifier "f"
+            BlockItem
+                Statement
+                    AttributedStatement
+                        Extension
+                            StandardAttribute "fallthrough"
+                        Statement
+            BlockItem
+                Statement
+                    LabeledStatement
+                        Label
+                            Expression
+                                Constant
+                                    Integer "2"
+                                        IntegerBase Decimal
+                                        IntegerSuffix false false
+                                            IntegerSize Int
+                        Statement
+                            AttributedStatement
+                                Extension
+                                    StandardAttribute "fallthrough"
+                                Statement
+            BlockItem
+                Statement
+                    LabeledStatement
+                        Label Default
+                        Statement Break
+===*/
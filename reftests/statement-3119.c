@@ -0,0 +1,15 @@
+#pragma c23
+#pragma round_trip
+[[likely]] if (x) {}
+
+/*===
+Statement
+    AttributedStatement
+        Extension
+            StandardAttribute "likely"
+        Statement
+            IfStatement
+                Expression
+                    Identifier "x"
+                Statement Compound
+===*/
@@ -5,6 +5,8 @@ __extension__ union { long l; };
 TranslationUnit
     ExternalDeclaration
         Declaration
+            DeclarationSpecifier
+                Extension Marker
             DeclarationSpecifier
                 TypeSpecifier
                     StructType
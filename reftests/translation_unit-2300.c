@@ -5,6 +5,7 @@ __extension__ _Static_assert(1,"ERR");
 TranslationUnit
     ExternalDeclaration
         StaticAssert
+            Extension Marker
             Expression
                 Constant
                     Integer "1"
@@ -0,0 +1,25 @@
+#ident "$Id: file.c,v 1.2$"
+int a;
+#sccs "@(#)file.c 1.2"
+# 12 "file.c" 2
+int b;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "a"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "b"
+===*/
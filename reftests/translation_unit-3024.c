@@ -0,0 +1,21 @@
+#pragma c23
+static_assert(sizeof(bool) == 1);
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        StaticAssert
+            Expression
+                BinaryOperatorExpression
+                    Expression
+                        TypeName
+                            SpecifierQualifier
+                                TypeSpecifier Bool
+                    Expression
+                        Constant
+                            Integer "1"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+                    BinaryOperator Equals
+===*/
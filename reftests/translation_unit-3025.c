@@ -0,0 +1,43 @@
+#pragma c23
+alignas(8) thread_local bool flag = true; static_assert(alignof(int) >= 1, "ok");
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                AlignmentSpecifier
+                    Expression
+                        Constant
+                            Integer "8"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+            DeclarationSpecifier
+                StorageClassSpecifier ThreadLocal
+            DeclarationSpecifier
+                TypeSpecifier Bool
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "flag"
+                Initializer
+                    Expression
+                        Constant Bool true
+    ExternalDeclaration
+        StaticAssert
+            Expression
+                BinaryOperatorExpression
+                    Expression
+                        TypeName
+                            SpecifierQualifier
+                                TypeSpecifier Int
+                    Expression
+                        Constant
+                            Integer "1"
+                                IntegerBase Decimal
+                                IntegerSuffix false false
+                                    IntegerSize Int
+                    BinaryOperator GreaterOrEqual
+            StringLiteral ["\"ok\""]
+===*/
@@ -0,0 +1,5 @@
+_Static_assert(1);
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,38 @@
+#pragma c23
+struct s { enum E : long x; enum E : 3; };
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "s"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        EnumType
+                                            Identifier "E"
+                                            SpecifierQualifier
+                                                TypeSpecifier Long
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "x"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    TypeSpecifier
+                                        EnumType
+                                            Identifier "E"
+                                StructDeclarator
+                                    Expression
+                                        Constant
+                                            Integer "3"
+                                                IntegerBase Decimal
+                                                IntegerSuffix false false
+                                                    IntegerSize Int
+===*/
@@ -0,0 +1,44 @@
+#pragma linkage
+extern "C" { typedef int T; T f(void); }
+T x;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        LinkageSpecification true
+            StringLiteral ["\"C\""]
+            ExternalDeclaration
+                Declaration
+                    DeclarationSpecifier
+                        StorageClassSpecifier Typedef
+                    DeclarationSpecifier
+                        TypeSpecifier Int
+                    InitDeclarator
+                        Declarator
+                            DeclaratorKind
+                                Identifier "T"
+            ExternalDeclaration
+                Declaration
+                    DeclarationSpecifier
+                        TypeSpecifier TypedefName
+                            Identifier "T"
+                    InitDeclarator
+                        Declarator
+                            DeclaratorKind
+                                Identifier "f"
+                            DerivedDeclarator
+                                FunctionDeclarator
+                                    ParameterDeclaration
+                                        DeclarationSpecifier
+                                            TypeSpecifier Void
+                                    Ellipsis None
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier TypedefName
+                    Identifier "T"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "x"
+===*/
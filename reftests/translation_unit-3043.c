@@ -0,0 +1,31 @@
+#pragma linkage
+extern "C" int g(void) { return 0; }
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        LinkageSpecification false
+            StringLiteral ["\"C\""]
+            ExternalDeclaration
+                FunctionDefinition
+                    DeclarationSpecifier
+                        TypeSpecifier Int
+                    Declarator
+                        DeclaratorKind
+                            Identifier "g"
+                        DerivedDeclarator
+                            FunctionDeclarator
+                                ParameterDeclaration
+                                    DeclarationSpecifier
+                                        TypeSpecifier Void
+                                Ellipsis None
+                    Statement Compound
+                        BlockItem
+                            Statement Return
+                                Expression
+                                    Constant
+                                        Integer "0"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+===*/
@@ -0,0 +1,5 @@
+extern "C" { int f(void); }
+
+/*===
+~ERROR
+===*/
@@ -0,0 +1,25 @@
+#pragma gnu
+#pragma is_typename __uint128_t
+__int128_t a; __uint128_t b;
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier TypedefName
+                    Identifier "__int128_t"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "a"
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier TypedefName
+                    Identifier "__uint128_t"
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "b"
+===*/
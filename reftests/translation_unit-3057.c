@@ -0,0 +1,58 @@
+#pragma gnu
+void f(void) { static void *t[] = { &&l }; goto *t[0]; l: return; }
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            StorageClassSpecifier Static
+                        DeclarationSpecifier
+                            TypeSpecifier Void
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "t"
+                                DerivedDeclarator
+                                DerivedDeclarator
+                                    ArrayDeclarator
+                                        ArraySize Unknown
+                            Initializer
+                                InitializerListItem
+                                    Initializer
+                                        Expression LabelAddress
+                                            Identifier "l"
+                BlockItem
+                    Statement GotoIndirect
+                        Expression
+                            BinaryOperatorExpression
+                                Expression
+                                    Identifier "t"
+                                Expression
+                                    Constant
+                                        Integer "0"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                                BinaryOperator Index
+                BlockItem
+                    Statement
+                        LabeledStatement
+                            Label
+                                Identifier "l"
+                            Statement Return
+===*/
@@ -0,0 +1,57 @@
+#pragma gnu
+int f(int a) { int sq(int x) { return x * x; } return sq(a); }
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Int
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    FunctionDefinition
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        Declarator
+                            DeclaratorKind
+                                Identifier "sq"
+                            DerivedDeclarator
+                                FunctionDeclarator
+                                    ParameterDeclaration
+                                        DeclarationSpecifier
+                                            TypeSpecifier Int
+                                        Declarator
+                                            DeclaratorKind
+                                                Identifier "x"
+                                    Ellipsis None
+                        Statement Compound
+                            BlockItem
+                                Statement Return
+                                    Expression
+                                        BinaryOperatorExpression
+                                            Expression
+                                                Identifier "x"
+                                            Expression
+                                                Identifier "x"
+                                            BinaryOperator Multiply
+                BlockItem
+                    Statement Return
+                        Expression
+                            CallExpression
+                                Expression
+                                    Identifier "sq"
+                                Expression
+                                    Identifier "a"
+===*/
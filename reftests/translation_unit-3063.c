@@ -0,0 +1,5 @@
+int f(int a) { int sq(int x) { return x * x; } return sq(a); }
+
+/*===
+~ERROR
+===*/
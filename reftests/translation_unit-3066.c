@@ -0,0 +1,14 @@
+#pragma comments
+int /* count */ n; // total
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "n"
+===*/
@@ -0,0 +1,41 @@
+#pragma sal
+_Check_return_ int f(_In_reads_(n) const int *a, __in_opt void *p, _Outptr_result_maybenull_ char **out);
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "f"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeQualifier Const
+                                DeclarationSpecifier
+                                    TypeSpecifier Int
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "a"
+                                    DerivedDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Void
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "p"
+                                    DerivedDeclarator
+                            ParameterDeclaration
+                                DeclarationSpecifier
+                                    TypeSpecifier Char
+                                Declarator
+                                    DeclaratorKind
+                                        Identifier "out"
+                                    DerivedDeclarator
+                                    DerivedDeclarator
+                            Ellipsis None
+===*/
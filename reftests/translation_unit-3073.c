@@ -0,0 +1,5 @@
+int f(_In_ int x);
+
+/*===
+~ERROR
+===*/
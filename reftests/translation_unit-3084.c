@@ -0,0 +1,35 @@
+#pragma gnu
+struct s { __extension__ long long x; __extension__ _Static_assert(1, ""); };
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier
+                    StructType
+                        StructKind Struct
+                        Identifier "s"
+                        StructDeclaration
+                            StructField
+                                SpecifierQualifier
+                                    Extension Marker
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                                SpecifierQualifier
+                                    TypeSpecifier Long
+                                StructDeclarator
+                                    Declarator
+                                        DeclaratorKind
+                                            Identifier "x"
+                        StructDeclaration
+                            StaticAssert
+                                Extension Marker
+                                Expression
+                                    Constant
+                                        Integer "1"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                                StringLiteral ["\"\""]
+===*/
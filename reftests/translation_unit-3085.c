@@ -0,0 +1,52 @@
+#pragma gnu
+__extension__ static int f(void) { __extension__ int a = 1; return __extension__ ({ a; }); }
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                Extension Marker
+            DeclarationSpecifier
+                StorageClassSpecifier Static
+            DeclarationSpecifier
+                TypeSpecifier Int
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            Extension Marker
+                        DeclarationSpecifier
+                            TypeSpecifier Int
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "a"
+                            Initializer
+                                Expression
+                                    Constant
+                                        Integer "1"
+                                            IntegerBase Decimal
+                                            IntegerSuffix false false
+                                                IntegerSize Int
+                BlockItem
+                    Statement Return
+                        Expression
+                            UnaryOperatorExpression
+                                UnaryOperator Extension
+                                Expression
+                                    Statement Compound
+                                        BlockItem
+                                            Statement
+                                                Expression
+                                                    Identifier "a"
+===*/
@@ -0,0 +1,36 @@
+#pragma gnu
+void f(void) { const __auto_type p = &x; }
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        FunctionDefinition
+            DeclarationSpecifier
+                TypeSpecifier Void
+            Declarator
+                DeclaratorKind
+                    Identifier "f"
+                DerivedDeclarator
+                    FunctionDeclarator
+                        ParameterDeclaration
+                            DeclarationSpecifier
+                                TypeSpecifier Void
+                        Ellipsis None
+            Statement Compound
+                BlockItem
+                    Declaration
+                        DeclarationSpecifier
+                            TypeQualifier Const
+                        DeclarationSpecifier
+                            TypeSpecifier AutoType
+                        InitDeclarator
+                            Declarator
+                                DeclaratorKind
+                                    Identifier "p"
+                            Initializer
+                                Expression
+                                    UnaryOperatorExpression
+                                        UnaryOperator Address
+                                        Expression
+                                            Identifier "x"
+===*/
@@ -0,0 +1,7 @@
+#pragma gnu
+// 130 nesting levels of nested functions, past the default limit
+void f0(void) { void f1(void) { void f2(void) { void f3(void) { void f4(void) { void f5(void) { void f6(void) { void f7(void) { void f8(void) { void f9(void) { void f10(void) { void f11(void) { void f12(void) { void f13(void) { void f14(void) { void f15(void) { void f16(void) { void f17(void) { void f18(void) { void f19(void) { void f20(void) { void f21(void) { void f22(void) { void f23(void) { void f24(void) { void f25(void) { void f26(void) { void f27(void) { void f28(void) { void f29(void) { void f30(void) { void f31(void) { void f32(void) { void f33(void) { void f34(void) { void f35(void) { void f36(void) { void f37(void) { void f38(void) { void f39(void) { void f40(void) { void f41(void) { void f42(void) { void f43(void) { void f44(void) { void f45(void) { void f46(void) { void f47(void) { void f48(void) { void f49(void) { void f50(void) { void f51(void) { void f52(void) { void f53(void) { void f54(void) { void f55(void) { void f56(void) { void f57(void) { void f58(void) { void f59(void) { void f60(void) { void f61(void) { void f62(void) { void f63(void) { void f64(void) { void f65(void) { void f66(void) { void f67(void) { void f68(void) { void f69(void) { void f70(void) { void f71(void) { void f72(void) { void f73(void) { void f74(void) { void f75(void) { void f76(void) { void f77(void) { void f78(void) { void f79(void) { void f80(void) { void f81(void) { void f82(void) { void f83(void) { void f84(void) { void f85(void) { void f86(void) { void f87(void) { void f88(void) { void f89(void) { void f90(void) { void f91(void) { void f92(void) { void f93(void) { void f94(void) { void f95(void) { void f96(void) { void f97(void) { void f98(void) { void f99(void) { void f100(void) { void f101(void) { void f102(void) { void f103(void) { void f104(void) { void f105(void) { void f106(void) { void f107(void) { void f108(void) { void f109(void) { void f110(void) { void f111(void) { void f112(void) { void f113(void) { void f114(void) { void f115(void) { void f116(void) { void f117(void) { void f118(void) { void f119(void) { void f120(void) { void f121(void) { void f122(void) { void f123(void) { void f124(void) { void f125(void) { void f126(void) { void f127(void) { void f128(void) { void f129(void) { }}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}}
+
+/*===
+~ERROR
+===*/
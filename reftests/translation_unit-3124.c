@@ -0,0 +1,28 @@
+#pragma c23
+#pragma round_trip
+[[gnu::visibility("hidden")]];
+int f() [[deprecated]];
+
+/*===
+TranslationUnit
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                Extension
+                    StandardAttribute "gnu::visibility"
+                        Expression
+                            StringLiteral ["\"hidden\""]
+    ExternalDeclaration
+        Declaration
+            DeclarationSpecifier
+                TypeSpecifier Int
+            InitDeclarator
+                Declarator
+                    DeclaratorKind
+                        Identifier "f"
+                    DerivedDeclarator
+                        FunctionDeclarator
+                            Ellipsis None
+                            Extension
+                                StandardAttribute "deprecated"
+===*/
@@ -0,0 +1,216 @@
+//! Annotation comments
+//!
+//! Specification languages such as ACSL write contracts, loop invariants and assertions in
+//! comments starting with `@`, placed before the function or statement they describe. When the
+//! source was parsed with `Config::comments`, `annotations` passes the contents of every comment
+//! to a callback that parses the ones it understands into values of its own type, and attaches
+//! each value to the function definition, declaration or statement that follows the comment.
+//!
+//! ```
+//! use lang_c::annotations::{annotations, Target};
+//! use lang_c::driver::{parse_preprocessed, Config};
+//!
+//! let source = r#"
+//!     /*@ requires n >= 0; */
+//!     int sum(int n) {
+//!         int s = 0; // running total
+//!         //@ loop invariant 0 <= i <= n;
+//!         for (int i = 0; i < n; i++) s += i;
+//!         return s;
+//!     }
+//! "#;
+//! let mut config = Config::default();
+//! config.comments = true;
+//! let parse = parse_preprocessed(&config, source.into()).unwrap();
+//!
+//! let found = annotations(&parse.source, &parse.unit, |text, _| {
+//!     if text.starts_with('@') {
+//!         Some(text[1..].trim().to_owned())
+//!     } else {
+//!         None
+//!     }
+//! });
+//! assert_eq!(found.len(), 2);
+//! assert_eq!(found[0].value, "requires n >= 0;");
+//! match found[0].target {
+//!     Some(Target::FunctionDefinition(..)) => {}
+//!     _ => panic!("contract is not attached to the function"),
+//! }
+//! assert_eq!(found[1].value, "loop invariant 0 <= i <= n;");
+//! match found[1].target {
+//!     Some(Target::Statement(..)) => {}
+//!     _ => panic!("invariant is not attached to the loop"),
+//! }
+//! ```
+//!
+//! A comment is attached to the outermost node that starts at the first token after it, if only
+//! whitespace, other comments and directives lie between them. A comment at the end of a block
+//! or of the file has no target.
+
+use std::collections::HashMap;
+
+use ast::*;
+use span::Span;
+use visit::{self, Visit};
+
+/// Comment accepted by the callback of `annotations`
+#[derive(Debug, Clone)]
+pub struct Annotation<'ast, T> {
+    /// Location of the whole comment, including its delimiters
+    pub span: Span,
+    /// Value returned by the callback
+    pub value: T,
+    /// Node the comment is placed before
+    pub target: Option<Target<'ast>>,
+}
+
+/// Node an annotation is attached to, with its location
+#[derive(Debug, Clone, Copy)]
+pub enum Target<'ast> {
+    FunctionDefinition(&'ast FunctionDefinition, Span),
+    Declaration(&'ast Declaration, Span),
+    Statement(&'ast Statement, Span),
+}
+
+/// Parse the comments in `source` with `parse` and attach the results to nodes of `unit`
+///
+/// The callback receives the text of a comment without its `/*`, `*/` or `//` delimiters and the
+/// location of that text, and returns `None` for comments that are not annotations.
+pub fn annotations<'ast, T, F>(
+    source: &str,
+    unit: &'ast TranslationUnit,
+    mut parse: F,
+) -> Vec<Annotation<'ast, T>>
+where
+    F: FnMut(&str, Span) -> Option<T>,
+{
+    let mut targets = Targets(HashMap::new());
+    targets.visit_translation_unit(unit);
+
+    let mut found = Vec::new();
+    for comment in comments(source) {
+        let text = comment.text;
+        if let Some(value) = parse(&source[text.start..text.end], text) {
+            found.push(Annotation {
+                span: comment.span,
+                value,
+                target: comment.next.and_then(|n| targets.0.get(&n).cloned()),
+            });
+        }
+    }
+    found
+}
+
+struct Comment {
+    span: Span,
+    text: Span,
+    /// Start of the first token after the comment
+    next: Option<usize>,
+}
+
+/// Comments in source text, skipping string and character literals and directives
+fn comments(source: &str) -> Vec<Comment> {
+    let bytes = source.as_bytes();
+    let mut comments: Vec<Comment> = Vec::new();
+    // Comments not followed by a token yet
+    let mut pending = 0;
+    let mut line_start = true;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\n' => {
+                line_start = true;
+                i += 1;
+            }
+            b' ' | b'\t' | b'\r' | b'\x0b' | b'\x0c' => i += 1,
+            b'#' if line_start => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = match source[i + 2..].find("*/") {
+                    Some(n) => i + 2 + n,
+                    None => bytes.len(),
+                };
+                comments.push(Comment {
+                    span: Span::span(i, (end + 2).min(bytes.len())),
+                    text: Span::span(i + 2, end),
+                    next: None,
+                });
+                i = end + 2;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = match source[i..].find('\n') {
+                    Some(n) => i + n,
+                    None => bytes.len(),
+                };
+                comments.push(Comment {
+                    span: Span::span(i, end),
+                    text: Span::span(i + 2, end),
+                    next: None,
+                });
+                i = end;
+            }
+            c => {
+                line_start = false;
+                for comment in &mut comments[pending..] {
+                    comment.next = Some(i);
+                }
+                pending = comments.len();
+                i = if c == b'"' || c == b'\'' {
+                    quoted_end(bytes, i)
+                } else {
+                    i + 1
+                };
+            }
+        }
+    }
+    comments
+}
+
+fn quoted_end(bytes: &[u8], open: usize) -> usize {
+    let quote = bytes[open];
+    let mut i = open + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\n' => return i,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Outermost node starting at each offset
+struct Targets<'ast>(HashMap<usize, Target<'ast>>);
+
+impl<'ast> Targets<'ast> {
+    fn add(&mut self, target: Target<'ast>, span: &Span) {
+        if !span.is_none() {
+            self.0.entry(span.start).or_insert(target);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Targets<'ast> {
+    fn visit_function_definition(
+        &mut self,
+        function_definition: &'ast FunctionDefinition,
+        span: &'ast Span,
+    ) {
+        self.add(Target::FunctionDefinition(function_definition, *span), span);
+        visit::visit_function_definition(self, function_definition, span);
+    }
+
+    fn visit_declaration(&mut self, declaration: &'ast Declaration, span: &'ast Span) {
+        self.add(Target::Declaration(declaration, *span), span);
+        visit::visit_declaration(self, declaration, span);
+    }
+
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        self.add(Target::Statement(statement, *span), span);
+        visit::visit_statement(self, statement, span);
+    }
+}
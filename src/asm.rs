@@ -0,0 +1,171 @@
+//! Constraint strings of GNU extended asm operands
+//!
+//! Operand constraints such as `"=&r"` or `"+rm,0"` are kept as string literals in the syntax
+//! tree. This module decodes them into the access mode of the operand and the alternatives it
+//! can be placed in.
+//!
+//! ```
+//! use lang_c::asm::{Access, Constraint, Item};
+//!
+//! let output = Constraint::parse("+r,m").unwrap();
+//! assert_eq!(output.access, Access::ReadWrite);
+//! assert_eq!(output.alternatives[0].items, [Item::Letter('r')]);
+//! assert_eq!(output.alternatives[1].items, [Item::Letter('m')]);
+//!
+//! let input = Constraint::parse("g,0").unwrap();
+//! assert_eq!(input.access, Access::Read);
+//! assert_eq!(input.alternatives[1].items, [Item::Matching(0)]);
+//!
+//! let scratch = Constraint::parse("=&r").unwrap();
+//! assert!(scratch.alternatives[0].early_clobber);
+//! ```
+
+use ast::GnuAsmOperand;
+
+/// Whether an operand is read, written or both
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// No modifier, an input operand
+    Read,
+    /// `=`, an output operand
+    Write,
+    /// `+`, an operand that is both read and written
+    ReadWrite,
+}
+
+/// Decoded operand constraint
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint {
+    pub access: Access,
+    /// `%`, the operand may be swapped with the following one
+    pub commutative: bool,
+    /// Alternatives separated by `,`, all operands of a statement must have the same number
+    pub alternatives: Vec<Alternative>,
+}
+
+/// Single alternative of a constraint
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Alternative {
+    /// `&`, the operand is written before all inputs are consumed
+    pub early_clobber: bool,
+    /// Places the operand may be in, any of which is acceptable
+    pub items: Vec<Item>,
+}
+
+/// Element of a constraint alternative
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    /// Register class or operand kind such as `r`, `m`, `i` or `g`
+    ///
+    /// Machine specific constraints spelled with several letters appear as consecutive items.
+    Letter(char),
+    /// Digit, the operand must be in the same place as the numbered operand
+    Matching(usize),
+    /// `[name]`, the operand must be in the same place as the named operand
+    MatchingName(String),
+    /// Register allocation hint such as `?`, `!`, `*` or `#`
+    Hint(char),
+}
+
+impl Constraint {
+    /// Decode the text of a constraint string
+    ///
+    /// Returns `None` for a malformed constraint, such as one with an unterminated operand
+    /// name.
+    pub fn parse(text: &str) -> Option<Constraint> {
+        let mut chars = text.chars().peekable();
+        let mut constraint = Constraint {
+            access: Access::Read,
+            commutative: false,
+            alternatives: vec![Alternative::default()],
+        };
+
+        while let Some(c) = chars.next() {
+            let alternative = constraint.alternatives.last_mut().unwrap();
+            match c {
+                '=' => constraint.access = Access::Write,
+                '+' => constraint.access = Access::ReadWrite,
+                '%' => constraint.commutative = true,
+                '&' => alternative.early_clobber = true,
+                ',' => constraint.alternatives.push(Alternative::default()),
+                '?' | '!' | '*' | '#' => alternative.items.push(Item::Hint(c)),
+                '0'..='9' => {
+                    let mut number = c.to_digit(10).unwrap() as usize;
+                    while let Some(d) = chars.peek().and_then(|c| c.to_digit(10)) {
+                        number = number.checked_mul(10)?.checked_add(d as usize)?;
+                        chars.next();
+                    }
+                    alternative.items.push(Item::Matching(number));
+                }
+                '[' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next()? {
+                            ']' => break,
+                            c => name.push(c),
+                        }
+                    }
+                    alternative.items.push(Item::MatchingName(name));
+                }
+                c if c.is_whitespace() => {}
+                c => alternative.items.push(Item::Letter(c)),
+            }
+        }
+
+        Some(constraint)
+    }
+}
+
+impl GnuAsmOperand {
+    /// Decoded constraint of the operand, see `constraint`
+    ///
+    /// ```
+    /// use lang_c::asm::Access;
+    /// use lang_c::ast::GnuAsmOperand;
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    /// use lang_c::span::Span;
+    /// use lang_c::visit::Visit;
+    ///
+    /// struct Operands(Vec<Access>);
+    ///
+    /// impl<'ast> Visit<'ast> for Operands {
+    ///     fn visit_gnu_asm_operand(&mut self, operand: &'ast GnuAsmOperand, _: &'ast Span) {
+    ///         self.0.push(operand.constraint().unwrap().access);
+    ///     }
+    /// }
+    ///
+    /// let source = r#"void f(int x, int y) { __asm__("add %1, %0" : "+r"(x) : "g"(y)); }"#;
+    /// let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+    /// let mut operands = Operands(Vec::new());
+    /// operands.visit_translation_unit(&parse.unit);
+    /// assert_eq!(operands.0, [Access::ReadWrite, Access::Read]);
+    /// ```
+    pub fn constraint(&self) -> Option<Constraint> {
+        constraint(self)
+    }
+}
+
+/// Decode the constraint of an operand
+///
+/// Returns `None` if the constraint string is malformed or uses escape sequences other than
+/// `\\` and `\"`.
+pub fn constraint(operand: &GnuAsmOperand) -> Option<Constraint> {
+    let mut text = String::new();
+    for part in &operand.constraints.node {
+        let inner = part.trim_start_matches(|c| c != '"');
+        if inner.len() < 2 || !inner.ends_with('"') {
+            return None;
+        }
+        let mut chars = inner[1..inner.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next()? {
+                    c @ '\\' | c @ '"' => text.push(c),
+                    _ => return None,
+                },
+                c => text.push(c),
+            }
+        }
+    }
+    Constraint::parse(&text)
+}
@@ -42,7 +42,15 @@ pub struct Identifier {
 pub enum Constant {
     Integer(Integer),
     Float(Float),
-    Character(String),
+    Character(Character),
+    /// `nullptr`
+    ///
+    /// (C23 6.4.4.5)
+    Nullptr,
+    /// `true` or `false`
+    ///
+    /// (C23 6.4.4.5)
+    Bool(bool),
 }
 
 /// Integer number literal
@@ -63,6 +71,8 @@ pub enum IntegerBase {
     Decimal,
     Octal,
     Hexadecimal,
+    /// (C23 6.4.4.1)
+    ///
     /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Binary-constants.html)
     Binary,
 }
@@ -93,6 +103,10 @@ pub enum IntegerSize {
     Long,
     /// `ll`
     LongLong,
+    /// `wb`, bit-precise integer just wide enough for the value
+    ///
+    /// (C23 6.4.4.1)
+    BitInt,
 }
 
 /// Floating point number literal
@@ -142,9 +156,40 @@ pub enum FloatFormat {
     ///
     /// [ISO/IEC TS 18661-3:2015](http://www.open-std.org/jtc1/sc22/wg14/www/docs/n1945.pdf)
     /// `fN`, `fNx`, `dN`, `dNx` suffixes
+    ///
+    /// Decimal suffixes are rejected when `Config::decimal_floats` is disabled.
     TS18661Format(TS18661FloatType),
 }
 
+/// Character constant
+///
+/// (C11 6.4.4.4)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Character {
+    pub encoding: CharacterEncoding,
+    /// Characters between the quotes, escape sequences included as written
+    pub body: String,
+}
+
+/// Encoding prefix of a character constant
+///
+/// (C11 6.4.4.4)
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum CharacterEncoding {
+    /// no prefix, type `int`
+    Plain,
+    /// `L` prefix, type `wchar_t`
+    Wide,
+    /// `u8` prefix, type `unsigned char`
+    ///
+    /// (C23 6.4.4.4)
+    Utf8,
+    /// `u` prefix, type `char16_t`
+    Utf16,
+    /// `U` prefix, type `char32_t`
+    Utf32,
+}
+
 /// String literal
 ///
 /// (C11 6.4.5)
@@ -209,6 +254,8 @@ pub enum Expression {
 
     /// Alignment of a type
     ///
+    /// Note: alignment of an expression is represented with `UnaryOperator::AlignOf`.
+    ///
     /// (C11 6.5.3)
     AlignOf(Box<Node<TypeName>>),
 
@@ -258,10 +305,27 @@ pub enum Expression {
     /// (C11 7.16.1.1).
     VaArg(Box<Node<VaArgExpression>>),
 
+    /// Type compatibility test
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Other-Builtins.html)
+    TypesCompatible(Box<Node<TypesCompatibleExpression>>),
+
+    /// Compile-time choice between two expressions
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Other-Builtins.html)
+    Choose(Box<Node<ChooseExpression>>),
+
     /// Statement expression
     ///
     /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Statement-Exprs.html)
     Statement(Box<Node<Statement>>),
+
+    /// Address of a label
+    ///
+    /// `&&label`, used as the target of `Statement::GotoIndirect`.
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Labels-as-Values.html)
+    LabelAddress(Box<Node<Identifier>>),
 }
 
 /// Struct or union member access
@@ -357,6 +421,22 @@ pub enum UnaryOperator {
     Negate,
     /// `sizeof operand`
     SizeOf,
+    /// `__alignof__ operand`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Alignment.html)
+    AlignOf,
+    /// `__real__ operand`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Complex.html)
+    Real,
+    /// `__imag__ operand`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Complex.html)
+    Imag,
+    /// `__extension__ operand`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Alternate-Keywords.html)
+    Extension,
 }
 
 /// Unary operator expression
@@ -467,7 +547,10 @@ pub struct BinaryOperatorExpression {
 #[derive(Debug, PartialEq, Clone)]
 pub struct ConditionalExpression {
     pub condition: Box<Node<Expression>>,
-    pub then_expression: Box<Node<Expression>>,
+    /// Missing when the value of the condition is the result if it is true
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Conditionals.html)
+    pub then_expression: Option<Box<Node<Expression>>>,
     pub else_expression: Box<Node<Expression>>,
 }
 
@@ -482,6 +565,33 @@ pub struct VaArgExpression {
     pub type_name: Node<TypeName>,
 }
 
+/// Type compatibility test
+///
+/// `__builtin_types_compatible_p(lhs, rhs)`, an integer constant that is 1 if the unqualified
+/// types are compatible and 0 otherwise.
+///
+/// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Other-Builtins.html)
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypesCompatibleExpression {
+    pub lhs: Node<TypeName>,
+    pub rhs: Node<TypeName>,
+}
+
+/// Compile-time choice between two expressions
+///
+/// `__builtin_choose_expr(condition, then_expression, else_expression)` is the expression
+/// selected by the integer constant `condition`, including its type and lvalue-ness. Unlike
+/// with a conditional, the other expression is not evaluated and need not have a compatible
+/// type.
+///
+/// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Other-Builtins.html)
+#[derive(Debug, PartialEq, Clone)]
+pub struct ChooseExpression {
+    pub condition: Box<Node<Expression>>,
+    pub then_expression: Box<Node<Expression>>,
+    pub else_expression: Box<Node<Expression>>,
+}
+
 /// Member offset expression
 ///
 /// Result of expansion of `offsetof` macro.
@@ -536,7 +646,9 @@ pub enum DeclarationSpecifier {
     Function(Node<FunctionSpecifier>),
     Alignment(Node<AlignmentSpecifier>),
     /// Vendor-specific declaration extensions that can be mixed with standard specifiers
-    Extension(Vec<Node<Extension>>),
+    ///
+    /// Each attribute in `__attribute__((a, b))` is a separate specifier, in source order.
+    Extension(Node<Extension>),
 }
 
 /// Defines a single name in a declaration
@@ -562,11 +674,15 @@ pub enum StorageClassSpecifier {
     /// `static`
     Static,
     /// `_Thread_local`
+    ///
+    /// `__thread` ([GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Thread-Local.html))
     ThreadLocal,
     /// `auto`
     Auto,
     /// `register`
     Register,
+    /// `constexpr` (C23 6.7.1)
+    Constexpr,
 }
 
 // From 6.7.2
@@ -614,8 +730,16 @@ pub enum TypeSpecifier {
     TypedefName(Node<Identifier>),
     /// Specifies type of another type or expression
     ///
+    /// `typeof` (C23 6.7.2.5)
+    ///
     /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Typeof.html)
     TypeOf(Node<TypeOf>),
+    /// Specifies type of another type or expression with qualifiers removed
+    ///
+    /// `typeof_unqual` (C23 6.7.2.5)
+    ///
+    /// `__typeof_unqual`, `__typeof_unqual__` (GNU extension)
+    TypeOfUnqual(Node<TypeOf>),
     /// Floating point types with guaranteed width and representation
     ///
     /// `_Float16`, `_Float32`, `_Float64`, `_Float128`
@@ -626,8 +750,26 @@ pub enum TypeSpecifier {
     ///
     /// `_Decimal16x`, `_Decimal32x`, `_Decimal64x`, `_Decimal128x`
     ///
+    /// Decimal types are rejected when `Config::decimal_floats` is disabled.
+    ///
     /// [ISO/IEC TS 18661-3:2015](http://www.open-std.org/jtc1/sc22/wg14/www/docs/n1945.pdf)
     TS18661Float(TS18661FloatType),
+    /// `_BitInt(N)`, integer with the given width
+    ///
+    /// (C23 6.7.2)
+    BitInt(Node<Expression>),
+    /// `__int128`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/_005f_005fint128.html)
+    Int128,
+    /// `__float128`, distinct from `_Float128` even where both use the same format
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Floating-Types.html)
+    Float128,
+    /// `__auto_type`, type of the initializer of the declared object
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Typeof.html)
+    AutoType,
 }
 
 /// Floating point type with guaranteed width and format
@@ -663,6 +805,8 @@ pub struct StructType {
     ///
     /// A [GNU extension](https://gcc.gnu.org/onlinedocs/gcc-8.1.0/gcc/Empty-Structures.html) allows the list to be empty.
     pub declarations: Option<Vec<Node<StructDeclaration>>>,
+    /// C23 attributes after the `struct` or `union` keyword
+    pub extensions: Vec<Node<Extension>>,
 }
 
 /// The only difference between a `struct` and a `union`
@@ -699,6 +843,8 @@ pub struct StructField {
 pub enum SpecifierQualifier {
     TypeSpecifier(Node<TypeSpecifier>),
     TypeQualifier(Node<TypeQualifier>),
+    /// Attributes of a struct or union member, one per specifier
+    Extension(Node<Extension>),
 }
 
 /// Field declarator for a struct or a union
@@ -718,7 +864,13 @@ pub struct StructDeclarator {
 #[derive(Debug, PartialEq, Clone)]
 pub struct EnumType {
     pub identifier: Option<Node<Identifier>>,
+    /// Fixed underlying type, `enum E : unsigned short`
+    ///
+    /// (C23 6.7.2.2)
+    pub underlying: Option<Vec<Node<SpecifierQualifier>>>,
     pub enumerators: Vec<Node<Enumerator>>,
+    /// C23 attributes after the `enum` keyword
+    pub extensions: Vec<Node<Extension>>,
 }
 
 /// Single constant inside a `enum` definition
@@ -728,6 +880,7 @@ pub struct EnumType {
 pub struct Enumerator {
     pub identifier: Node<Identifier>,
     pub expression: Option<Box<Node<Expression>>>,
+    pub extensions: Vec<Node<Extension>>,
 }
 
 // From 6.7.3
@@ -850,13 +1003,18 @@ pub enum DerivedDeclarator {
 pub struct ArrayDeclarator {
     pub qualifiers: Vec<Node<TypeQualifier>>,
     pub size: ArraySize,
+    /// C23 attributes after the closing bracket, which apply to the array type
+    pub extensions: Vec<Node<Extension>>,
 }
 
 /// Function parameter part of a declarator
 #[derive(Debug, PartialEq, Clone)]
 pub struct FunctionDeclarator {
+    /// Empty for `(...)`, a C23 variadic function without named parameters
     pub parameters: Vec<Node<ParameterDeclaration>>,
     pub ellipsis: Ellipsis,
+    /// C23 attributes after the closing parenthesis, which apply to the function type
+    pub extensions: Vec<Node<Extension>>,
 }
 
 /// List of qualifiers that can follow a `*` in a declaration
@@ -926,6 +1084,7 @@ pub struct TypeName {
 #[derive(Debug, PartialEq, Clone)]
 pub enum Initializer {
     Expression(Box<Node<Expression>>),
+    /// Braced list, empty for `{}` (C23 6.7.10)
     List(Vec<Node<InitializerListItem>>),
 }
 
@@ -980,8 +1139,11 @@ pub struct RangeDesignator {
 /// (C11 6.7.10)
 #[derive(Debug, PartialEq, Clone)]
 pub struct StaticAssert {
+    /// `__extension__` keywords before the assertion
+    pub extensions: Vec<Node<Extension>>,
     pub expression: Box<Node<Expression>>,
-    pub message: Node<StringLiteral>,
+    /// Message to report if the assertion fails, optional since C23
+    pub message: Option<Node<StringLiteral>>,
 }
 
 // From 6.8 Statement
@@ -1000,11 +1162,17 @@ pub enum Statement {
     DoWhile(Node<DoWhileStatement>),
     For(Node<ForStatement>),
     Goto(Node<Identifier>),
+    /// Jump to a computed label address, `goto *expression;`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Labels-as-Values.html)
+    GotoIndirect(Box<Node<Expression>>),
     Continue,
     Break,
     Return(Option<Box<Node<Expression>>>),
     /// Vendor specific inline assembly extensions
     Asm(Node<AsmStatement>),
+    /// Statement with C23 attributes, `[[likely]] if (x) {}`
+    Attributed(Node<AttributedStatement>),
 }
 
 /// Labeled statement
@@ -1016,6 +1184,18 @@ pub struct LabeledStatement {
     pub statement: Box<Node<Statement>>,
 }
 
+/// Statement with C23 attributes
+///
+/// An attribute declaration in a function body, such as `[[fallthrough]];`, is parsed as
+/// attributes on an empty expression statement, so that it can follow a label.
+///
+/// (C23 6.8)
+#[derive(Debug, PartialEq, Clone)]
+pub struct AttributedStatement {
+    pub extensions: Vec<Node<Extension>>,
+    pub statement: Box<Node<Statement>>,
+}
+
 /// If statement
 ///
 /// (C11 6.8.4)
@@ -1102,6 +1282,14 @@ pub enum BlockItem {
     Declaration(Node<Declaration>),
     StaticAssert(Node<StaticAssert>),
     Statement(Node<Statement>),
+    /// Local label declaration, `__label__ a, b;`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Local-Labels.html)
+    LocalLabels(Vec<Node<Identifier>>),
+    /// Nested function definition
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Nested-Functions.html)
+    FunctionDefinition(Node<FunctionDefinition>),
 }
 
 // From 6.9 External definitions
@@ -1120,6 +1308,8 @@ pub enum ExternalDeclaration {
     Declaration(Node<Declaration>),
     StaticAssert(Node<StaticAssert>),
     FunctionDefinition(Node<FunctionDefinition>),
+    /// C++ linkage specification, accepted when enabled in the configuration
+    LinkageSpecification(Node<LinkageSpecification>),
 }
 
 /// Function definition
@@ -1137,6 +1327,21 @@ pub struct FunctionDefinition {
     pub statement: Node<Statement>,
 }
 
+/// Declarations with the linkage of another language
+///
+/// `extern "C" { ... }` or `extern "C" declaration` from a header shared with C++, usually
+/// guarded by `#ifdef __cplusplus` when the header is preprocessed.
+///
+/// (C++17 10.5)
+#[derive(Debug, PartialEq, Clone)]
+pub struct LinkageSpecification {
+    /// Language name such as `"C"`
+    pub language: Node<StringLiteral>,
+    pub declarations: Vec<Node<ExternalDeclaration>>,
+    /// Whether the declarations are enclosed in braces
+    pub braced: bool,
+}
+
 // Syntax extensions
 
 /// Extended vendor-specific syntax that does not fit elsewhere
@@ -1150,10 +1355,26 @@ pub enum Extension {
     ///
     /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Asm-Labels.html)
     AsmLabel(Node<StringLiteral>),
+    /// Hardware register of a register variable
+    ///
+    /// Written like an assembler name, on a declarator of a declaration with the `register`
+    /// storage class.
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Explicit-Register-Variables.html)
+    AsmRegister(Node<StringLiteral>),
     /// Platform availability
     ///
     /// [Clang extension](https://clang.llvm.org/docs/AttributeReference.html#availability)
     AvailabilityAttribute(Node<AvailabilityAttribute>),
+    /// Attribute in the standard `[[...]]` syntax
+    ///
+    /// (C23 6.7.13)
+    StandardAttribute(StandardAttribute),
+    /// `__extension__` keyword before a declaration, function definition, struct member or
+    /// static assertion
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Alternate-Keywords.html)
+    Marker,
 }
 
 /// Attributes
@@ -1165,6 +1386,22 @@ pub struct Attribute {
     pub arguments: Vec<Node<Expression>>,
 }
 
+/// Attribute in the standard `[[...]]` syntax
+///
+/// Arguments are parsed as expressions, which covers the standard attributes and the common
+/// vendor ones. Other balanced token sequences, which the standard allows as arguments of vendor
+/// attributes, are not accepted.
+///
+/// (C23 6.7.13)
+#[derive(Debug, PartialEq, Clone)]
+pub struct StandardAttribute {
+    /// Vendor prefix, such as `gnu` in `[[gnu::aligned(8)]]`
+    pub prefix: Option<Node<String>>,
+    pub name: Node<String>,
+    /// Arguments in parentheses, `None` if there are no parentheses
+    pub arguments: Option<Vec<Node<Expression>>>,
+}
+
 /// Platform availability attribute
 ///
 /// [Clang extension](https://clang.llvm.org/docs/AttributeReference.html#availability)
@@ -1217,10 +1454,15 @@ pub enum AsmStatement {
 #[derive(Debug, PartialEq, Clone)]
 pub struct GnuExtendedAsmStatement {
     pub qualifier: Option<Node<TypeQualifier>>,
+    /// `asm goto`, which may jump to one of `labels`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Extended-Asm.html#GotoLabels)
+    pub goto: bool,
     pub template: Node<StringLiteral>,
     pub outputs: Vec<Node<GnuAsmOperand>>,
     pub inputs: Vec<Node<GnuAsmOperand>>,
     pub clobbers: Vec<Node<StringLiteral>>,
+    pub labels: Vec<Node<Identifier>>,
 }
 
 /// Single input or output operand specifier for GNU extended asm statement
@@ -1235,6 +1477,8 @@ pub struct GnuAsmOperand {
 
 /// Type of an expression or type
 ///
+/// (C23 6.7.2.5)
+///
 /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Typeof.html)
 #[derive(Debug, PartialEq, Clone)]
 pub enum TypeOf {
@@ -57,8 +57,7 @@ pub fn concat<T>(mut a: Vec<T>, b: Vec<T>) -> Vec<T> {
 }
 
 pub fn infix(
-    node: Node<()>,
-    op: BinaryOperator,
+    op: Node<BinaryOperator>,
     lhs: Node<Expression>,
     rhs: Node<Expression>,
 ) -> Node<Expression> {
@@ -66,7 +65,7 @@ pub fn infix(
     Node::new(
         Expression::BinaryOperator(Box::new(Node::new(
             BinaryOperatorExpression {
-                operator: Node::new(op, node.span),
+                operator: op,
                 lhs: Box::new(lhs),
                 rhs: Box::new(rhs),
             },
@@ -76,6 +75,98 @@ pub fn infix(
     )
 }
 
+/// Wrap a statement node as a boxed statement with the same span
+pub fn boxed_statement<T>(
+    node: Node<T>,
+    statement: fn(Node<T>) -> Statement,
+) -> Box<Node<Statement>> {
+    let span = node.span;
+    Box::new(Node::new(statement(node), span))
+}
+
+/// The `? b : c` part of a conditional expression, without `b` for the GNU `?:` form
+pub type ConditionalOperation = (Option<Box<Node<Expression>>>, Node<Expression>);
+
+/// Nest the operands of a chain of conditional operators to the right
+///
+/// Every conditional in the chain, and the last operand, ends at `end`.
+pub fn conditional(
+    condition: Node<Expression>,
+    operations: Vec<ConditionalOperation>,
+    end: usize,
+) -> Expression {
+    let mut conditions = vec![condition];
+    let mut then_expressions = Vec::new();
+    for (then_expression, operand) in operations {
+        then_expressions.push(then_expression);
+        conditions.push(operand);
+    }
+
+    let last = conditions.pop().expect("chain has an operand");
+    let mut expr = Node::new(last.node, Span::span(last.span.start, end));
+    while let Some(condition) = conditions.pop() {
+        let span = Span::span(condition.span.start, end);
+        let conditional = ConditionalExpression {
+            condition: Box::new(condition),
+            then_expression: then_expressions.pop().expect("operator for each condition"),
+            else_expression: Box::new(expr),
+        };
+        expr = Node::new(
+            Expression::Conditional(Box::new(Node::new(conditional, span))),
+            span,
+        );
+    }
+    expr.node
+}
+
+/// Nest a statement in the labeled statements of a chain of labels
+pub fn labeled_statements(
+    labels: Vec<Node<Label>>,
+    mut statement: Box<Node<Statement>>,
+) -> Box<Node<Statement>> {
+    for label in labels.into_iter().rev() {
+        let span = Span::span(label.span.start, statement.span.end);
+        let labeled = LabeledStatement { label, statement };
+        statement = Box::new(Node::new(
+            Statement::Labeled(Node::new(labeled, span)),
+            span,
+        ));
+    }
+    statement
+}
+
+/// An `else if` branch: where its `if` starts, the condition and the statement
+pub type ElseIf = (usize, Box<Node<Expression>>, Box<Node<Statement>>);
+
+/// Nest the branches of an `else if` chain in the `else` branch of the previous `if`
+///
+/// Every `if` statement in the chain ends at `end`.
+pub fn if_statements(
+    condition: Box<Node<Expression>>,
+    then_statement: Box<Node<Statement>>,
+    else_ifs: Vec<ElseIf>,
+    mut else_statement: Option<Box<Node<Statement>>>,
+    end: usize,
+) -> IfStatement {
+    for (start, condition, then_statement) in else_ifs.into_iter().rev() {
+        let span = Span::span(start, end);
+        let statement = IfStatement {
+            condition,
+            then_statement,
+            else_statement,
+        };
+        else_statement = Some(Box::new(Node::new(
+            Statement::If(Node::new(statement, span)),
+            span,
+        )));
+    }
+    IfStatement {
+        condition,
+        then_statement,
+        else_statement,
+    }
+}
+
 pub fn with_ext(mut d: Node<Declarator>, e: Option<Vec<Node<Extension>>>) -> Node<Declarator> {
     if let Some(e) = e {
         d.node.extensions.extend(e);
@@ -83,6 +174,48 @@ pub fn with_ext(mut d: Node<Declarator>, e: Option<Vec<Node<Extension>>>) -> Nod
     d
 }
 
+/// Turn the asm labels of `register` variables into asm registers
+pub fn asm_registers(
+    specifiers: &[Node<DeclarationSpecifier>],
+    mut declarators: Vec<Node<InitDeclarator>>,
+) -> Vec<Node<InitDeclarator>> {
+    let register = specifiers.iter().any(|s| match s.node {
+        DeclarationSpecifier::StorageClass(ref c) => c.node == StorageClassSpecifier::Register,
+        _ => false,
+    });
+    if register {
+        for declarator in &mut declarators {
+            for extension in &mut declarator.node.declarator.node.extensions {
+                let label = match extension.node {
+                    Extension::AsmLabel(ref s) => s.clone(),
+                    _ => continue,
+                };
+                extension.node = Extension::AsmRegister(label);
+            }
+        }
+    }
+    declarators
+}
+
+/// Split asm qualifiers into the type qualifier and whether `goto` was given
+///
+/// `None` in the list stands for `goto`.
+pub fn asm_qualifiers(
+    list: Vec<Option<Node<TypeQualifier>>>,
+) -> Result<(Option<Node<TypeQualifier>>, bool), &'static str> {
+    let mut qualifier = None;
+    let mut goto = false;
+    for q in list {
+        match q {
+            Some(_) if qualifier.is_some() => return Err("single asm qualifier"),
+            Some(q) => qualifier = Some(q),
+            None if goto => return Err("single asm goto"),
+            None => goto = true,
+        }
+    }
+    Ok((qualifier, goto))
+}
+
 pub fn ts18661_float(binary: bool, width: usize, extended: bool) -> TS18661FloatType {
     TS18661FloatType {
         format: match (binary, extended) {
@@ -104,6 +237,9 @@ pub fn int_suffix(mut s: &str) -> Result<IntegerSuffix, &'static str> {
         if l == IntegerSize::Int && (s.starts_with("ll") || s.starts_with("LL")) {
             l = IntegerSize::LongLong;
             s = &s[2..];
+        } else if l == IntegerSize::Int && (s.starts_with("wb") || s.starts_with("WB")) {
+            l = IntegerSize::BitInt;
+            s = &s[2..];
         } else if l == IntegerSize::Int && (s.starts_with("l") || s.starts_with("L")) {
             l = IntegerSize::Long;
             s = &s[1..];
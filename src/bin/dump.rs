@@ -1,4 +1,4 @@
-//! Parse a C file and dump the AST.
+//! Parse a C file and dump the AST, or the control flow graphs of its functions with `-cfg`.
 
 extern crate lang_c;
 
@@ -11,6 +11,7 @@ fn main() {
     let mut config = Config::default();
     let mut source = None;
     let mut quiet = false;
+    let mut cfg = false;
 
     for opt in std::env::args().skip(1) {
         if opt == "-use-gcc" {
@@ -19,6 +20,8 @@ fn main() {
             config = Config::with_clang();
         } else if opt == "-q" {
             quiet = true;
+        } else if opt == "-cfg" {
+            cfg = true;
         } else if opt.starts_with("-") {
             config.cpp_options.push(opt);
         } else {
@@ -41,7 +44,9 @@ fn main() {
 
     match lang_c::driver::parse(&config, &source) {
         Ok(parse) => {
-            if !quiet {
+            if cfg {
+                print!("{}", lang_c::cfg::dot(&parse));
+            } else if !quiet {
                 let mut buf = String::new();
                 {
                     let mut printer = lang_c::print::Printer::new(&mut buf);
@@ -0,0 +1,882 @@
+//! Control flow graphs of function bodies
+//!
+//! Splits the body of a function definition into basic blocks of declarations, statements and
+//! controlling expressions, connected by the jumps between them. Graphs can be written in the
+//! Graphviz DOT format, with each block labeled by the source text of its items.
+//!
+//! ```
+//! use lang_c::ast::ExternalDeclaration;
+//! use lang_c::cfg::{Cfg, EdgeKind, ENTRY, EXIT};
+//! use lang_c::driver::{parse_preprocessed, Config};
+//!
+//! let source = "int abs(int x) { if (x < 0) return -x; return x; }";
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//! let cfg = match parse.unit.0[0].node {
+//!     ExternalDeclaration::FunctionDefinition(ref f) => Cfg::new(&f.node),
+//!     _ => unreachable!(),
+//! };
+//!
+//! let condition = cfg.blocks[ENTRY].successors[0].to;
+//! let text = |b: usize| -> Vec<_> {
+//!     cfg.blocks[b].items.iter().map(|s| &source[s.start..s.end]).collect()
+//! };
+//! assert_eq!(text(condition), ["x < 0"]);
+//!
+//! let kinds: Vec<_> = cfg.blocks[condition].successors.iter().map(|e| e.kind).collect();
+//! assert_eq!(kinds, [EdgeKind::True, EdgeKind::False]);
+//! let negative = cfg.blocks[condition].successors[0].to;
+//! assert_eq!(text(negative), ["return -x;"]);
+//! assert_eq!(cfg.blocks[negative].successors[0].to, EXIT);
+//! ```
+//!
+//! The statements of a GNU statement expression come before the item that evaluates it, so a
+//! `return` or `break` inside one leaves the enclosing statement. When the statement expression
+//! is an operand of `&&`, `||`, `?:` or a generic selection, an edge also bypasses it. Operands
+//! of `sizeof`, `_Alignof` and `typeof` are not evaluated and nested functions are kept as single
+//! items of the enclosing block. A computed `goto` has an edge to every label whose address is
+//! taken in the function.
+//!
+//! ```
+//! use lang_c::ast::ExternalDeclaration;
+//! use lang_c::cfg::{Cfg, EXIT};
+//! use lang_c::driver::{parse_preprocessed, Config};
+//!
+//! let source = "int f(int x) { int y = ({ if (!x) return 0; x + 1; }); return y; }";
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//! let cfg = match parse.unit.0[0].node {
+//!     ExternalDeclaration::FunctionDefinition(ref f) => Cfg::new(&f.node),
+//!     _ => unreachable!(),
+//! };
+//!
+//! let block = |text: &str| {
+//!     let found = cfg.blocks.iter().position(|b| {
+//!         b.items.iter().any(|s| &source[s.start..s.end] == text)
+//!     });
+//!     found.unwrap()
+//! };
+//! let early = block("return 0;");
+//! assert_eq!(cfg.blocks[early].successors[0].to, EXIT);
+//! let last = block("x + 1;");
+//! assert_eq!(cfg.blocks[last].items.len(), 3);
+//! assert!(cfg.predecessors(last).contains(&block("!x")));
+//! ```
+//!
+//! Leaving the scope of a variable with a GNU `cleanup` attribute calls the cleanup function,
+//! whether control falls off the end of the block or jumps out of it with `return`, `break`,
+//! `continue` or `goto`. The call is an item located at the attribute. Cleanups are called in
+//! the reverse order of the declarations, and not for a computed `goto`.
+//!
+//! ```
+//! use lang_c::ast::ExternalDeclaration;
+//! use lang_c::cfg::Cfg;
+//! use lang_c::driver::{parse_preprocessed, Config};
+//!
+//! let source = r#"
+//!     void f(void) {
+//!         char *p __attribute__((cleanup(release))) = get();
+//!         if (!p) return;
+//!         use(p);
+//!     }
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//! let cfg = match parse.unit.0[0].node {
+//!     ExternalDeclaration::FunctionDefinition(ref f) => Cfg::new(&f.node),
+//!     _ => unreachable!(),
+//! };
+//!
+//! let texts: Vec<Vec<_>> = cfg.blocks.iter().map(|b| {
+//!     b.items.iter().map(|s| &source[s.start..s.end]).collect()
+//! }).collect();
+//! assert!(texts.contains(&vec!["return;", "cleanup(release)"]));
+//! assert!(texts.contains(&vec!["use(p);", "cleanup(release)"]));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::mem;
+
+use ast::*;
+use driver::Parse;
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// Index of the block control enters the function through
+pub const ENTRY: usize = 0;
+/// Index of the block reached by `return` and by falling off the end of the function
+pub const EXIT: usize = 1;
+
+/// Condition under which control follows an edge
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeKind {
+    /// Unconditional jump or fall through
+    Next,
+    /// Controlling expression compares unequal to zero
+    True,
+    /// Controlling expression compares equal to zero
+    False,
+    /// Switch to a `case` label, with the location of its expression
+    Case(Span),
+    /// Switch to the `default` label, or past the switch body if there is none
+    Default,
+}
+
+/// Transfer of control from one block to another
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    /// Index of the target block
+    pub to: usize,
+    pub kind: EdgeKind,
+}
+
+/// Sequence of items executed in order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BasicBlock {
+    /// Locations of declarations, expression statements, jumps, controlling expressions and the
+    /// `cleanup` attributes of variables that go out of scope
+    pub items: Vec<Span>,
+    pub successors: Vec<Edge>,
+}
+
+/// Control flow graph of a function
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cfg {
+    /// Blocks of the function, starting with `ENTRY` and `EXIT`, which contain no items
+    ///
+    /// Other blocks that nothing jumps to are kept if they contain items, since they show
+    /// unreachable code.
+    pub blocks: Vec<BasicBlock>,
+}
+
+impl Cfg {
+    /// Build the control flow graph of a function body
+    pub fn new(function: &FunctionDefinition) -> Cfg {
+        let mut builder = Builder {
+            blocks: vec![BasicBlock::default(), BasicBlock::default()],
+            current: ENTRY,
+            breaks: Vec::new(),
+            continues: Vec::new(),
+            declared: Vec::new(),
+            cleanups: Vec::new(),
+            label_cleanups: HashMap::new(),
+            gotos: Vec::new(),
+            switches: Vec::new(),
+            labels: HashMap::new(),
+            indirect: Vec::new(),
+        };
+        builder.current = builder.block();
+        builder.edge(ENTRY, builder.current, EdgeKind::Next);
+        builder.statement(&function.statement);
+        let current = builder.current;
+        builder.edge(current, EXIT, EdgeKind::Next);
+
+        // Run the cleanups of the scopes a jump leaves on the way to the label
+        for (from, name, scope) in mem::replace(&mut builder.gotos, Vec::new()) {
+            let to = builder.label(name);
+            let kept = match builder.label_cleanups.get(name) {
+                Some(target) => scope
+                    .iter()
+                    .zip(target)
+                    .take_while(|&(a, b)| a == b)
+                    .count(),
+                None => 0,
+            };
+            if kept == scope.len() {
+                continue;
+            }
+            let block = builder.block();
+            for &c in scope[kept..].iter().rev() {
+                let span = builder.declared[c];
+                builder.blocks[block].items.push(span);
+            }
+            builder.edge(block, to, EdgeKind::Next);
+            for edge in &mut builder.blocks[from].successors {
+                if edge.to == to {
+                    edge.to = block;
+                }
+            }
+        }
+
+        let mut addresses = LabelAddresses(Vec::new());
+        addresses.visit_statement(&function.statement.node, &function.statement.span);
+        for from in builder.indirect.clone() {
+            for name in &addresses.0 {
+                let to = builder.label(name);
+                if !builder.blocks[from].successors.iter().any(|e| e.to == to) {
+                    builder.edge(from, to, EdgeKind::Next);
+                }
+            }
+        }
+
+        let mut cfg = Cfg {
+            blocks: builder.blocks,
+        };
+        cfg.simplify();
+        cfg
+    }
+
+    /// Blocks with an edge to a block
+    pub fn predecessors(&self, block: usize) -> Vec<usize> {
+        (0..self.blocks.len())
+            .filter(|&b| self.blocks[b].successors.iter().any(|e| e.to == block))
+            .collect()
+    }
+
+    /// Write the graph as a DOT subgraph named after the function
+    ///
+    /// Node names are prefixed with `prefix` so that several functions can share one graph.
+    pub fn write_dot<W: Write>(
+        &self,
+        w: &mut W,
+        name: &str,
+        prefix: &str,
+        source: &str,
+    ) -> ::std::fmt::Result {
+        try!(writeln!(w, "    subgraph \"cluster_{}\" {{", prefix));
+        try!(writeln!(w, "        label=\"{}\";", escape(name)));
+        for (i, block) in self.blocks.iter().enumerate() {
+            let label = match i {
+                ENTRY => "entry".to_string(),
+                EXIT => "exit".to_string(),
+                _ => block
+                    .items
+                    .iter()
+                    .map(|s| format!("{}\\l", escape(&snippet(source, *s))))
+                    .collect(),
+            };
+            let shape = if i == ENTRY || i == EXIT {
+                "oval"
+            } else {
+                "box"
+            };
+            try!(writeln!(
+                w,
+                "        {}_{} [shape={}, label=\"{}\"];",
+                prefix, i, shape, label
+            ));
+        }
+        for (i, block) in self.blocks.iter().enumerate() {
+            for edge in &block.successors {
+                let label = match edge.kind {
+                    EdgeKind::Next => String::new(),
+                    EdgeKind::True => " [label=\"true\"]".to_string(),
+                    EdgeKind::False => " [label=\"false\"]".to_string(),
+                    EdgeKind::Case(s) => {
+                        format!(" [label=\"case {}\"]", escape(&snippet(source, s)))
+                    }
+                    EdgeKind::Default => " [label=\"default\"]".to_string(),
+                };
+                try!(writeln!(
+                    w,
+                    "        {}_{} -> {}_{}{};",
+                    prefix, i, prefix, edge.to, label
+                ));
+            }
+        }
+        writeln!(w, "    }}")
+    }
+
+    /// Remove blocks without items that nothing jumps to or that only pass control on
+    fn simplify(&mut self) {
+        let mut removed = vec![false; self.blocks.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in 2..self.blocks.len() {
+                if removed[b] || !self.blocks[b].items.is_empty() {
+                    continue;
+                }
+                let successors = &self.blocks[b].successors;
+                let target = if successors.len() == 1
+                    && successors[0].kind == EdgeKind::Next
+                    && successors[0].to != b
+                {
+                    Some(successors[0].to)
+                } else {
+                    None
+                };
+                let reached = (0..self.blocks.len()).any(|p| {
+                    !removed[p] && p != b && self.blocks[p].successors.iter().any(|e| e.to == b)
+                });
+                if reached && target.is_none() {
+                    continue;
+                }
+                for p in 0..self.blocks.len() {
+                    for edge in &mut self.blocks[p].successors {
+                        if edge.to == b {
+                            edge.to = target.unwrap_or(b);
+                        }
+                    }
+                }
+                removed[b] = true;
+                changed = true;
+            }
+        }
+
+        let mut index = Vec::new();
+        let mut next = 0;
+        for &r in &removed {
+            index.push(next);
+            if !r {
+                next += 1;
+            }
+        }
+        let mut b = 0;
+        self.blocks.retain(|_| {
+            b += 1;
+            !removed[b - 1]
+        });
+        for block in &mut self.blocks {
+            for edge in &mut block.successors {
+                edge.to = index[edge.to];
+            }
+        }
+    }
+}
+
+/// Variable with a `cleanup` attribute
+#[derive(Debug, Clone)]
+pub struct Cleanup<'ast> {
+    /// Name of the variable
+    pub variable: &'ast Node<Identifier>,
+    /// Function called with the address of the variable when it goes out of scope
+    pub function: &'ast Node<Identifier>,
+    /// Location of the attribute
+    pub span: Span,
+}
+
+/// Variables of a block scope declaration that have a `cleanup` attribute, in declaration order
+///
+/// An attribute among the declaration specifiers applies to every declarator without one of its
+/// own. Declarations with a storage class other than `auto` or `register` have no cleanups.
+pub fn cleanups(declaration: &Declaration) -> Vec<Cleanup<'_>> {
+    let mut shared = None;
+    for specifier in &declaration.specifiers {
+        match specifier.node {
+            DeclarationSpecifier::StorageClass(ref s) => match s.node {
+                StorageClassSpecifier::Auto | StorageClassSpecifier::Register => {}
+                _ => return Vec::new(),
+            },
+            DeclarationSpecifier::Extension(ref e) => {
+                if let Some(function) = cleanup_function(&e.node) {
+                    shared = Some((function, e.span));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut found = Vec::new();
+    for init in &declaration.declarators {
+        let declarator = &init.node.declarator.node;
+        let variable = match declarator.kind.node {
+            DeclaratorKind::Identifier(ref i) => i,
+            _ => continue,
+        };
+        let own = declarator
+            .extensions
+            .iter()
+            .rev()
+            .find_map(|e| cleanup_function(&e.node).map(|f| (f, e.span)));
+        if let Some((function, span)) = own.or(shared) {
+            found.push(Cleanup {
+                variable,
+                function,
+                span,
+            });
+        }
+    }
+    found
+}
+
+/// Function named by a `cleanup` attribute
+fn cleanup_function(extension: &Extension) -> Option<&Node<Identifier>> {
+    let arguments = match *extension {
+        Extension::Attribute(ref a) if a.name.node.trim_matches('_') == "cleanup" => &a.arguments,
+        Extension::StandardAttribute(ref a) => match (&a.prefix, &a.arguments) {
+            (Some(p), Some(arguments))
+                if p.node.trim_matches('_') == "gnu"
+                    && a.name.node.trim_matches('_') == "cleanup" =>
+            {
+                arguments
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match arguments.first() {
+        Some(&Node {
+            node: Expression::Identifier(ref i),
+            ..
+        }) if arguments.len() == 1 => Some(i),
+        _ => None,
+    }
+}
+
+/// Control flow graphs of all function definitions in DOT format
+///
+/// Each function is drawn as a cluster of one directed graph.
+pub fn dot(parse: &Parse) -> String {
+    let mut out = String::from("digraph cfg {\n");
+    write_functions(&mut out, &parse.unit.0, &mut 0, &parse.source);
+    out.push_str("}\n");
+    out
+}
+
+/// Write the function definitions among `externals`, numbering clusters by declaration
+fn write_functions(
+    out: &mut String,
+    externals: &[Node<ExternalDeclaration>],
+    next: &mut usize,
+    source: &str,
+) {
+    for external in externals {
+        let i = *next;
+        *next += 1;
+        match external.node {
+            ExternalDeclaration::FunctionDefinition(ref f) => {
+                let name = match declarator_identifier(&f.node.declarator.node) {
+                    Some(i) => &*i.node.name,
+                    None => "",
+                };
+                let cfg = Cfg::new(&f.node);
+                cfg.write_dot(out, name, &format!("f{}", i), source)
+                    .unwrap();
+            }
+            ExternalDeclaration::LinkageSpecification(ref l) => {
+                write_functions(out, &l.node.declarations, next, source)
+            }
+            _ => {}
+        }
+    }
+}
+
+struct Builder<'ast> {
+    blocks: Vec<BasicBlock>,
+    current: usize,
+    /// Targets of `break` in the enclosing loops and switches, with the number of cleanups in
+    /// scope there
+    breaks: Vec<(usize, usize)>,
+    /// Targets of `continue` in the enclosing loops, with the number of cleanups in scope there
+    continues: Vec<(usize, usize)>,
+    /// Locations of the `cleanup` attributes of all variables, in declaration order
+    declared: Vec<Span>,
+    /// Cleanups of the variables in scope, innermost last, as indices into `declared`
+    cleanups: Vec<usize>,
+    /// Cleanups in scope at each label
+    label_cleanups: HashMap<&'ast str, Vec<usize>>,
+    /// Blocks ending in a jump to a label, with the cleanups in scope at the jump
+    gotos: Vec<(usize, &'ast str, Vec<usize>)>,
+    /// Block of each enclosing switch and whether it has a default label
+    switches: Vec<(usize, bool)>,
+    labels: HashMap<&'ast str, usize>,
+    /// Blocks ending in a computed `goto`
+    indirect: Vec<usize>,
+}
+
+impl<'ast> Builder<'ast> {
+    fn block(&mut self) -> usize {
+        self.blocks.push(BasicBlock::default());
+        self.blocks.len() - 1
+    }
+
+    fn edge(&mut self, from: usize, to: usize, kind: EdgeKind) {
+        self.blocks[from].successors.push(Edge { to, kind });
+    }
+
+    fn item(&mut self, span: Span) {
+        self.blocks[self.current].items.push(span);
+    }
+
+    /// Leave the current block and continue in a new one, or in `to` if given
+    fn goto(&mut self, to: Option<usize>, kind: EdgeKind) -> usize {
+        let next = match to {
+            Some(to) => to,
+            None => self.block(),
+        };
+        let current = self.current;
+        self.edge(current, next, kind);
+        self.current = next;
+        next
+    }
+
+    /// End the current block with a jump, statements that follow are unreachable
+    fn jump(&mut self, to: usize) {
+        let current = self.current;
+        self.edge(current, to, EdgeKind::Next);
+        self.current = self.block();
+    }
+
+    fn label(&mut self, name: &'ast str) -> usize {
+        if let Some(&block) = self.labels.get(name) {
+            return block;
+        }
+        let block = self.block();
+        self.labels.insert(name, block);
+        block
+    }
+
+    fn statement(&mut self, statement: &'ast Node<Statement>) {
+        match statement.node {
+            Statement::Labeled(ref l) => {
+                let target = match l.node.label.node {
+                    Label::Identifier(ref i) => {
+                        let name = &*i.node.name;
+                        self.label_cleanups.insert(name, self.cleanups.clone());
+                        self.label(name)
+                    }
+                    Label::Case(ref e) => self.case(EdgeKind::Case(e.span)),
+                    Label::Default => self.case(EdgeKind::Default),
+                };
+                self.goto(Some(target), EdgeKind::Next);
+                self.statement(&l.node.statement);
+            }
+            Statement::Attributed(ref a) => self.statement(&a.node.statement),
+            Statement::Compound(ref items) => {
+                let depth = self.cleanups.len();
+                for item in items {
+                    match item.node {
+                        BlockItem::Declaration(ref d) => self.declaration(d),
+                        BlockItem::FunctionDefinition(ref f) => self.item(f.span),
+                        BlockItem::StaticAssert(_) | BlockItem::LocalLabels(_) => {}
+                        BlockItem::Statement(ref s) => self.statement(s),
+                    }
+                }
+                self.leave(depth);
+            }
+            Statement::Expression(Some(ref e)) => {
+                self.evaluated(statement.span, |v| v.visit_expression(&e.node, &e.span))
+            }
+            Statement::Asm(ref a) => {
+                self.evaluated(statement.span, |v| v.visit_asm_statement(&a.node, &a.span));
+                // `asm goto` may jump to any of its labels or fall through
+                if let AsmStatement::GnuExtended(ref e) = a.node {
+                    if e.goto {
+                        let current = self.current;
+                        for label in &e.labels {
+                            let name = &*label.node.name;
+                            let target = self.label(name);
+                            self.edge(current, target, EdgeKind::Next);
+                            self.gotos.push((current, name, self.cleanups.clone()));
+                        }
+                        self.goto(None, EdgeKind::Next);
+                    }
+                }
+            }
+            Statement::Expression(None) => {}
+            Statement::If(ref i) => {
+                self.expression(&i.node.condition);
+                let condition = self.current;
+                let join = self.block();
+                self.goto(None, EdgeKind::True);
+                self.statement(&i.node.then_statement);
+                self.goto(Some(join), EdgeKind::Next);
+                self.current = condition;
+                match i.node.else_statement {
+                    Some(ref e) => {
+                        self.goto(None, EdgeKind::False);
+                        self.statement(e);
+                        self.goto(Some(join), EdgeKind::Next);
+                    }
+                    None => {
+                        self.goto(Some(join), EdgeKind::False);
+                    }
+                }
+            }
+            Statement::Switch(ref s) => {
+                self.expression(&s.node.expression);
+                let switch = self.current;
+                let exit = self.block();
+                self.breaks.push((exit, self.cleanups.len()));
+                self.switches.push((switch, false));
+                // Statements before the first label are unreachable
+                self.current = self.block();
+                self.statement(&s.node.statement);
+                self.goto(Some(exit), EdgeKind::Next);
+                self.breaks.pop();
+                if let Some((_, false)) = self.switches.pop() {
+                    self.edge(switch, exit, EdgeKind::Default);
+                }
+            }
+            Statement::While(ref w) => {
+                let head = self.goto(None, EdgeKind::Next);
+                self.expression(&w.node.expression);
+                let condition = self.current;
+                let exit = self.block();
+                self.goto(None, EdgeKind::True);
+                self.edge(condition, exit, EdgeKind::False);
+                self.looped(&w.node.statement, exit, head);
+                self.goto(Some(head), EdgeKind::Next);
+                self.current = exit;
+            }
+            Statement::DoWhile(ref d) => {
+                let body = self.goto(None, EdgeKind::Next);
+                let condition = self.block();
+                let exit = self.block();
+                self.looped(&d.node.statement, exit, condition);
+                self.goto(Some(condition), EdgeKind::Next);
+                self.expression(&d.node.expression);
+                let current = self.current;
+                self.edge(current, body, EdgeKind::True);
+                self.goto(Some(exit), EdgeKind::False);
+            }
+            Statement::For(ref f) => {
+                let depth = self.cleanups.len();
+                match f.node.initializer.node {
+                    ForInitializer::Empty => {}
+                    ForInitializer::Expression(ref e) => self.expression(e),
+                    ForInitializer::Declaration(ref d) => self.declaration(d),
+                    ForInitializer::StaticAssert(_) => {}
+                }
+                let head = self.goto(None, EdgeKind::Next);
+                let step = self.block();
+                let exit = self.block();
+                if let Some(ref c) = f.node.condition {
+                    self.expression(c);
+                    let condition = self.current;
+                    self.goto(None, EdgeKind::True);
+                    self.edge(condition, exit, EdgeKind::False);
+                } else {
+                    self.goto(None, EdgeKind::Next);
+                }
+                self.looped(&f.node.statement, exit, step);
+                self.goto(Some(step), EdgeKind::Next);
+                if let Some(ref s) = f.node.step {
+                    self.expression(s);
+                }
+                self.goto(Some(head), EdgeKind::Next);
+                self.current = exit;
+                self.leave(depth);
+            }
+            Statement::Goto(ref i) => {
+                self.item(statement.span);
+                let name = &*i.node.name;
+                let target = self.label(name);
+                self.gotos.push((self.current, name, self.cleanups.clone()));
+                self.jump(target);
+            }
+            Statement::GotoIndirect(ref e) => {
+                self.evaluated(statement.span, |v| v.visit_expression(&e.node, &e.span));
+                self.indirect.push(self.current);
+                self.current = self.block();
+            }
+            Statement::Continue | Statement::Break => {
+                self.item(statement.span);
+                let targets = match statement.node {
+                    Statement::Continue => &self.continues,
+                    _ => &self.breaks,
+                };
+                match targets.last().cloned() {
+                    Some((target, depth)) => {
+                        self.run_cleanups(depth);
+                        self.jump(target);
+                    }
+                    None => self.current = self.block(),
+                }
+            }
+            Statement::Return(ref e) => {
+                self.evaluated(statement.span, |v| {
+                    if let Some(ref e) = *e {
+                        v.visit_expression(&e.node, &e.span);
+                    }
+                });
+                self.run_cleanups(0);
+                self.jump(EXIT);
+            }
+        }
+    }
+
+    /// Controlling or full expression, after the statement expressions it evaluates
+    fn expression(&mut self, expression: &'ast Node<Expression>) {
+        self.evaluated(expression.span, |v| {
+            v.visit_expression(&expression.node, &expression.span)
+        });
+    }
+
+    /// Add an item, after the statements of the GNU statement expressions found by `visit`
+    fn evaluated<F>(&mut self, span: Span, visit: F)
+    where
+        F: FnOnce(&mut StatementExpressions<'ast>),
+    {
+        let mut found = StatementExpressions {
+            bodies: Vec::new(),
+            conditional: 0,
+        };
+        visit(&mut found);
+        for (body, conditional) in found.bodies {
+            if !conditional {
+                self.statement(body);
+                continue;
+            }
+            let before = self.current;
+            self.goto(None, EdgeKind::Next);
+            self.statement(body);
+            let after = self.goto(None, EdgeKind::Next);
+            self.edge(before, after, EdgeKind::Next);
+        }
+        self.item(span);
+    }
+
+    /// Block scope declaration, whose variables with a `cleanup` attribute enter the scope
+    fn declaration(&mut self, declaration: &'ast Node<Declaration>) {
+        self.evaluated(declaration.span, |v| {
+            v.visit_declaration(&declaration.node, &declaration.span)
+        });
+        for cleanup in cleanups(&declaration.node) {
+            self.cleanups.push(self.declared.len());
+            self.declared.push(cleanup.span);
+        }
+    }
+
+    /// Call the cleanups of the variables in scope, except for the outermost `depth` ones
+    fn run_cleanups(&mut self, depth: usize) {
+        for i in (depth..self.cleanups.len()).rev() {
+            let span = self.declared[self.cleanups[i]];
+            self.item(span);
+        }
+    }
+
+    /// End a scope that started with `depth` cleanups in scope
+    ///
+    /// Falling off the end of the scope calls the cleanups of its variables, unless the end is
+    /// unreachable.
+    fn leave(&mut self, depth: usize) {
+        let current = self.current;
+        let reached = !self.blocks[current].items.is_empty()
+            || self
+                .blocks
+                .iter()
+                .any(|b| b.successors.iter().any(|e| e.to == current));
+        if reached {
+            self.run_cleanups(depth);
+        }
+        self.cleanups.truncate(depth);
+    }
+
+    /// Body of a loop with its `break` and `continue` targets
+    fn looped(&mut self, body: &'ast Node<Statement>, exit: usize, next: usize) {
+        self.breaks.push((exit, self.cleanups.len()));
+        self.continues.push((next, self.cleanups.len()));
+        self.statement(body);
+        self.breaks.pop();
+        self.continues.pop();
+    }
+
+    /// Block of a `case` or `default` label, reached from the enclosing switch
+    fn case(&mut self, kind: EdgeKind) -> usize {
+        let block = self.block();
+        if let Some(&mut (switch, ref mut default)) = self.switches.last_mut() {
+            if kind == EdgeKind::Default {
+                *default = true;
+            }
+            self.blocks[switch]
+                .successors
+                .push(Edge { to: block, kind });
+        }
+        block
+    }
+}
+
+/// Outermost GNU statement expressions evaluated by an item, in source order
+///
+/// Each body is paired with whether it is evaluated only under a condition.
+struct StatementExpressions<'ast> {
+    bodies: Vec<(&'ast Node<Statement>, bool)>,
+    /// Number of enclosing operands that may not be evaluated
+    conditional: usize,
+}
+
+impl<'ast> StatementExpressions<'ast> {
+    fn conditional(&mut self, expression: &'ast Node<Expression>) {
+        self.conditional += 1;
+        self.visit_expression(&expression.node, &expression.span);
+        self.conditional -= 1;
+    }
+}
+
+impl<'ast> Visit<'ast> for StatementExpressions<'ast> {
+    fn visit_expression(&mut self, expression: &'ast Expression, span: &'ast Span) {
+        match *expression {
+            Expression::Statement(ref s) => self.bodies.push((s, self.conditional > 0)),
+            Expression::SizeOf(_) | Expression::AlignOf(_) | Expression::TypesCompatible(_) => {}
+            _ => visit::visit_expression(self, expression, span),
+        }
+    }
+
+    fn visit_unary_operator_expression(
+        &mut self,
+        u: &'ast UnaryOperatorExpression,
+        span: &'ast Span,
+    ) {
+        match u.operator.node {
+            UnaryOperator::SizeOf | UnaryOperator::AlignOf => {}
+            _ => visit::visit_unary_operator_expression(self, u, span),
+        }
+    }
+
+    fn visit_binary_operator_expression(
+        &mut self,
+        b: &'ast BinaryOperatorExpression,
+        span: &'ast Span,
+    ) {
+        match b.operator.node {
+            BinaryOperator::LogicalAnd | BinaryOperator::LogicalOr => {
+                self.visit_expression(&b.lhs.node, &b.lhs.span);
+                self.conditional(&b.rhs);
+            }
+            _ => visit::visit_binary_operator_expression(self, b, span),
+        }
+    }
+
+    fn visit_conditional_expression(&mut self, c: &'ast ConditionalExpression, _: &'ast Span) {
+        self.visit_expression(&c.condition.node, &c.condition.span);
+        if let Some(ref e) = c.then_expression {
+            self.conditional(e);
+        }
+        self.conditional(&c.else_expression);
+    }
+
+    fn visit_choose_expression(&mut self, c: &'ast ChooseExpression, _: &'ast Span) {
+        self.conditional(&c.then_expression);
+        self.conditional(&c.else_expression);
+    }
+
+    fn visit_generic_selection(&mut self, g: &'ast GenericSelection, _: &'ast Span) {
+        self.conditional += 1;
+        for association in &g.associations {
+            self.visit_generic_association(&association.node, &association.span);
+        }
+        self.conditional -= 1;
+    }
+
+    fn visit_type_of(&mut self, _: &'ast TypeOf, _: &'ast Span) {}
+}
+
+/// Labels used in `&&label` expressions, in source order
+struct LabelAddresses<'ast>(Vec<&'ast str>);
+
+impl<'ast> Visit<'ast> for LabelAddresses<'ast> {
+    fn visit_expression(&mut self, expression: &'ast Expression, span: &'ast Span) {
+        if let Expression::LabelAddress(ref l) = *expression {
+            self.0.push(&l.node.name);
+        }
+        visit::visit_expression(self, expression, span);
+    }
+}
+
+fn declarator_identifier(declarator: &Declarator) -> Option<&Node<Identifier>> {
+    match declarator.kind.node {
+        DeclaratorKind::Abstract => None,
+        DeclaratorKind::Identifier(ref i) => Some(i),
+        DeclaratorKind::Declarator(ref d) => declarator_identifier(&d.node),
+    }
+}
+
+/// Source text of an item on a single line
+fn snippet(source: &str, span: Span) -> String {
+    let text = source.get(span.start..span.end).unwrap_or("");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,199 @@
+//! Functions run before and after `main`
+//!
+//! GNU `constructor` and `destructor` attributes mark functions the loader calls when a program
+//! starts or exits. An optional priority orders them: constructors run from the lowest priority
+//! to the highest and destructors the other way round, with functions without a priority last
+//! among constructors and first among destructors.
+//!
+//! ```
+//! use lang_c::constructors::{constructors, destructors};
+//! use lang_c::driver::{parse_preprocessed, Config};
+//!
+//! let source = r#"
+//!     __attribute__((constructor)) void late(void) {}
+//!     void early(void) __attribute__((constructor(101)));
+//!     __attribute__((constructor(200), destructor(200))) void middle(void) {}
+//!     __attribute__((destructor)) void cleanup(void) {}
+//! "#;
+//! let parse = parse_preprocessed(&Config::with_gcc(), source.into()).unwrap();
+//!
+//! let names = |entries: Vec<lang_c::constructors::Entry>| -> Vec<String> {
+//!     entries.iter().map(|e| e.function.node.name.clone()).collect()
+//! };
+//! assert_eq!(names(constructors(&parse.unit)), ["early", "middle", "late"]);
+//! assert_eq!(names(destructors(&parse.unit)), ["cleanup", "middle"]);
+//! assert_eq!(constructors(&parse.unit)[0].priority, Some(101));
+//! ```
+
+use std::collections::HashSet;
+
+use ast::*;
+use eval::eval;
+use span::{Node, Span};
+
+/// Priority the loader uses for functions declared without one
+pub const DEFAULT_PRIORITY: u64 = 65535;
+
+/// Whether a function runs at startup or at exit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// `constructor`, runs before `main`
+    Constructor,
+    /// `destructor`, runs after `main` returns or `exit` is called
+    Destructor,
+}
+
+/// Decoded `constructor` or `destructor` attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InitAttribute {
+    pub kind: Kind,
+    /// Priority given as the attribute argument
+    ///
+    /// An argument that is not a non-negative integer constant is treated as missing.
+    pub priority: Option<u64>,
+}
+
+impl InitAttribute {
+    /// Decode a GNU attribute, returning `None` for other attributes
+    pub fn from_attribute(attribute: &Attribute) -> Option<InitAttribute> {
+        let kind = match attribute.name.node.trim_matches('_') {
+            "constructor" => Kind::Constructor,
+            "destructor" => Kind::Destructor,
+            _ => return None,
+        };
+        let priority = match attribute.arguments.first() {
+            Some(e) => match eval(e, &|_: &str| None).map(|v| v.integer()) {
+                Ok(Some(p)) if p >= 0 => Some(p as u64),
+                _ => None,
+            },
+            None => None,
+        };
+        Some(InitAttribute { kind, priority })
+    }
+}
+
+/// Function marked with a `constructor` or `destructor` attribute
+#[derive(Debug, Clone)]
+pub struct Entry<'ast> {
+    /// Name of the function at its first declaration with the attribute
+    pub function: &'ast Node<Identifier>,
+    pub priority: Option<u64>,
+    /// Location of the attribute
+    pub span: Span,
+}
+
+/// Constructors of a translation unit in the order they run
+///
+/// Functions with the same priority keep their source order.
+pub fn constructors(unit: &TranslationUnit) -> Vec<Entry<'_>> {
+    let mut entries = collect(unit, Kind::Constructor);
+    entries.sort_by_key(|e| e.priority.unwrap_or(DEFAULT_PRIORITY));
+    entries
+}
+
+/// Destructors of a translation unit in the order they run
+///
+/// Functions with the same priority run in reverse source order.
+pub fn destructors(unit: &TranslationUnit) -> Vec<Entry<'_>> {
+    let mut entries = collect(unit, Kind::Destructor);
+    entries.reverse();
+    entries.sort_by(|a, b| {
+        let priority = |e: &Entry| e.priority.unwrap_or(DEFAULT_PRIORITY);
+        priority(b).cmp(&priority(a))
+    });
+    entries
+}
+
+/// Functions with an attribute of the given kind, each at its first declaration with one
+fn collect(unit: &TranslationUnit, kind: Kind) -> Vec<Entry<'_>> {
+    let mut entries = Vec::new();
+    let mut seen = HashSet::new();
+    collect_from(&unit.0, kind, &mut entries);
+    entries.retain(|e| seen.insert(&*e.function.node.name));
+    entries
+}
+
+fn collect_from<'ast>(
+    externals: &'ast [Node<ExternalDeclaration>],
+    kind: Kind,
+    entries: &mut Vec<Entry<'ast>>,
+) {
+    for external in externals {
+        match external.node {
+            ExternalDeclaration::Declaration(ref d) => {
+                let typedef = d.node.specifiers.iter().any(|s| match s.node {
+                    DeclarationSpecifier::StorageClass(ref s) => {
+                        s.node == StorageClassSpecifier::Typedef
+                    }
+                    _ => false,
+                });
+                if typedef {
+                    continue;
+                }
+                for init in &d.node.declarators {
+                    let declarator = &init.node.declarator.node;
+                    if let Some(e) = find(kind, &d.node.specifiers, declarator) {
+                        entries.push(e);
+                    }
+                }
+            }
+            ExternalDeclaration::FunctionDefinition(ref f) => {
+                if let Some(e) = find(kind, &f.node.specifiers, &f.node.declarator.node) {
+                    entries.push(e);
+                }
+            }
+            ExternalDeclaration::StaticAssert(_) => {}
+            ExternalDeclaration::LinkageSpecification(ref l) => {
+                collect_from(&l.node.declarations, kind, entries)
+            }
+        }
+    }
+}
+
+/// Attribute of the given kind on a function declaration
+fn find<'ast>(
+    kind: Kind,
+    specifiers: &'ast [Node<DeclarationSpecifier>],
+    declarator: &'ast Declarator,
+) -> Option<Entry<'ast>> {
+    let function = function_identifier(declarator);
+    let specifiers = specifiers.iter().filter_map(|s| match s.node {
+        DeclarationSpecifier::Extension(ref e) => Some(e),
+        _ => None,
+    });
+    for e in specifiers.chain(&declarator.extensions) {
+        let attribute = match e.node {
+            Extension::Attribute(ref a) => InitAttribute::from_attribute(a),
+            _ => None,
+        };
+        match (function, attribute) {
+            (Some(function), Some(a)) if a.kind == kind => {
+                return Some(Entry {
+                    function,
+                    priority: a.priority,
+                    span: e.span,
+                })
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Name declared by a declarator if it declares a function
+fn function_identifier(declarator: &Declarator) -> Option<&Node<Identifier>> {
+    let identifier = match declarator.kind.node {
+        DeclaratorKind::Identifier(ref i) => i,
+        _ => return None,
+    };
+    for derived in &declarator.derived {
+        match derived.node {
+            DerivedDeclarator::Pointer(_) => {}
+            DerivedDeclarator::Function(_) | DerivedDeclarator::KRFunction(_) => {
+                return Some(identifier)
+            }
+            DerivedDeclarator::Array(_) => return None,
+        }
+    }
+    None
+}
@@ -1,25 +1,183 @@
 //! Preprocess and parse C source file into an abstract syntax tree
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error;
 use std::fmt;
-use std::io;
+use std::fs::File;
+use std::io::{self, Read};
+use std::panic::RefUnwindSafe;
 use std::path::Path;
 use std::process::Command;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use ast::TranslationUnit;
-use env::Env;
+use ast::{Identifier, TranslationUnit};
+use env::{self, Env, DEFAULT_MAX_NESTING};
+use includes::{line_markers, IncludeGraph, LineMarker};
 use parser::translation_unit;
+use size;
+use span::{Node, Span};
+
+/// Classifies identifiers missing from the symbol table, `true` for typedef names
+pub type TypenameHook = env::TypenameHook;
 
 /// Parser configuration
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct Config {
     /// Command used to invoke C preprocessor
     pub cpp_command: String,
     /// Options to pass to the preprocessor program
     pub cpp_options: Vec<String>,
+    /// Whether `parse` runs the preprocessor on its input
+    pub input: InputMode,
     /// Language flavor to parse
     pub flavor: Flavor,
+    /// Accept syntax introduced in C23 in addition to the language flavor
+    pub c23: bool,
+    /// Accept C++ `extern "C"` linkage specifications
+    ///
+    /// Headers shared with C++ wrap their declarations in `extern "C" { ... }` under
+    /// `#ifdef __cplusplus`. This allows parsing such headers when they were not preprocessed, or
+    /// were preprocessed as C++. The specifications are kept as
+    /// `ExternalDeclaration::LinkageSpecification`.
+    pub linkage_specifications: bool,
+    /// Accept comments in the text to parse
+    ///
+    /// Preprocessors remove comments unless asked to keep them, for example with the `-C` option
+    /// of `gcc` and `clang`. Comments are skipped like whitespace; the `annotations` module
+    /// extracts the ones that carry structured annotations.
+    pub comments: bool,
+    /// Skip Microsoft source code annotations such as `_In_`, `_Out_writes_(n)` and `__in_opt`
+    ///
+    /// Windows headers define these annotations as empty macros, but they survive in sources
+    /// that were not preprocessed or were preprocessed without the SAL headers. With this option
+    /// they are skipped like whitespace wherever they appear.
+    ///
+    /// ```
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    ///
+    /// let mut config = Config::default();
+    /// config.sal_annotations = true;
+    /// let source = "_Success_(return != 0) int read(_In_ int fd, _Out_writes_(n) char *buf, int n);";
+    /// parse_preprocessed(&config, source.into()).unwrap();
+    /// ```
+    pub sal_annotations: bool,
+    /// Parse uses of the standard macros `offsetof` and `va_arg` as the built-ins they expand to
+    ///
+    /// In sources that were not preprocessed these macros keep their names, and their type name
+    /// arguments can not be parsed as a function call. With this option they become
+    /// `Expression::OffsetOf` and `Expression::VaArg`. `va_start`, `va_end` and `va_copy` take
+    /// only expressions and already parse as calls. The option is enabled automatically when
+    /// `InputMode::Auto` falls back to parsing with the directives removed.
+    ///
+    /// ```
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    ///
+    /// let mut config = Config::default();
+    /// config.builtin_macros = true;
+    /// let source = concat!(
+    ///     "struct s { int a[2]; };\n",
+    ///     "int f(__builtin_va_list ap) { return offsetof(struct s, a[1]) + va_arg(ap, int); }\n",
+    /// );
+    /// parse_preprocessed(&config, source.into()).unwrap();
+    /// ```
+    pub builtin_macros: bool,
+    /// Accept the decimal floating types `_Decimal32`, `_Decimal64`, `_Decimal128` and
+    /// `_DecimalNx`, and the `df`, `dd`, `dl` and `dN` suffixes of floating constants
+    ///
+    /// Enabled by default. Disable it to parse strictly conforming C11, where these are not
+    /// keywords.
+    ///
+    /// ```
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    ///
+    /// let mut config = Config::default();
+    /// parse_preprocessed(&config, "_Decimal64 x = 1.5dd;".into()).unwrap();
+    /// config.decimal_floats = false;
+    /// parse_preprocessed(&config, "_Decimal64 x = 1.5dd;".into()).unwrap_err();
+    /// ```
+    pub decimal_floats: bool,
+    /// Collect identifiers classified using the typedef table into `Parse::ambiguities`
+    pub report_ambiguities: bool,
+    /// Count the tokens and nodes of the parsed source into `Stats`
+    ///
+    /// Counting walks the whole tree once more after parsing, so it is off by default.
+    pub size_stats: bool,
+    /// Maximum nesting depth of expressions, statements and declarators
+    ///
+    /// Deeper input is rejected with a syntax error instead of overflowing the stack. A block that
+    /// is the body of a statement shares its level with that statement, and chains of labels,
+    /// `else if` branches and conditional operators do not nest. The default accepts the nesting
+    /// C11 requires implementations to support and is safe for threads with 2MB of stack, raise it
+    /// when parsing on a larger stack.
+    ///
+    /// ```
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    ///
+    /// let mut config = Config::default();
+    /// config.max_nesting_depth = 2;
+    /// let error = parse_preprocessed(&config, "int x = -(-1);".into()).unwrap_err();
+    /// assert_eq!(error.nesting_limit, Some(2));
+    ///
+    /// // Alternatives the parser tried and abandoned at the limit do not hide other errors
+    /// let source = "typedef int T; int x = sizeof(T); int y = ;";
+    /// let error = parse_preprocessed(&config, source.into()).unwrap_err();
+    /// assert_eq!(error.nesting_limit, None);
+    /// assert_eq!(error.column, 43);
+    ///
+    /// // Each label of a chain shares the level of the statement it labels
+    /// let source = format!("int f(int x) {{ switch (x) {{ {} return 0; }} }}", "case 1: ".repeat(300));
+    /// assert!(parse_preprocessed(&Config::default(), source).is_ok());
+    /// ```
+    pub max_nesting_depth: usize,
+    /// Alternative spellings of keywords, mapped to the keyword they stand for
+    ///
+    /// Supports storage class specifiers, `typedef`, basic type specifiers, type qualifiers,
+    /// function specifiers and `asm`. Aliases of other keywords are ignored. The alternative
+    /// spellings can not be used as identifiers.
+    ///
+    /// ```
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    ///
+    /// let mut config = Config::default();
+    /// config.keyword_aliases.insert("__far_const".into(), "const".into());
+    /// parse_preprocessed(&config, "__far_const int x = 1;".into()).unwrap();
+    /// ```
+    pub keyword_aliases: HashMap<String, String>,
+    /// Classify identifiers that were not declared in the parsed source
+    ///
+    /// The hook is called with the name of an undeclared identifier found where a type could
+    /// appear and returns `true` to treat it as a typedef name. This allows parsing code with
+    /// types known from an external source, such as an index or headers that were not included.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use lang_c::ast::CastExpression;
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    /// use lang_c::span::Span;
+    /// use lang_c::visit::Visit;
+    ///
+    /// struct Casts(usize);
+    ///
+    /// impl<'ast> Visit<'ast> for Casts {
+    ///     fn visit_cast_expression(&mut self, _: &'ast CastExpression, _: &'ast Span) {
+    ///         self.0 += 1;
+    ///     }
+    /// }
+    ///
+    /// let mut config = Config::default();
+    /// config.typename_hook = Some(Arc::new(|name: &str| name.ends_with("_t")));
+    /// let source = "int f(int x) { return (size_t)x; }";
+    /// let parse = parse_preprocessed(&config, source.into()).unwrap();
+    /// let mut casts = Casts(0);
+    /// casts.visit_translation_unit(&parse.unit);
+    /// assert_eq!(casts.0, 1);
+    ///
+    /// // The hook is unwind safe, so the configuration can be shared with `catch_unwind`
+    /// let result = std::panic::catch_unwind(|| parse_preprocessed(&config, source.into()));
+    /// assert!(result.unwrap().is_ok());
+    /// ```
+    pub typename_hook: Option<TypenameHook>,
 }
 
 impl Config {
@@ -28,7 +186,19 @@ impl Config {
         Config {
             cpp_command: "gcc".into(),
             cpp_options: vec!["-E".into()],
+            input: InputMode::Preprocess,
             flavor: Flavor::GnuC11,
+            c23: false,
+            linkage_specifications: false,
+            comments: false,
+            sal_annotations: false,
+            builtin_macros: false,
+            decimal_floats: true,
+            report_ambiguities: false,
+            size_stats: false,
+            max_nesting_depth: DEFAULT_MAX_NESTING,
+            keyword_aliases: HashMap::new(),
+            typename_hook: None,
         }
     }
 
@@ -37,11 +207,45 @@ impl Config {
         Config {
             cpp_command: "clang".into(),
             cpp_options: vec!["-E".into()],
+            input: InputMode::Preprocess,
             flavor: Flavor::ClangC11,
+            c23: false,
+            linkage_specifications: false,
+            comments: false,
+            sal_annotations: false,
+            builtin_macros: false,
+            decimal_floats: true,
+            report_ambiguities: false,
+            size_stats: false,
+            max_nesting_depth: DEFAULT_MAX_NESTING,
+            keyword_aliases: HashMap::new(),
+            typename_hook: None,
         }
     }
 }
 
+impl fmt::Debug for Config {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Config")
+            .field("cpp_command", &self.cpp_command)
+            .field("cpp_options", &self.cpp_options)
+            .field("input", &self.input)
+            .field("flavor", &self.flavor)
+            .field("c23", &self.c23)
+            .field("linkage_specifications", &self.linkage_specifications)
+            .field("comments", &self.comments)
+            .field("sal_annotations", &self.sal_annotations)
+            .field("builtin_macros", &self.builtin_macros)
+            .field("decimal_floats", &self.decimal_floats)
+            .field("report_ambiguities", &self.report_ambiguities)
+            .field("size_stats", &self.size_stats)
+            .field("max_nesting_depth", &self.max_nesting_depth)
+            .field("keyword_aliases", &self.keyword_aliases)
+            .field("typename_hook", &self.typename_hook.is_some())
+            .finish()
+    }
+}
+
 impl Default for Config {
     #[cfg(target_os = "macos")]
     fn default() -> Config {
@@ -65,18 +269,161 @@ pub enum Flavor {
     ClangC11,
 }
 
+/// How `parse` obtains the text to parse from a file
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum InputMode {
+    /// Run the preprocessor on the file
+    Preprocess,
+    /// Parse the file as it is, it has already been preprocessed
+    Preprocessed,
+    /// Run the preprocessor only if the file contains directives
+    ///
+    /// Line markers, `#line`, `#pragma`, `#ident` and `#sccs` do not count, since they remain in
+    /// preprocessed output. If the preprocessor fails, for example because it is not installed
+    /// or a header is missing, the file is parsed with its directives removed and macros left
+    /// unexpanded, and the preprocessor error is kept in `Parse::preprocessor_error`. When that
+    /// parse fails as well, the preprocessor error is returned.
+    ///
+    /// ```
+    /// use lang_c::driver::{parse, Config, InputMode, Preprocessing};
+    ///
+    /// let path = std::env::temp_dir().join("lang-c-input-mode.c");
+    /// std::fs::write(&path, "#include \"missing.h\"\nint x;\n").unwrap();
+    ///
+    /// let mut config = Config::default();
+    /// config.input = InputMode::Auto;
+    /// let stripped = parse(&config, &path).unwrap();
+    /// assert_eq!(stripped.preprocessing, Preprocessing::Stripped);
+    /// assert!(stripped.preprocessor_error.is_some());
+    /// assert_eq!(stripped.unit.0.len(), 1);
+    ///
+    /// std::fs::write(&path, "#include \"missing.h\"\nint x = ;\n").unwrap();
+    /// assert!(parse(&config, &path).unwrap_err().to_string().contains("preprocessor error"));
+    ///
+    /// std::fs::write(&path, "#ident \"v1\"\nint x;\n").unwrap();
+    /// let direct = parse(&config, &path).unwrap();
+    /// assert_eq!(direct.preprocessing, Preprocessing::None);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    Auto,
+}
+
+/// How the parsed text was obtained
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Preprocessing {
+    /// Text was parsed as given
+    None,
+    /// Text is the output of the preprocessor
+    External,
+    /// Preprocessor failed and the text is the input with its directives removed
+    Stripped,
+}
+
 /// Result of a successful parse
 #[derive(Clone, Debug)]
 pub struct Parse {
     /// Pre-processed source text
     pub source: String,
+    /// Whether the source was produced by the preprocessor
+    pub preprocessing: Preprocessing,
+    /// Why the preprocessor failed, when `InputMode::Auto` parsed the file without it
+    pub preprocessor_error: Option<String>,
     /// Root of the abstract syntax tree
     pub unit: TranslationUnit,
+    /// Identifiers classified using the typedef table, ordered by position
+    ///
+    /// Only filled in when `Config::report_ambiguities` is set.
+    pub ambiguities: Vec<Ambiguity>,
+    /// Measurements taken while parsing
+    pub stats: Stats,
+}
+
+/// Size of the input and time spent in each phase of parsing
+///
+/// The grammar matches characters without a separate lexer, so lexing is part of parsing and
+/// is not timed on its own. Tokens and nodes are counted after parsing when
+/// `Config::size_stats` is set, the same way as `size::total`.
+///
+/// ```
+/// use lang_c::driver::{parse_preprocessed, Config};
+///
+/// let mut config = Config::default();
+/// let parse = parse_preprocessed(&config, "int x = (1 + 2);".into()).unwrap();
+/// assert_eq!(parse.stats.source_bytes, 16);
+/// assert_eq!(parse.stats.tokens, None);
+/// assert_eq!(parse.stats.preprocess_time, None);
+/// assert!(parse.stats.peak_nesting >= 1);
+/// assert!(parse.stats.memo_entries >= 2);
+///
+/// config.size_stats = true;
+/// let parse = parse_preprocessed(&config, "int x = (1 + 2);".into()).unwrap();
+/// assert_eq!(parse.stats.tokens, Some(9));
+/// assert!(parse.stats.nodes > parse.stats.tokens);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    /// Length of the pre-processed source
+    pub source_bytes: usize,
+    /// Tokens in the pre-processed source, not counting line directives and comments
+    pub tokens: Option<usize>,
+    /// Nodes in the syntax tree, one per line of its `print` dump
+    pub nodes: Option<usize>,
+    /// Time spent running the preprocessor, `None` for pre-processed input
+    pub preprocess_time: Option<Duration>,
+    /// Time spent parsing the pre-processed source
+    pub parse_time: Duration,
+    /// Deepest nesting of expressions, statements and declarators
+    ///
+    /// Compare with `Config::max_nesting_depth` to see how close the input came to the limit.
+    pub peak_nesting: usize,
+    /// Size of the parser's memoization cache at the end of parsing, in entries
+    ///
+    /// The cache keeps the results of postfix expressions by position, which saves reparsing
+    /// them when the parser backtracks. It only grows during a parse.
+    pub memo_entries: usize,
+}
+
+impl Parse {
+    /// Include tree recovered from the line markers in the source
+    ///
+    /// Empty unless the preprocessor was run with line markers enabled.
+    pub fn includes(&self) -> IncludeGraph {
+        IncludeGraph::new(&self.source)
+    }
+
+    /// Line markers and `#line` directives in the source
+    pub fn line_markers(&self) -> Vec<Node<LineMarker>> {
+        line_markers(&self.source)
+    }
+}
+
+/// Identifier that was classified as a type name or not by looking it up in the typedef table
+///
+/// C grammar is ambiguous without knowing which identifiers name types: `(x)(y)` is either
+/// a cast or a call, `T * p;` is either a declaration or a multiplication. A wrong parse is
+/// most often caused by a missing typedef, and the list of these decisions helps to find it.
+///
+/// ```
+/// use lang_c::driver::{parse_preprocessed, Config};
+///
+/// let mut config = Config::default();
+/// config.report_ambiguities = true;
+/// let parse = parse_preprocessed(&config, "void f() { T * p; }".into()).unwrap();
+/// assert_eq!(parse.ambiguities[0].identifier.node.name, "T");
+/// assert!(!parse.ambiguities[0].typename);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ambiguity {
+    /// Identifier that was looked up
+    pub identifier: Node<Identifier>,
+    /// Identifier was interpreted as a type name
+    pub typename: bool,
 }
 
 #[derive(Debug)]
 /// Error type returned from `parse`
 pub enum Error {
+    /// Preprocessor failed, or the input could not be read
     PreprocessorError(io::Error),
     SyntaxError(SyntaxError),
 }
@@ -118,6 +465,8 @@ pub struct SyntaxError {
     pub offset: usize,
     /// Tokens expected at the error location
     pub expected: HashSet<&'static str>,
+    /// Nesting depth limit, when the error was caused by input nested deeper than that
+    pub nesting_limit: Option<usize>,
 }
 
 impl SyntaxError {
@@ -134,10 +483,84 @@ impl SyntaxError {
 
         Ok(())
     }
+
+    /// Check if the source is a valid prefix that ended before the parse could complete
+    ///
+    /// This is the case for unbalanced braces and parentheses or a missing final `;`, where an
+    /// interactive tool should ask for more input rather than report an error.
+    ///
+    /// ```
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    ///
+    /// let config = Config::default();
+    /// let error = parse_preprocessed(&config, "int f(void) {\n  if (x) {".into()).unwrap_err();
+    /// assert!(error.is_incomplete());
+    /// let error = parse_preprocessed(&config, "int f(void) { } }\n".into()).unwrap_err();
+    /// assert!(!error.is_incomplete());
+    /// ```
+    pub fn is_incomplete(&self) -> bool {
+        self.nesting_limit.is_none() && self.source[self.offset..].trim().is_empty()
+    }
+
+    /// Find the location of a `#` or `##` token or of a directive, if the error suggests the
+    /// input was not preprocessed
+    ///
+    /// This is the token at the error location if it starts with `#`, and otherwise the first
+    /// directive in the source that the preprocessor would have removed.
+    ///
+    /// ```
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    ///
+    /// let source = "#include <stdio.h>\nFILE *f;\n";
+    /// let error = parse_preprocessed(&Config::default(), source.into()).unwrap_err();
+    /// let span = error.unpreprocessed().unwrap();
+    /// assert_eq!(&source[span.start..span.end], "#include <stdio.h>");
+    ///
+    /// let source = "int a ## b;";
+    /// let error = parse_preprocessed(&Config::default(), source.into()).unwrap_err();
+    /// assert_eq!(error.unpreprocessed().map(|s| (s.start, s.end)), Some((6, 8)));
+    ///
+    /// // Directives that survive preprocessing do not count
+    /// let source = "#ident \"v1\"\nint x = ;\n";
+    /// let error = parse_preprocessed(&Config::default(), source.into()).unwrap_err();
+    /// assert_eq!(error.unpreprocessed(), None);
+    /// assert_eq!(error.line, 2);
+    /// ```
+    pub fn unpreprocessed(&self) -> Option<Span> {
+        if self.nesting_limit.is_some() {
+            return None;
+        }
+        let rest = &self.source[self.offset..];
+        let token = rest.trim_start();
+        if token.starts_with('#') {
+            let start = self.offset + rest.len() - token.len();
+            let len = if token.starts_with("##") { 2 } else { 1 };
+            return Some(Span::span(start, start + len));
+        }
+        first_directive(&self.source)
+    }
 }
 
 impl fmt::Display for SyntaxError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(limit) = self.nesting_limit {
+            return write!(
+                fmt,
+                "nesting depth limit of {} exceeded at line {} column {}",
+                limit, self.line, self.column
+            );
+        }
+        if let Some(span) = self.unpreprocessed() {
+            let (line, column) = line_column(&self.source, span.start);
+            return write!(
+                fmt,
+                "input appears unpreprocessed, '{}' at line {} column {}; enable preprocessing or \
+                 set the input mode to auto",
+                self.source[span.start..span.end].trim_end(),
+                line,
+                column
+            );
+        }
         try!(write!(
             fmt,
             "unexpected token at line {} column {}, expected ",
@@ -148,35 +571,284 @@ impl fmt::Display for SyntaxError {
 }
 
 /// Parse a C file
+///
+/// The file is preprocessed first unless `Config::input` says otherwise.
 pub fn parse<P: AsRef<Path>>(config: &Config, source: P) -> Result<Parse, Error> {
-    let processed = match preprocess(config, source.as_ref()) {
-        Ok(s) => s,
-        Err(e) => return Err(Error::PreprocessorError(e)),
+    let path = source.as_ref();
+    // Keep the file contents for the fallback when automatic detection runs the preprocessor
+    let text = match config.input {
+        InputMode::Preprocess => None,
+        InputMode::Preprocessed | InputMode::Auto => {
+            let text = match read(path) {
+                Ok(text) => text,
+                Err(e) => return Err(Error::PreprocessorError(e)),
+            };
+            if config.input == InputMode::Preprocessed || !has_directives(&text) {
+                return Ok(try!(parse_preprocessed(config, text)));
+            }
+            Some(text)
+        }
     };
 
-    Ok(try!(parse_preprocessed(config, processed)))
+    let start = Instant::now();
+    let processed = match (preprocess(config, path), text) {
+        (Ok(s), _) => s,
+        (Err(e), Some(text)) => {
+            let mut config = config.clone();
+            config.builtin_macros = true;
+            let mut parse = match parse_preprocessed(&config, strip_directives(&text)) {
+                Ok(parse) => parse,
+                // The preprocessor error explains the failure better than the stripped text
+                Err(_) => return Err(Error::PreprocessorError(e)),
+            };
+            parse.preprocessing = Preprocessing::Stripped;
+            parse.preprocessor_error = Some(e.to_string());
+            return Ok(parse);
+        }
+        (Err(e), None) => return Err(Error::PreprocessorError(e)),
+    };
+    let preprocess_time = start.elapsed();
+
+    let mut parse = try!(parse_preprocessed(config, processed));
+    parse.preprocessing = Preprocessing::External;
+    parse.stats.preprocess_time = Some(preprocess_time);
+    Ok(parse)
 }
 
 pub fn parse_preprocessed(config: &Config, source: String) -> Result<Parse, SyntaxError> {
+    let mut env = config_env(config);
+    parse_with_env(source, &mut env)
+}
+
+/// Incremental parser that keeps its state between inputs
+///
+/// Typedef names and other identifiers declared by earlier inputs remain
+/// visible when parsing later ones, which is what interactive tools need
+/// to parse C a few declarations at a time.
+///
+/// ```
+/// use lang_c::driver::{Config, ParserSession};
+///
+/// let mut session = ParserSession::new(&Config::default());
+/// session.feed("typedef int T;".to_string()).unwrap();
+/// session.feed("T x = (T) 1;".to_string()).unwrap();
+/// assert_eq!(session.parses().len(), 2);
+/// ```
+pub struct ParserSession {
+    env: Env,
+    parses: Vec<Parse>,
+}
+
+impl ParserSession {
+    /// Start a new session for the language flavor in `config`
+    pub fn new(config: &Config) -> ParserSession {
+        ParserSession {
+            env: config_env(config),
+            parses: Vec::new(),
+        }
+    }
+
+    /// Parse a pre-processed snippet in the context of all previous ones
+    ///
+    /// On success the result is appended to the session history and a
+    /// reference to it is returned. Spans in the returned tree refer to the
+    /// snippet itself. A snippet that fails to parse leaves the session
+    /// unchanged.
+    pub fn feed(&mut self, source: String) -> Result<&Parse, SyntaxError> {
+        let mut env = self.env.clone();
+        let parse = try!(parse_with_env(source, &mut env));
+        self.env = env;
+        self.parses.push(parse);
+        Ok(self.parses.last().unwrap())
+    }
+
+    /// Results of all successfully parsed snippets, in order
+    pub fn parses(&self) -> &[Parse] {
+        &self.parses
+    }
+
+    /// Check if a name is a type, as declared by the previous snippets or classified by the hook
+    pub fn is_typename(&self, name: &str) -> bool {
+        self.env.is_typename(name)
+    }
+
+    /// Classify identifiers that were not declared by any snippet
+    ///
+    /// Replaces the hook from `Config::typename_hook`. The hook is called with the name of an
+    /// undeclared identifier found where a type could appear and returns `true` to treat it as
+    /// a typedef name. This allows parsing code out of order, with types known from an external
+    /// source such as an index.
+    ///
+    /// ```
+    /// use lang_c::driver::{Config, ParserSession};
+    ///
+    /// let mut session = ParserSession::new(&Config::default());
+    /// session.set_typename_hook(|name| name.ends_with("_t"));
+    /// session.feed("void f(void) { (size_t) 1; }".to_string()).unwrap();
+    /// assert!(session.is_typename("size_t"));
+    /// ```
+    pub fn set_typename_hook<F>(&mut self, hook: F)
+    where
+        F: Fn(&str) -> bool + Send + Sync + RefUnwindSafe + 'static,
+    {
+        self.env.typename_hook = Some(Arc::new(hook));
+    }
+}
+
+fn config_env(config: &Config) -> Env {
     let mut env = match config.flavor {
         Flavor::StdC11 => Env::with_core(),
         Flavor::GnuC11 => Env::with_gnu(),
         Flavor::ClangC11 => Env::with_clang(),
     };
+    if config.report_ambiguities {
+        env.typedef_lookups = Some(Vec::new());
+    }
+    env.max_nesting = config.max_nesting_depth;
+    env.size_stats = config.size_stats;
+    if config.c23 {
+        env.enable_c23();
+    }
+    env.linkage_specifications = config.linkage_specifications;
+    env.comments = config.comments;
+    env.sal_annotations = config.sal_annotations;
+    env.builtin_macros = config.builtin_macros;
+    env.decimal_floats = config.decimal_floats;
+    for (alias, keyword) in &config.keyword_aliases {
+        env.add_keyword_alias(alias, keyword);
+    }
+    env.typename_hook = config.typename_hook.clone();
+    env
+}
 
-    match translation_unit(&source, &mut env) {
-        Ok(unit) => Ok(Parse {
-            source: source,
-            unit: unit,
-        }),
-        Err(err) => Err(SyntaxError {
-            source: source,
-            line: err.line,
-            column: err.column,
-            offset: err.offset,
-            expected: err.expected,
-        }),
+fn parse_with_env(source: String, env: &mut Env) -> Result<Parse, SyntaxError> {
+    env.peak_nesting = 0;
+    env.memo_entries = 0;
+    let start = Instant::now();
+    let result = translation_unit(&source, env);
+    let parse_time = start.elapsed();
+    let nesting_exceeded = env.nesting_exceeded.take();
+
+    // Backtracking may look up the same identifier several times, keep the last decision
+    let mut lookups = BTreeMap::new();
+    if let Some(ref mut list) = env.typedef_lookups {
+        for (identifier, typename) in list.drain(..) {
+            lookups.insert(
+                identifier.span.start,
+                Ambiguity {
+                    identifier,
+                    typename,
+                },
+            );
+        }
     }
+
+    match result {
+        Ok(unit) => {
+            let size = if env.size_stats {
+                Some(size::total(&source, &unit))
+            } else {
+                None
+            };
+            Ok(Parse {
+                preprocessing: Preprocessing::None,
+                preprocessor_error: None,
+                unit,
+                ambiguities: lookups.into_iter().map(|(_, a)| a).collect(),
+                stats: Stats {
+                    source_bytes: source.len(),
+                    tokens: size.map(|s| s.tokens),
+                    nodes: size.map(|s| s.nodes),
+                    preprocess_time: None,
+                    parse_time,
+                    peak_nesting: env.peak_nesting,
+                    memo_entries: env.memo_entries,
+                },
+                source,
+            })
+        }
+        Err(err) => match nesting_exceeded {
+            Some(offset) => {
+                let (line, column) = line_column(&source, offset);
+                Err(SyntaxError {
+                    source,
+                    line,
+                    column,
+                    offset,
+                    expected: HashSet::new(),
+                    nesting_limit: Some(env.max_nesting),
+                })
+            }
+            None => Err(SyntaxError {
+                source,
+                line: err.line,
+                column: err.column,
+                offset: err.offset,
+                expected: err.expected,
+                nesting_limit: None,
+            }),
+        },
+    }
+}
+
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.bytes().filter(|&c| c == b'\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    (line, column)
+}
+
+fn read(path: &Path) -> io::Result<String> {
+    let mut text = String::new();
+    try!(try!(File::open(path)).read_to_string(&mut text));
+    Ok(text)
+}
+
+/// Check for directives that the preprocessor would not leave in its output
+fn has_directives(text: &str) -> bool {
+    first_directive(text).is_some()
+}
+
+/// Location of the first directive that the preprocessor would not leave in its output
+fn first_directive(text: &str) -> Option<Span> {
+    let mut start = 0;
+    for line in text.split('\n') {
+        let line_start = start;
+        start += line.len() + 1;
+        let trimmed = line.trim_start();
+        if !trimmed.starts_with('#') {
+            continue;
+        }
+        let directive = trimmed[1..].trim_start();
+        let passed = directive.is_empty()
+            || directive.starts_with(|c: char| c.is_ascii_digit())
+            || directive.starts_with("line")
+            || directive.starts_with("pragma")
+            || directive.starts_with("ident")
+            || directive.starts_with("sccs");
+        if !passed {
+            let offset = line_start + line.len() - trimmed.len();
+            return Some(Span::span(offset, line_start + line.trim_end().len()));
+        }
+    }
+    None
+}
+
+/// Replace directives and their continuation lines with empty lines
+fn strip_directives(text: &str) -> String {
+    let mut continued = false;
+    let lines: Vec<_> = text
+        .split('\n')
+        .map(|line| {
+            if continued || line.trim_start().starts_with('#') {
+                continued = line.trim_end_matches('\r').ends_with('\\');
+                ""
+            } else {
+                line
+            }
+        })
+        .collect();
+    lines.join("\n")
 }
 
 fn preprocess(config: &Config, source: &Path) -> io::Result<String> {
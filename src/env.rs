@@ -1,4 +1,6 @@
 use std::collections::{HashMap, HashSet};
+use std::panic::RefUnwindSafe;
+use std::sync::Arc;
 
 use ast::*;
 use span::Node;
@@ -10,11 +12,37 @@ pub enum Symbol {
     Identifier,
 }
 
+/// Default limit on nesting of expressions, statements and declarators
+///
+/// Admits the nesting that C11 5.2.4.1 requires implementations to support, such as 127 levels
+/// of blocks and 63 levels of parentheses, and keeps the recursive descent parser within a 2MB
+/// thread stack, also in debug builds.
+pub const DEFAULT_MAX_NESTING: usize = 128;
+
+/// Classifies identifiers missing from the symbol table, `true` for typedef names
+pub type TypenameHook = Arc<dyn Fn(&str) -> bool + Send + Sync + RefUnwindSafe>;
+
+#[derive(Clone)]
 pub struct Env {
     pub symbols: Vec<HashMap<String, Symbol>>,
     pub extensions_gnu: bool,
     pub extensions_clang: bool,
+    pub c23: bool,
+    pub linkage_specifications: bool,
+    pub comments: bool,
+    pub sal_annotations: bool,
+    pub builtin_macros: bool,
+    pub decimal_floats: bool,
     pub reserved: HashSet<&'static str>,
+    pub typedef_lookups: Option<Vec<(Node<Identifier>, bool)>>,
+    pub nesting: usize,
+    pub max_nesting: usize,
+    pub peak_nesting: usize,
+    pub memo_entries: usize,
+    pub size_stats: bool,
+    pub nesting_exceeded: Option<usize>,
+    pub typename_hook: Option<TypenameHook>,
+    pub keyword_aliases: HashMap<String, &'static str>,
 }
 
 impl Env {
@@ -29,8 +57,23 @@ impl Env {
         Env {
             extensions_gnu: false,
             extensions_clang: false,
+            c23: false,
+            linkage_specifications: false,
+            comments: false,
+            sal_annotations: false,
+            builtin_macros: false,
+            decimal_floats: true,
             symbols: vec![HashMap::default()],
             reserved: reserved,
+            typedef_lookups: None,
+            nesting: 0,
+            max_nesting: DEFAULT_MAX_NESTING,
+            peak_nesting: 0,
+            memo_entries: 0,
+            size_stats: false,
+            nesting_exceeded: None,
+            typename_hook: None,
+            keyword_aliases: HashMap::new(),
         }
     }
 
@@ -38,13 +81,30 @@ impl Env {
         let mut symbols = HashMap::default();
         let mut reserved = HashSet::default();
         symbols.insert("__builtin_va_list".to_owned(), Symbol::Typename);
+        symbols.insert("__int128_t".to_owned(), Symbol::Typename);
+        symbols.insert("__uint128_t".to_owned(), Symbol::Typename);
         reserved.extend(strings::RESERVED_C11.iter());
         reserved.extend(strings::RESERVED_GNU.iter());
         Env {
             extensions_gnu: true,
             extensions_clang: false,
+            c23: false,
+            linkage_specifications: false,
+            comments: false,
+            sal_annotations: false,
+            builtin_macros: false,
+            decimal_floats: true,
             symbols: vec![symbols],
             reserved: reserved,
+            typedef_lookups: None,
+            nesting: 0,
+            max_nesting: DEFAULT_MAX_NESTING,
+            peak_nesting: 0,
+            memo_entries: 0,
+            size_stats: false,
+            nesting_exceeded: None,
+            typename_hook: None,
+            keyword_aliases: HashMap::new(),
         }
     }
 
@@ -52,14 +112,31 @@ impl Env {
         let mut symbols = HashMap::default();
         let mut reserved = HashSet::default();
         symbols.insert("__builtin_va_list".to_owned(), Symbol::Typename);
+        symbols.insert("__int128_t".to_owned(), Symbol::Typename);
+        symbols.insert("__uint128_t".to_owned(), Symbol::Typename);
         reserved.extend(strings::RESERVED_C11.iter());
         reserved.extend(strings::RESERVED_GNU.iter());
         reserved.extend(strings::RESERVED_CLANG.iter());
         Env {
             extensions_gnu: true,
             extensions_clang: true,
+            c23: false,
+            linkage_specifications: false,
+            comments: false,
+            sal_annotations: false,
+            builtin_macros: false,
+            decimal_floats: true,
             symbols: vec![symbols],
             reserved: reserved,
+            typedef_lookups: None,
+            nesting: 0,
+            max_nesting: DEFAULT_MAX_NESTING,
+            peak_nesting: 0,
+            memo_entries: 0,
+            size_stats: false,
+            nesting_exceeded: None,
+            typename_hook: None,
+            keyword_aliases: HashMap::new(),
         }
     }
 
@@ -71,13 +148,99 @@ impl Env {
         self.symbols.pop().expect("more scope pops than pushes");
     }
 
-    pub fn is_typename(&self, ident: &str) -> bool {
+    /// Make `alias` an alternative spelling of `keyword`, returns false if `keyword` can not be aliased
+    pub fn add_keyword_alias(&mut self, alias: &str, keyword: &str) -> bool {
+        match strings::ALIASABLE.iter().find(|&&k| k == keyword) {
+            Some(k) => {
+                self.keyword_aliases.insert(alias.to_owned(), k);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn keyword_alias(&self, name: &str) -> Result<&'static str, &'static str> {
+        self.keyword_aliases
+            .get(name)
+            .cloned()
+            .ok_or("keyword alias")
+    }
+
+    pub fn is_reserved(&self, name: &str) -> bool {
+        self.reserved.contains(name) || self.keyword_aliases.contains_key(name)
+    }
+
+    /// Check if `name` is a Microsoft source code annotation to skip
+    pub fn sal_annotation(&self, name: &str) -> Result<(), &'static str> {
+        if !self.sal_annotations {
+            return Err("sal annotations disabled");
+        }
+        let sal2 = name.ends_with('_') && strings::SAL_PREFIXES.iter().any(|p| name.starts_with(p));
+        let sal1 = strings::SAL1_ANNOTATIONS.iter().any(|&a| {
+            name.starts_with(a) && (name.len() == a.len() || name[a.len()..].starts_with('_'))
+        });
+        if sal2 || sal1 {
+            Ok(())
+        } else {
+            Err("sal annotation")
+        }
+    }
+
+    pub fn enable_c23(&mut self) {
+        self.c23 = true;
+        self.reserved.extend(strings::RESERVED_C23.iter());
+    }
+
+    pub fn enter_nesting(&mut self, offset: usize) -> Result<(), &'static str> {
+        if let Some(exceeded) = self.nesting_exceeded {
+            if offset > exceeded {
+                // The parser got past the failure, which did not cause the final error
+                self.nesting_exceeded = None;
+            } else {
+                // Other ways of parsing the input before it would nest as deep, and trying all of
+                // them takes exponential time
+                return Err("<nesting depth limit>");
+            }
+        }
+        if self.nesting < self.max_nesting {
+            self.nesting += 1;
+            self.peak_nesting = self.peak_nesting.max(self.nesting);
+            Ok(())
+        } else {
+            self.nesting_exceeded = Some(offset);
+            Err("<nesting depth limit>")
+        }
+    }
+
+    pub fn leave_nesting(&mut self) {
+        self.nesting -= 1;
+    }
+
+    pub fn lookup(&self, ident: &str) -> Option<Symbol> {
         for scope in self.symbols.iter().rev() {
             if let Some(symbol) = scope.get(ident) {
-                return *symbol == Symbol::Typename;
+                return Some(*symbol);
             }
         }
-        false
+        None
+    }
+
+    pub fn is_typename(&self, ident: &str) -> bool {
+        match self.lookup(ident) {
+            Some(symbol) => symbol == Symbol::Typename,
+            None => match self.typename_hook {
+                Some(ref hook) => hook(ident),
+                None => false,
+            },
+        }
+    }
+
+    pub fn is_typename_lookup(&mut self, ident: &Node<Identifier>) -> bool {
+        let typename = self.is_typename(&ident.node.name);
+        if let Some(ref mut lookups) = self.typedef_lookups {
+            lookups.push((ident.clone(), typename));
+        }
+        typename
     }
 
     pub fn handle_declarator(&mut self, d: &Node<Declarator>, sym: Symbol) {
@@ -0,0 +1,866 @@
+//! Evaluation of side-effect-free expressions
+//!
+//! Computes the value of arithmetic expressions using values of variables supplied by the
+//! caller, for example to show watch expressions in a debugger.
+//!
+//! ```
+//! use lang_c::ast::{ExternalDeclaration, Initializer};
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::eval::{eval, EvalError, Value};
+//!
+//! let source = "int r = (a + 1) * 2 > b ? a << 4 : -1, s = c++;".to_string();
+//! let parse = parse_preprocessed(&Config::default(), source).unwrap();
+//! let declaration = match parse.unit.0[0].node {
+//!     ExternalDeclaration::Declaration(ref d) => d,
+//!     _ => unreachable!(),
+//! };
+//! let initializer = |i: usize| match declaration.node.declarators[i].node.initializer {
+//!     Some(ref init) => match init.node {
+//!         Initializer::Expression(ref e) => e,
+//!         _ => unreachable!(),
+//!     },
+//!     None => unreachable!(),
+//! };
+//!
+//! let variables = |name: &str| match name {
+//!     "a" => Some(Value::Int(3)),
+//!     "b" => Some(Value::Unsigned(5)),
+//!     _ => None,
+//! };
+//! assert_eq!(eval(initializer(0), &variables), Ok(Value::Int(48)));
+//!
+//! match eval(initializer(1), &variables) {
+//!     Err(EvalError::SideEffect(_)) => {}
+//!     r => panic!("unexpected result {:?}", r),
+//! }
+//! ```
+//!
+//! Values keep their C type: integers are promoted to `int` or one of the wider types, with the
+//! widths of the target's data model, and `double` is the only floating point type. Operands
+//! undergo the usual arithmetic conversions, unsigned results wrap around at the width of their
+//! type, and signed results that do not fit are an overflow. Casts to `char` and `short` give the
+//! truncated value promoted to `int`.
+//!
+//! ```
+//! # use lang_c::ast::{ExternalDeclaration, Initializer};
+//! # use lang_c::driver::{parse_preprocessed, Config};
+//! # use lang_c::eval::{eval, Value};
+//! let source = "int a = ~0u, b = (unsigned)-1 + 1, c = 1 ? 2 : 3.0, d = 1 << 31;".to_string();
+//! # let parse = parse_preprocessed(&Config::default(), source).unwrap();
+//! # let declaration = match parse.unit.0[0].node {
+//! #     ExternalDeclaration::Declaration(ref d) => d,
+//! #     _ => unreachable!(),
+//! # };
+//! # let initializer = |i: usize| match declaration.node.declarators[i].node.initializer {
+//! #     Some(ref init) => match init.node {
+//! #         Initializer::Expression(ref e) => e,
+//! #         _ => unreachable!(),
+//! #     },
+//! #     None => unreachable!(),
+//! # };
+//! let variables = |_: &str| None;
+//! assert_eq!(eval(initializer(0), &variables), Ok(Value::Unsigned(4294967295)));
+//! assert_eq!(eval(initializer(1), &variables), Ok(Value::Unsigned(0)));
+//! assert_eq!(eval(initializer(2), &variables), Ok(Value::Float(2.0)));
+//! assert!(eval(initializer(3), &variables).is_err());
+//! ```
+
+use std::error;
+use std::fmt;
+
+use ast::*;
+use layout::{Endianness, Target};
+use span::{Node, Span};
+
+/// Value of an evaluated expression
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    /// `int`
+    Int(i64),
+    /// `unsigned int`
+    Unsigned(u64),
+    /// `long`
+    Long(i64),
+    /// `unsigned long`
+    UnsignedLong(u64),
+    /// `long long`
+    LongLong(i64),
+    /// `unsigned long long`
+    UnsignedLongLong(u64),
+    /// `double`
+    Float(f64),
+}
+
+/// Reason an expression could not be evaluated
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    /// Identifier has no value in the environment
+    UnknownIdentifier(Node<Identifier>),
+    /// Expression modifies a variable or calls a function
+    SideEffect(Span),
+    /// Expression can not be evaluated without type information or memory
+    Unsupported(Span),
+    /// Division or remainder by zero
+    DivisionByZero(Span),
+    /// Result of signed integer arithmetic does not fit in the value
+    Overflow(Span),
+    /// Shift count is negative or not less than the width of the value
+    InvalidShift(Span),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EvalError::UnknownIdentifier(ref i) => {
+                write!(fmt, "unknown identifier `{}`", i.node.name)
+            }
+            EvalError::SideEffect(_) => write!(fmt, "expression has side effects"),
+            EvalError::Unsupported(_) => write!(fmt, "expression is not supported"),
+            EvalError::DivisionByZero(_) => write!(fmt, "division by zero"),
+            EvalError::Overflow(_) => write!(fmt, "integer overflow"),
+            EvalError::InvalidShift(_) => write!(fmt, "invalid shift count"),
+        }
+    }
+}
+
+impl error::Error for EvalError {
+    fn description(&self) -> &str {
+        "expression evaluation error"
+    }
+}
+
+impl Value {
+    /// Test if the value compares unequal to zero
+    pub fn is_true(&self) -> bool {
+        match self.integer() {
+            Some(i) => i != 0,
+            None => self.float() != 0.0,
+        }
+    }
+
+    /// Value of an integer, `None` for a floating point number
+    pub fn integer(&self) -> Option<i128> {
+        match *self {
+            Value::Int(i) | Value::Long(i) | Value::LongLong(i) => Some(i as i128),
+            Value::Unsigned(u) | Value::UnsignedLong(u) | Value::UnsignedLongLong(u) => {
+                Some(u as i128)
+            }
+            Value::Float(_) => None,
+        }
+    }
+
+    /// Value converted to a floating point number
+    pub fn float(&self) -> f64 {
+        match (*self, self.integer()) {
+            (Value::Float(f), _) => f,
+            (_, Some(i)) => i as f64,
+            (_, None) => unreachable!("values are integers or floating point numbers"),
+        }
+    }
+
+    fn kind(&self) -> Kind {
+        match *self {
+            Value::Int(_) => Kind::Integer(Rank::Int, false),
+            Value::Unsigned(_) => Kind::Integer(Rank::Int, true),
+            Value::Long(_) => Kind::Integer(Rank::Long, false),
+            Value::UnsignedLong(_) => Kind::Integer(Rank::Long, true),
+            Value::LongLong(_) => Kind::Integer(Rank::LongLong, false),
+            Value::UnsignedLongLong(_) => Kind::Integer(Rank::LongLong, true),
+            Value::Float(_) => Kind::Float,
+        }
+    }
+}
+
+/// Execution character set, in which plain character constants and string literals are encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    /// UTF-8, with characters outside ASCII taking more than one byte
+    Utf8,
+    /// ISO 8859-1
+    Latin1,
+    /// EBCDIC code page 037, as used on IBM mainframes
+    Ebcdic,
+}
+
+impl Charset {
+    /// Bytes that encode a character, or `None` if the character set does not contain it
+    pub fn encode(&self, c: char) -> Option<Vec<u8>> {
+        match *self {
+            Charset::Utf8 => {
+                let mut buffer = [0; 4];
+                Some(c.encode_utf8(&mut buffer).as_bytes().to_vec())
+            }
+            Charset::Latin1 if (c as u32) < 0x100 => Some(vec![c as u8]),
+            Charset::Ebcdic if (c as u32) < 0x100 => Some(vec![EBCDIC[c as usize]]),
+            _ => None,
+        }
+    }
+}
+
+/// Code page 037 bytes of the characters U+0000 to U+00FF
+const EBCDIC: [u8; 256] = [
+    0x00, 0x01, 0x02, 0x03, 0x37, 0x2d, 0x2e, 0x2f, 0x16, 0x05, 0x25, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+    0x10, 0x11, 0x12, 0x13, 0x3c, 0x3d, 0x32, 0x26, 0x18, 0x19, 0x3f, 0x27, 0x1c, 0x1d, 0x1e, 0x1f,
+    0x40, 0x5a, 0x7f, 0x7b, 0x5b, 0x6c, 0x50, 0x7d, 0x4d, 0x5d, 0x5c, 0x4e, 0x6b, 0x60, 0x4b, 0x61,
+    0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0x7a, 0x5e, 0x4c, 0x7e, 0x6e, 0x6f,
+    0x7c, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7, 0xc8, 0xc9, 0xd1, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6,
+    0xd7, 0xd8, 0xd9, 0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, 0xba, 0xe0, 0xbb, 0xb0, 0x6d,
+    0x79, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96,
+    0x97, 0x98, 0x99, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7, 0xa8, 0xa9, 0xc0, 0x4f, 0xd0, 0xa1, 0x07,
+    0x20, 0x21, 0x22, 0x23, 0x24, 0x15, 0x06, 0x17, 0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x09, 0x0a, 0x1b,
+    0x30, 0x31, 0x1a, 0x33, 0x34, 0x35, 0x36, 0x08, 0x38, 0x39, 0x3a, 0x3b, 0x04, 0x14, 0x3e, 0xff,
+    0x41, 0xaa, 0x4a, 0xb1, 0x9f, 0xb2, 0x6a, 0xb5, 0xbd, 0xb4, 0x9a, 0x8a, 0x5f, 0xca, 0xaf, 0xbc,
+    0x90, 0x8f, 0xea, 0xfa, 0xbe, 0xa0, 0xb6, 0xb3, 0x9d, 0xda, 0x9b, 0x8b, 0xb7, 0xb8, 0xb9, 0xab,
+    0x64, 0x65, 0x62, 0x66, 0x63, 0x67, 0x9e, 0x68, 0x74, 0x71, 0x72, 0x73, 0x78, 0x75, 0x76, 0x77,
+    0xac, 0x69, 0xed, 0xee, 0xeb, 0xef, 0xec, 0xbf, 0x80, 0xfd, 0xfe, 0xfb, 0xfc, 0xad, 0xae, 0x59,
+    0x44, 0x45, 0x42, 0x46, 0x43, 0x47, 0x9c, 0x48, 0x54, 0x51, 0x52, 0x53, 0x58, 0x55, 0x56, 0x57,
+    0x8c, 0x49, 0xcd, 0xce, 0xcb, 0xcf, 0xcc, 0xe1, 0x70, 0xdd, 0xde, 0xdb, 0xdc, 0x8d, 0x8e, 0xdf,
+];
+
+/// Evaluate an expression
+///
+/// Values of identifiers are obtained from `variables`. Evaluation stops at the first
+/// subexpression with side effects, such as an assignment or a function call. Integer types have
+/// the widths of the LP64 data model and character constants are encoded in UTF-8.
+pub fn eval<F>(expr: &Node<Expression>, variables: &F) -> Result<Value, EvalError>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    eval_with(expr, variables, &Target::lp64(Endianness::Little))
+}
+
+/// Evaluate an expression with the integer widths and character set of `target`
+///
+/// ```
+/// use lang_c::ast::{ExternalDeclaration, Initializer};
+/// use lang_c::driver::{parse_preprocessed, Config};
+/// use lang_c::eval::{eval_with, Charset, Value};
+/// use lang_c::layout::{Endianness, Target};
+///
+/// let source = "int c = 'é', l = -1L < 1u;".to_string();
+/// let parse = parse_preprocessed(&Config::default(), source).unwrap();
+/// let declaration = match parse.unit.0[0].node {
+///     ExternalDeclaration::Declaration(ref d) => d,
+///     _ => unreachable!(),
+/// };
+/// let initializer = |i: usize| match declaration.node.declarators[i].node.initializer {
+///     Some(ref init) => match init.node {
+///         Initializer::Expression(ref e) => e,
+///         _ => unreachable!(),
+///     },
+///     None => unreachable!(),
+/// };
+///
+/// let variables = |_: &str| None;
+/// let mut target = Target::lp64(Endianness::Little);
+/// target.charset = Charset::Latin1;
+/// assert_eq!(eval_with(initializer(0), &variables, &target), Ok(Value::Int(-23)));
+/// target.charset = Charset::Ebcdic;
+/// assert_eq!(eval_with(initializer(0), &variables, &target), Ok(Value::Int(0x51)));
+/// target.charset = Charset::Utf8;
+/// assert!(eval_with(initializer(0), &variables, &target).is_err());
+///
+/// // `1u` converts to `long` when it is wider than `int`, and `-1L` to `unsigned long` otherwise
+/// assert_eq!(eval_with(initializer(1), &variables, &target), Ok(Value::Int(1)));
+/// let target = Target::ilp32(Endianness::Little);
+/// assert_eq!(eval_with(initializer(1), &variables, &target), Ok(Value::Int(0)));
+/// ```
+pub fn eval_with<F>(
+    expr: &Node<Expression>,
+    variables: &F,
+    target: &Target,
+) -> Result<Value, EvalError>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    Evaluator { variables, target }.eval(expr)
+}
+
+/// Integer conversion rank of a promoted integer type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Rank {
+    Int,
+    Long,
+    LongLong,
+}
+
+/// Type of a value
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    /// Integer with its rank and whether it is unsigned
+    Integer(Rank, bool),
+    Float,
+}
+
+/// Type named in a cast
+#[derive(Debug, Clone, Copy)]
+enum CastType {
+    Bool,
+    /// Integer narrower than `int` with its width in bits and whether it is unsigned
+    Narrow(u32, bool),
+    Kind(Kind),
+}
+
+struct Evaluator<'a, F: 'a> {
+    variables: &'a F,
+    target: &'a Target,
+}
+
+impl<'a, F> Evaluator<'a, F>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    fn eval(&self, expr: &Node<Expression>) -> Result<Value, EvalError> {
+        let span = expr.span;
+        match expr.node {
+            Expression::Identifier(ref i) => match (self.variables)(&i.node.name) {
+                Some(v) => self.convert(v, v.kind(), span),
+                None => Err(EvalError::UnknownIdentifier((**i).clone())),
+            },
+            Expression::Constant(ref c) => {
+                self.constant(&c.node).ok_or(EvalError::Unsupported(span))
+            }
+            Expression::UnaryOperator(ref u) => self.unary(&u.node, span),
+            Expression::BinaryOperator(ref b) => self.binary(&b.node, span),
+            Expression::Conditional(ref c) => {
+                let condition = try!(self.eval(&c.node.condition));
+                let then = match c.node.then_expression {
+                    Some(ref e) => &**e,
+                    None => &c.node.condition,
+                };
+                let (value, other) = if condition.is_true() {
+                    (try!(self.eval(then)), &*c.node.else_expression)
+                } else {
+                    (try!(self.eval(&c.node.else_expression)), then)
+                };
+                // The result has the common type of both operands
+                match self.type_of(other) {
+                    Some(kind) => self.convert(value, self.common(value.kind(), kind), span),
+                    None => Ok(value),
+                }
+            }
+            Expression::Choose(ref c) => {
+                if try!(self.eval(&c.node.condition)).is_true() {
+                    self.eval(&c.node.then_expression)
+                } else {
+                    self.eval(&c.node.else_expression)
+                }
+            }
+            Expression::Comma(ref exprs) => {
+                let mut value = Err(EvalError::Unsupported(span));
+                for expr in exprs.iter() {
+                    value = Ok(try!(self.eval(expr)));
+                }
+                value
+            }
+            Expression::Cast(ref c) => {
+                let value = try!(self.eval(&c.node.expression));
+                self.cast(&c.node.type_name.node, value, span)
+            }
+            Expression::Call(_) => Err(EvalError::SideEffect(span)),
+            _ => Err(EvalError::Unsupported(span)),
+        }
+    }
+
+    /// Type of an expression, `None` if it is not known without evaluating the expression
+    fn type_of(&self, expr: &Node<Expression>) -> Option<Kind> {
+        let int = Kind::Integer(Rank::Int, false);
+        match expr.node {
+            Expression::Identifier(ref i) => (self.variables)(&i.node.name).map(|v| v.kind()),
+            Expression::Constant(ref c) => self.constant(&c.node).map(|v| v.kind()),
+            Expression::UnaryOperator(ref u) => match u.node.operator.node {
+                UnaryOperator::Plus
+                | UnaryOperator::Minus
+                | UnaryOperator::Complement
+                | UnaryOperator::Real
+                | UnaryOperator::Imag
+                | UnaryOperator::Extension => self.type_of(&u.node.operand),
+                UnaryOperator::Negate => Some(int),
+                _ => None,
+            },
+            Expression::BinaryOperator(ref b) => {
+                use ast::BinaryOperator::*;
+
+                match b.node.operator.node {
+                    Less | Greater | LessOrEqual | GreaterOrEqual | Equals | NotEquals
+                    | LogicalAnd | LogicalOr => Some(int),
+                    ShiftLeft | ShiftRight => self.type_of(&b.node.lhs),
+                    Multiply | Divide | Modulo | Plus | Minus | BitwiseAnd | BitwiseXor
+                    | BitwiseOr => {
+                        let lhs = self.type_of(&b.node.lhs)?;
+                        Some(self.common(lhs, self.type_of(&b.node.rhs)?))
+                    }
+                    _ => None,
+                }
+            }
+            Expression::Conditional(ref c) => {
+                let then = match c.node.then_expression {
+                    Some(ref e) => self.type_of(e)?,
+                    None => self.type_of(&c.node.condition)?,
+                };
+                Some(self.common(then, self.type_of(&c.node.else_expression)?))
+            }
+            Expression::Choose(ref c) => {
+                if self.eval(&c.node.condition).ok()?.is_true() {
+                    self.type_of(&c.node.then_expression)
+                } else {
+                    self.type_of(&c.node.else_expression)
+                }
+            }
+            Expression::Comma(ref exprs) => self.type_of(exprs.last()?),
+            Expression::Cast(ref c) => match self.cast_type(&c.node.type_name.node)? {
+                CastType::Bool | CastType::Narrow(..) => Some(int),
+                CastType::Kind(kind) => Some(kind),
+            },
+            _ => None,
+        }
+    }
+
+    fn unary(&self, expr: &UnaryOperatorExpression, span: Span) -> Result<Value, EvalError> {
+        match expr.operator.node {
+            UnaryOperator::PostIncrement
+            | UnaryOperator::PostDecrement
+            | UnaryOperator::PreIncrement
+            | UnaryOperator::PreDecrement => return Err(EvalError::SideEffect(span)),
+            UnaryOperator::Address
+            | UnaryOperator::Indirection
+            | UnaryOperator::SizeOf
+            | UnaryOperator::AlignOf => return Err(EvalError::Unsupported(span)),
+            _ => {}
+        }
+
+        let value = try!(self.eval(&expr.operand));
+        match (&expr.operator.node, value.kind(), value.integer()) {
+            (&UnaryOperator::Plus, _, _) => Ok(value),
+            (&UnaryOperator::Minus, _, None) => Ok(Value::Float(-value.float())),
+            (&UnaryOperator::Minus, Kind::Integer(rank, unsigned), Some(i)) => {
+                self.arithmetic(Some(-i), rank, unsigned, span)
+            }
+            (&UnaryOperator::Complement, Kind::Integer(rank, unsigned), Some(i)) => {
+                Ok(self.wrap(!i, rank, unsigned))
+            }
+            (&UnaryOperator::Negate, _, _) => Ok(Value::Int(!value.is_true() as i64)),
+            // Values are never complex, their imaginary part is zero
+            (&UnaryOperator::Real, _, _) | (&UnaryOperator::Extension, _, _) => Ok(value),
+            (&UnaryOperator::Imag, kind, _) => self.convert(Value::Int(0), kind, span),
+            _ => Err(EvalError::Unsupported(span)),
+        }
+    }
+
+    fn binary(&self, expr: &BinaryOperatorExpression, span: Span) -> Result<Value, EvalError> {
+        use ast::BinaryOperator::*;
+
+        match expr.operator.node {
+            Assign | AssignMultiply | AssignDivide | AssignModulo | AssignPlus | AssignMinus
+            | AssignShiftLeft | AssignShiftRight | AssignBitwiseAnd | AssignBitwiseXor
+            | AssignBitwiseOr => return Err(EvalError::SideEffect(span)),
+            Index => return Err(EvalError::Unsupported(span)),
+            _ => {}
+        }
+
+        let lhs = try!(self.eval(&expr.lhs));
+        match expr.operator.node {
+            LogicalAnd if !lhs.is_true() => return Ok(Value::Int(0)),
+            LogicalOr if lhs.is_true() => return Ok(Value::Int(1)),
+            _ => {}
+        }
+        let rhs = try!(self.eval(&expr.rhs));
+
+        match expr.operator.node {
+            LogicalAnd | LogicalOr => return Ok(Value::Int(rhs.is_true() as i64)),
+            ShiftLeft | ShiftRight => return self.shift(&expr.operator.node, lhs, rhs, span),
+            _ => {}
+        }
+
+        let op = &expr.operator.node;
+        let kind = self.common(lhs.kind(), rhs.kind());
+        let lhs = try!(self.convert(lhs, kind, span));
+        let rhs = try!(self.convert(rhs, kind, span));
+        let (rank, unsigned) = match kind {
+            Kind::Integer(rank, unsigned) => (rank, unsigned),
+            Kind::Float => {
+                let (a, b) = (lhs.float(), rhs.float());
+                return Ok(Value::Float(match *op {
+                    Multiply => a * b,
+                    Divide => a / b,
+                    Plus => a + b,
+                    Minus => a - b,
+                    Modulo | BitwiseAnd | BitwiseXor | BitwiseOr => {
+                        return Err(EvalError::Unsupported(span))
+                    }
+                    _ => return Ok(compare(op, a, b)),
+                }));
+            }
+        };
+        let (a, b) = match (lhs.integer(), rhs.integer()) {
+            (Some(a), Some(b)) => (a, b),
+            _ => unreachable!("operands have a common type after conversion"),
+        };
+        // Operands have at most 64 bits, only unsigned products can exceed 128 bits
+        let result = match *op {
+            Multiply if unsigned => Some(a.wrapping_mul(b)),
+            Multiply => a.checked_mul(b),
+            Divide | Modulo if b == 0 => return Err(EvalError::DivisionByZero(span)),
+            Divide => Some(a / b),
+            // The remainder is undefined if the quotient does not fit
+            Modulo if unsigned || self.fits(a / b, rank) => Some(a % b),
+            Modulo => None,
+            Plus => Some(a + b),
+            Minus => Some(a - b),
+            BitwiseAnd => Some(a & b),
+            BitwiseXor => Some(a ^ b),
+            BitwiseOr => Some(a | b),
+            _ => return Ok(compare(op, a, b)),
+        };
+        self.arithmetic(result, rank, unsigned, span)
+    }
+
+    /// Shift with the type of the promoted left operand
+    fn shift(
+        &self,
+        op: &BinaryOperator,
+        lhs: Value,
+        rhs: Value,
+        span: Span,
+    ) -> Result<Value, EvalError> {
+        let (value, rank, unsigned) = match (lhs.integer(), lhs.kind()) {
+            (Some(i), Kind::Integer(rank, unsigned)) => (i, rank, unsigned),
+            _ => return Err(EvalError::Unsupported(span)),
+        };
+        let count = match rhs.integer() {
+            Some(c) if c >= 0 && c < self.width(rank) as i128 => c as u32,
+            Some(_) => return Err(EvalError::InvalidShift(span)),
+            None => return Err(EvalError::Unsupported(span)),
+        };
+        match *op {
+            BinaryOperator::ShiftLeft if unsigned => Ok(self.wrap(value << count, rank, true)),
+            BinaryOperator::ShiftLeft if value < 0 => Err(EvalError::Overflow(span)),
+            BinaryOperator::ShiftLeft => self.arithmetic(Some(value << count), rank, false, span),
+            _ => Ok(integer_value(value >> count, rank, unsigned)),
+        }
+    }
+
+    /// Width of an integer type in bits
+    fn width(&self, rank: Rank) -> u32 {
+        let size = match rank {
+            Rank::Int => self.target.int_size,
+            Rank::Long => self.target.long_size,
+            Rank::LongLong => 8,
+        };
+        size as u32 * 8
+    }
+
+    /// Test if a value is in the range of a signed integer type
+    fn fits(&self, value: i128, rank: Rank) -> bool {
+        let limit = 1 << (self.width(rank) - 1);
+        -limit <= value && value < limit
+    }
+
+    /// Convert an integer to an integer type, modulo its width (C11 6.3.1.3)
+    fn wrap(&self, value: i128, rank: Rank, unsigned: bool) -> Value {
+        integer_value(wrap(value, self.width(rank), unsigned), rank, unsigned)
+    }
+
+    /// Result of integer arithmetic, `None` or out of range if it overflowed
+    ///
+    /// Unsigned results wrap around, signed ones that do not fit are an error.
+    fn arithmetic(
+        &self,
+        value: Option<i128>,
+        rank: Rank,
+        unsigned: bool,
+        span: Span,
+    ) -> Result<Value, EvalError> {
+        match value {
+            Some(v) if unsigned => Ok(self.wrap(v, rank, true)),
+            Some(v) if self.fits(v, rank) => Ok(integer_value(v, rank, false)),
+            _ => Err(EvalError::Overflow(span)),
+        }
+    }
+
+    /// Common type of the usual arithmetic conversions (C11 6.3.1.8)
+    fn common(&self, a: Kind, b: Kind) -> Kind {
+        let ((rank_a, unsigned_a), (rank_b, unsigned_b)) = match (a, b) {
+            (Kind::Integer(ra, ua), Kind::Integer(rb, ub)) => ((ra, ua), (rb, ub)),
+            _ => return Kind::Float,
+        };
+        if unsigned_a == unsigned_b {
+            return Kind::Integer(rank_a.max(rank_b), unsigned_a);
+        }
+        let (unsigned, signed) = if unsigned_a {
+            (rank_a, rank_b)
+        } else {
+            (rank_b, rank_a)
+        };
+        if unsigned >= signed {
+            Kind::Integer(unsigned, true)
+        } else if self.width(signed) > self.width(unsigned) {
+            Kind::Integer(signed, false)
+        } else {
+            Kind::Integer(signed, true)
+        }
+    }
+
+    /// Convert a value to a type, an error if a floating point value does not fit
+    fn convert(&self, value: Value, kind: Kind, span: Span) -> Result<Value, EvalError> {
+        match (value.integer(), kind) {
+            (Some(i), Kind::Integer(rank, unsigned)) => Ok(self.wrap(i, rank, unsigned)),
+            (None, Kind::Integer(rank, unsigned)) => {
+                match truncate(value.float(), self.width(rank), unsigned) {
+                    Some(i) => Ok(integer_value(i, rank, unsigned)),
+                    None => Err(EvalError::Overflow(span)),
+                }
+            }
+            (_, Kind::Float) => Ok(Value::Float(value.float())),
+        }
+    }
+
+    fn cast(&self, type_name: &TypeName, value: Value, span: Span) -> Result<Value, EvalError> {
+        match self.cast_type(type_name) {
+            Some(CastType::Bool) => Ok(Value::Int(value.is_true() as i64)),
+            // The result is promoted to `int`
+            Some(CastType::Narrow(width, unsigned)) => {
+                let i = match value.integer() {
+                    Some(i) => wrap(i, width, unsigned),
+                    None => match truncate(value.float(), width, unsigned) {
+                        Some(i) => i,
+                        None => return Err(EvalError::Overflow(span)),
+                    },
+                };
+                Ok(Value::Int(i as i64))
+            }
+            Some(CastType::Kind(kind)) => self.convert(value, kind, span),
+            None => Err(EvalError::Unsupported(span)),
+        }
+    }
+
+    fn cast_type(&self, type_name: &TypeName) -> Option<CastType> {
+        if type_name.declarator.is_some() {
+            return None;
+        }
+
+        let (mut width, mut unsigned, mut float, mut boolean) = (None, false, false, false);
+        let mut longs = 0;
+        for specifier in &type_name.specifiers {
+            let t = match specifier.node {
+                SpecifierQualifier::TypeSpecifier(ref t) => &t.node,
+                SpecifierQualifier::TypeQualifier(_) | SpecifierQualifier::Extension(_) => continue,
+            };
+            match *t {
+                TypeSpecifier::Char => width = Some(8),
+                TypeSpecifier::Short => width = Some(16),
+                TypeSpecifier::Int | TypeSpecifier::Signed => {}
+                TypeSpecifier::Long => longs += 1,
+                TypeSpecifier::Unsigned => unsigned = true,
+                TypeSpecifier::Float | TypeSpecifier::Double => float = true,
+                TypeSpecifier::Bool => boolean = true,
+                _ => return None,
+            }
+        }
+
+        if boolean {
+            return Some(CastType::Bool);
+        }
+        if float {
+            return Some(CastType::Kind(Kind::Float));
+        }
+        let rank = match longs {
+            0 => Rank::Int,
+            1 => Rank::Long,
+            _ => Rank::LongLong,
+        };
+        match width {
+            Some(width) if width < self.width(Rank::Int) => Some(CastType::Narrow(width, unsigned)),
+            _ => Some(CastType::Kind(Kind::Integer(rank, unsigned))),
+        }
+    }
+
+    fn constant(&self, c: &Constant) -> Option<Value> {
+        match *c {
+            Constant::Integer(ref i) => self.integer(i),
+            Constant::Float(ref f) => float(f),
+            Constant::Character(ref c) => character(c, self.target.charset),
+            Constant::Nullptr => None,
+            Constant::Bool(b) => Some(Value::Int(b as i64)),
+        }
+    }
+
+    /// Integer constant with the first type of its suffix that can represent it (C11 6.4.4.1)
+    fn integer(&self, i: &Integer) -> Option<Value> {
+        if i.suffix.imaginary {
+            return None;
+        }
+        let radix = match i.base {
+            IntegerBase::Decimal => 10,
+            IntegerBase::Octal => 8,
+            IntegerBase::Hexadecimal => 16,
+            IntegerBase::Binary => 2,
+        };
+        let value = match u64::from_str_radix(&i.number, radix) {
+            Ok(v) => v as i128,
+            Err(_) => return None,
+        };
+        let ranks: &[Rank] = match i.suffix.size {
+            IntegerSize::Int => &[Rank::Int, Rank::Long, Rank::LongLong],
+            IntegerSize::Long => &[Rank::Long, Rank::LongLong],
+            IntegerSize::LongLong => &[Rank::LongLong],
+            IntegerSize::BitInt => return None,
+        };
+        // Decimal constants without a suffix are only unsigned if no signed type is wide enough
+        let decimal = i.base == IntegerBase::Decimal;
+        for &rank in ranks {
+            if !i.suffix.unsigned && self.fits(value, rank) {
+                return Some(integer_value(value, rank, false));
+            }
+            if (i.suffix.unsigned || !decimal) && value >> self.width(rank) == 0 {
+                return Some(integer_value(value, rank, true));
+            }
+        }
+        Some(Value::UnsignedLongLong(value as u64))
+    }
+}
+
+fn compare<T: PartialOrd>(op: &BinaryOperator, a: T, b: T) -> Value {
+    let result = match *op {
+        BinaryOperator::Less => a < b,
+        BinaryOperator::Greater => a > b,
+        BinaryOperator::LessOrEqual => a <= b,
+        BinaryOperator::GreaterOrEqual => a >= b,
+        BinaryOperator::Equals => a == b,
+        BinaryOperator::NotEquals => a != b,
+        _ => unreachable!("not a comparison operator"),
+    };
+    Value::Int(result as i64)
+}
+
+fn integer_value(value: i128, rank: Rank, unsigned: bool) -> Value {
+    match (rank, unsigned) {
+        (Rank::Int, false) => Value::Int(value as i64),
+        (Rank::Int, true) => Value::Unsigned(value as u64),
+        (Rank::Long, false) => Value::Long(value as i64),
+        (Rank::Long, true) => Value::UnsignedLong(value as u64),
+        (Rank::LongLong, false) => Value::LongLong(value as i64),
+        (Rank::LongLong, true) => Value::UnsignedLongLong(value as u64),
+    }
+}
+
+/// Reduce an integer modulo 2 to the power of `width`, to the range of the signedness
+fn wrap(value: i128, width: u32, unsigned: bool) -> i128 {
+    let value = value & ((1 << width) - 1);
+    if !unsigned && value >> (width - 1) != 0 {
+        value - (1 << width)
+    } else {
+        value
+    }
+}
+
+/// Integer part of a floating point number, `None` if it does not fit in `width` bits
+fn truncate(f: f64, width: u32, unsigned: bool) -> Option<i128> {
+    let f = f.trunc();
+    let limit = 2f64.powi(width as i32 - !unsigned as i32);
+    let min = if unsigned { 0.0 } else { -limit };
+    if min <= f && f < limit {
+        Some(f as i128)
+    } else {
+        None
+    }
+}
+
+fn float(f: &Float) -> Option<Value> {
+    if f.suffix.imaginary {
+        return None;
+    }
+    if let FloatFormat::TS18661Format(ref t) = f.suffix.format {
+        if t.format != TS18661FloatFormat::BinaryInterchange
+            && t.format != TS18661FloatFormat::BinaryExtended
+        {
+            return None;
+        }
+    }
+    match f.base {
+        FloatBase::Decimal => f.number.parse().ok().map(Value::Float),
+        FloatBase::Hexadecimal => hex_float(&f.number).map(Value::Float),
+    }
+}
+
+fn hex_float(s: &str) -> Option<f64> {
+    let p = s.find(|c| c == 'p' || c == 'P')?;
+    let exponent: i32 = s[p + 1..].parse().ok()?;
+    let mut mantissa = 0.0;
+    let mut scale = 0;
+    let mut fraction = false;
+    for c in s[..p].chars() {
+        match c {
+            '.' => fraction = true,
+            c => {
+                mantissa = mantissa * 16.0 + c.to_digit(16).unwrap() as f64;
+                if fraction {
+                    scale -= 4;
+                }
+            }
+        }
+    }
+    Some(mantissa * 2f64.powi(exponent + scale))
+}
+
+fn character(c: &Character, charset: Charset) -> Option<Value> {
+    let body = &*c.body;
+    let mut chars = body.chars();
+    // Numeric escapes give the value of a code unit, other characters are encoded
+    let (value, raw) = match chars.next() {
+        Some('\\') => match chars.next() {
+            Some('n') => ('\n' as u32, false),
+            Some('t') => ('\t' as u32, false),
+            Some('r') => ('\r' as u32, false),
+            Some('a') => (7, false),
+            Some('b') => (8, false),
+            Some('f') => (12, false),
+            Some('v') => (11, false),
+            Some('x') => {
+                let digits = chars.as_str();
+                chars = "".chars();
+                match u32::from_str_radix(digits, 16) {
+                    Ok(v) => (v, true),
+                    Err(_) => return None,
+                }
+            }
+            Some(d) if d.is_digit(8) => {
+                let digits = &body[1..];
+                chars = "".chars();
+                match u32::from_str_radix(digits, 8) {
+                    Ok(v) => (v, true),
+                    Err(_) => return None,
+                }
+            }
+            Some(c) => (c as u32, false),
+            None => return None,
+        },
+        Some(ch) => (ch as u32, false),
+        None => return None,
+    };
+    if chars.next().is_some() {
+        return None;
+    }
+    match c.encoding {
+        // Plain character constants have type int with the value of a (signed) char
+        CharacterEncoding::Plain => {
+            let unit = if raw {
+                value
+            } else {
+                let bytes = charset.encode(std::char::from_u32(value)?)?;
+                if bytes.len() != 1 {
+                    return None;
+                }
+                bytes[0] as u32
+            };
+            if unit <= 0xff {
+                Some(Value::Int(unit as u8 as i8 as i64))
+            } else {
+                None
+            }
+        }
+        // Characters outside ASCII do not fit in a single UTF-8 code unit
+        CharacterEncoding::Utf8 if value > 0xff || (value > 0x7f && !raw) => None,
+        _ => Some(Value::Int(value as i64)),
+    }
+}
@@ -0,0 +1,310 @@
+//! Expression builder
+//!
+//! Builds `Expression` trees for code generators and transformations. Every operand is wrapped in
+//! the node and box its parent expects, negative numbers become a unary minus applied to a
+//! constant, and string literals are escaped, so a built tree has the same shape as one produced
+//! by the parser. Grouping follows the order of the calls and the `unparse` module adds the
+//! parentheses it needs when printing.
+//!
+//! ```
+//! use lang_c::ast::TypeSpecifier;
+//! use lang_c::driver::Config;
+//! use lang_c::expr::{int, type_name, var};
+//! use lang_c::unparse::unparse_expression;
+//!
+//! let e = var("x").add(int(1)).mul(var("y")).cast(type_name(&[TypeSpecifier::Long]));
+//! assert_eq!(unparse_expression(&Config::default(), &e.into()), "(long)((x + 1) * y)");
+//!
+//! let e = var("f").call(vec![var("p").arrow("len"), int(-2)]).not();
+//! assert_eq!(unparse_expression(&Config::default(), &e.into()), "!f(p->len, -2)");
+//! ```
+//!
+//! Nodes of built trees have no span.
+
+// Methods are named after the C operators they build, not the Rust operators they resemble
+#![allow(clippy::should_implement_trait)]
+
+use ast::*;
+use span::{Node, Span};
+
+/// Expression under construction
+#[derive(Debug, PartialEq, Clone)]
+pub struct Expr(pub Node<Expression>);
+
+impl From<Expr> for Node<Expression> {
+    fn from(e: Expr) -> Node<Expression> {
+        e.0
+    }
+}
+
+impl From<Node<Expression>> for Expr {
+    fn from(e: Node<Expression>) -> Expr {
+        Expr(e)
+    }
+}
+
+/// Reference to a variable, function or enumerator
+pub fn var(name: &str) -> Expr {
+    let identifier = Identifier {
+        name: name.to_owned(),
+    };
+    expr(Expression::Identifier(node(identifier)))
+}
+
+/// Integer constant, negative values are negated positive constants
+pub fn int(value: i64) -> Expr {
+    let c = integer(value.wrapping_abs() as u64, false);
+    if value < 0 {
+        c.unary(UnaryOperator::Minus)
+    } else {
+        c
+    }
+}
+
+/// Integer constant with a `u` suffix
+pub fn unsigned(value: u64) -> Expr {
+    integer(value, true)
+}
+
+/// String literal with the given contents
+///
+/// Quotes, backslashes and characters outside of printable ASCII are escaped.
+pub fn string(value: &str) -> Expr {
+    let mut s = String::from("\"");
+    for &b in value.as_bytes() {
+        match b {
+            b'"' => s.push_str("\\\""),
+            b'\\' => s.push_str("\\\\"),
+            b'\n' => s.push_str("\\n"),
+            b'\t' => s.push_str("\\t"),
+            0x20..=0x7e => s.push(b as char),
+            _ => s.push_str(&format!("\\{:03o}", b)),
+        }
+    }
+    s.push('"');
+    expr(Expression::StringLiteral(node(vec![s])))
+}
+
+/// `sizeof(type)`
+pub fn size_of(type_name: TypeName) -> Expr {
+    expr(Expression::SizeOf(node(type_name)))
+}
+
+/// Expressions separated by commas, evaluated in order
+pub fn comma(expressions: Vec<Expr>) -> Expr {
+    let expressions = expressions.into_iter().map(|e| e.0).collect();
+    expr(Expression::Comma(Box::new(expressions)))
+}
+
+/// Type name made of type specifiers only, such as `unsigned long`
+pub fn type_name(specifiers: &[TypeSpecifier]) -> TypeName {
+    TypeName {
+        specifiers: specifiers
+            .iter()
+            .map(|s| {
+                let s = Node::new(s.clone(), Span::none());
+                Node::new(SpecifierQualifier::TypeSpecifier(s), Span::none())
+            })
+            .collect(),
+        declarator: None,
+    }
+}
+
+impl Expr {
+    /// Apply a binary operator with `self` on the left
+    pub fn binary(self, operator: BinaryOperator, rhs: Expr) -> Expr {
+        expr(Expression::BinaryOperator(node(BinaryOperatorExpression {
+            operator: Node::new(operator, Span::none()),
+            lhs: Box::new(self.0),
+            rhs: Box::new(rhs.0),
+        })))
+    }
+
+    /// Apply a prefix or postfix unary operator
+    pub fn unary(self, operator: UnaryOperator) -> Expr {
+        expr(Expression::UnaryOperator(node(UnaryOperatorExpression {
+            operator: Node::new(operator, Span::none()),
+            operand: Box::new(self.0),
+        })))
+    }
+
+    pub fn add(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::Plus, rhs)
+    }
+
+    pub fn sub(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::Minus, rhs)
+    }
+
+    pub fn mul(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::Multiply, rhs)
+    }
+
+    pub fn div(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::Divide, rhs)
+    }
+
+    pub fn rem(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::Modulo, rhs)
+    }
+
+    pub fn shl(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::ShiftLeft, rhs)
+    }
+
+    pub fn shr(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::ShiftRight, rhs)
+    }
+
+    pub fn lt(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::Less, rhs)
+    }
+
+    pub fn gt(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::Greater, rhs)
+    }
+
+    pub fn le(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::LessOrEqual, rhs)
+    }
+
+    pub fn ge(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::GreaterOrEqual, rhs)
+    }
+
+    pub fn equals(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::Equals, rhs)
+    }
+
+    pub fn not_equals(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::NotEquals, rhs)
+    }
+
+    pub fn bit_and(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::BitwiseAnd, rhs)
+    }
+
+    pub fn bit_xor(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::BitwiseXor, rhs)
+    }
+
+    pub fn bit_or(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::BitwiseOr, rhs)
+    }
+
+    /// `self && rhs`
+    pub fn and(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::LogicalAnd, rhs)
+    }
+
+    /// `self || rhs`
+    pub fn or(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::LogicalOr, rhs)
+    }
+
+    pub fn assign(self, rhs: Expr) -> Expr {
+        self.binary(BinaryOperator::Assign, rhs)
+    }
+
+    /// `self[index]`
+    pub fn index(self, index: Expr) -> Expr {
+        self.binary(BinaryOperator::Index, index)
+    }
+
+    /// `-self`
+    pub fn neg(self) -> Expr {
+        self.unary(UnaryOperator::Minus)
+    }
+
+    /// `!self`
+    pub fn not(self) -> Expr {
+        self.unary(UnaryOperator::Negate)
+    }
+
+    /// `~self`
+    pub fn complement(self) -> Expr {
+        self.unary(UnaryOperator::Complement)
+    }
+
+    /// `*self`
+    pub fn deref(self) -> Expr {
+        self.unary(UnaryOperator::Indirection)
+    }
+
+    /// `&self`
+    pub fn address(self) -> Expr {
+        self.unary(UnaryOperator::Address)
+    }
+
+    /// `sizeof self`
+    pub fn size_of(self) -> Expr {
+        self.unary(UnaryOperator::SizeOf)
+    }
+
+    /// `self(arguments...)`
+    pub fn call(self, arguments: Vec<Expr>) -> Expr {
+        expr(Expression::Call(node(CallExpression {
+            callee: Box::new(self.0),
+            arguments: arguments.into_iter().map(|a| a.0).collect(),
+        })))
+    }
+
+    /// `self.name`
+    pub fn member(self, name: &str) -> Expr {
+        self.member_access(MemberOperator::Direct, name)
+    }
+
+    /// `self->name`
+    pub fn arrow(self, name: &str) -> Expr {
+        self.member_access(MemberOperator::Indirect, name)
+    }
+
+    /// `(type_name) self`
+    pub fn cast(self, type_name: TypeName) -> Expr {
+        expr(Expression::Cast(node(CastExpression {
+            type_name: Node::new(type_name, Span::none()),
+            expression: Box::new(self.0),
+        })))
+    }
+
+    /// `self ? then_expression : else_expression`
+    pub fn conditional(self, then_expression: Expr, else_expression: Expr) -> Expr {
+        expr(Expression::Conditional(node(ConditionalExpression {
+            condition: Box::new(self.0),
+            then_expression: Some(Box::new(then_expression.0)),
+            else_expression: Box::new(else_expression.0),
+        })))
+    }
+
+    fn member_access(self, operator: MemberOperator, name: &str) -> Expr {
+        let identifier = Identifier {
+            name: name.to_owned(),
+        };
+        expr(Expression::Member(node(MemberExpression {
+            operator: Node::new(operator, Span::none()),
+            expression: Box::new(self.0),
+            identifier: Node::new(identifier, Span::none()),
+        })))
+    }
+}
+
+fn integer(value: u64, unsigned: bool) -> Expr {
+    let integer = Integer {
+        base: IntegerBase::Decimal,
+        number: value.to_string().into_boxed_str(),
+        suffix: IntegerSuffix {
+            size: IntegerSize::Int,
+            unsigned,
+            imaginary: false,
+        },
+    };
+    expr(Expression::Constant(node(Constant::Integer(integer))))
+}
+
+fn expr(e: Expression) -> Expr {
+    Expr(Node::new(e, Span::none()))
+}
+
+fn node<T>(value: T) -> Box<Node<T>> {
+    Box::new(Node::new(value, Span::none()))
+}
@@ -0,0 +1,505 @@
+//! Struct and union member lookup
+//!
+//! Resolves member paths such as `a.b[2].c` against the struct, union and typedef definitions
+//! of a translation unit. Anonymous struct and union members are searched transparently and
+//! typedef names are followed.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::fields::FieldLookup;
+//!
+//! let source = r#"
+//!     typedef struct { int x, y; } point;
+//!     struct shape {
+//!         int kind;
+//!         union { point corners[4]; int radius; };
+//!     };
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.to_string()).unwrap();
+//! let lookup = FieldLookup::new(&parse.unit);
+//!
+//! let path = lookup.find_field("struct shape", "corners[2].y").unwrap();
+//! let names: Vec<_> = path.iter().map(|f| f.name()).collect();
+//! assert_eq!(names, [None, Some("corners"), Some("y")]);
+//! assert_eq!(path[1].indices, [2]);
+//!
+//! assert!(lookup.find_field("struct shape", "kind.x").is_none());
+//! ```
+//!
+//! Tags and typedef names are collected from the whole translation unit without regard to
+//! scope; the first definition of each name is used.
+//!
+//! A lookup created for a target also gives the byte offset of each member, laid out as
+//! described in the `layout` module.
+//!
+//! ```
+//! # use lang_c::driver::{parse_preprocessed, Config};
+//! # use lang_c::fields::FieldLookup;
+//! # use lang_c::layout::{Endianness, Target};
+//! let source = "struct s { char c; struct { short x, y; } p[3]; };";
+//! # let parse = parse_preprocessed(&Config::default(), source.to_string()).unwrap();
+//! let lookup = FieldLookup::with_target(&parse.unit, Target::lp64(Endianness::Little));
+//! let path = lookup.find_field("struct s", "p[2].y").unwrap();
+//! let offsets: Vec<_> = path.iter().map(|f| f.offset).collect();
+//! assert_eq!(offsets, [Some(2), Some(12)]);
+//! ```
+
+use std::collections::HashMap;
+
+use ast::*;
+use eval::eval;
+use layout::{self, Layouts, Target};
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// Struct, union and typedef definitions of a translation unit
+pub struct FieldLookup<'ast> {
+    structs: HashMap<(StructKind, &'ast str), &'ast StructType>,
+    typedefs: HashMap<&'ast str, Type<'ast>>,
+    layouts: Option<Layouts<'ast>>,
+}
+
+/// Single member on a resolved path
+#[derive(Debug, Clone)]
+pub struct Field<'ast> {
+    /// Declaration of the member
+    pub field: &'ast Node<StructField>,
+    /// Declarator of the member, `None` for an anonymous struct or union
+    pub declarator: Option<&'ast Node<StructDeclarator>>,
+    /// Array subscripts applied to the member, in path order
+    pub indices: Vec<usize>,
+    /// Offset of the member from the start of the outermost type in bytes, before the
+    /// subscripts are applied
+    ///
+    /// Only known for a lookup created for a target and a type with a known layout.
+    pub offset: Option<usize>,
+}
+
+impl<'ast> Field<'ast> {
+    /// Name of the member, `None` for an anonymous struct or union
+    pub fn name(&self) -> Option<&'ast str> {
+        self.declarator
+            .and_then(|d| d.node.declarator.as_ref())
+            .and_then(|d| declarator_name(&d.node))
+    }
+}
+
+/// Base type that may name an aggregate and derived declarators applied to it
+///
+/// Derived declarators are stored innermost first, so the last one is the outermost type.
+#[derive(Clone)]
+struct Type<'ast> {
+    base: Base<'ast>,
+    derived: Vec<&'ast DerivedDeclarator>,
+}
+
+#[derive(Clone, Copy)]
+enum Base<'ast> {
+    Other,
+    Struct(&'ast StructType),
+    TypedefName(&'ast str),
+}
+
+impl<'ast> FieldLookup<'ast> {
+    /// Collect definitions from a translation unit
+    pub fn new(unit: &'ast TranslationUnit) -> FieldLookup<'ast> {
+        let mut lookup = FieldLookup {
+            structs: HashMap::new(),
+            typedefs: HashMap::new(),
+            layouts: None,
+        };
+        lookup.visit_translation_unit(unit);
+        lookup
+    }
+
+    /// Collect definitions from a translation unit, computing member offsets for `target`
+    pub fn with_target(unit: &'ast TranslationUnit, target: Target) -> FieldLookup<'ast> {
+        let mut lookup = FieldLookup::new(unit);
+        lookup.layouts = Some(Layouts::new(unit, target));
+        lookup
+    }
+
+    /// Resolve a member path within a type
+    ///
+    /// The type is either `struct tag`, `union tag` or a typedef name. The path is a sequence of
+    /// member names separated by `.`, each optionally followed by array subscripts such as `[2]`.
+    ///
+    /// Returns the chain of members from the outermost to the innermost, including any anonymous
+    /// members that were traversed, or `None` if the path does not resolve.
+    pub fn find_field(&self, ty: &str, path: &str) -> Option<Vec<Field<'ast>>> {
+        let mut current = self.parse_type(ty)?;
+        let mut result: Vec<Field<'ast>> = Vec::new();
+        let mut offset = Some(0);
+
+        for component in path.split('.') {
+            let (name, indices) = parse_component(component)?;
+            let struct_type = self.struct_type(&current)?;
+            let (mut fields, mut ty) = self.find_member(struct_type, name)?;
+            for _ in &indices {
+                ty = self.subscript(ty)?;
+            }
+            offset = offset.and_then(|o| self.offsets(struct_type, name, &indices, o, &mut fields));
+            fields.last_mut().unwrap().indices = indices;
+            result.extend(fields);
+            current = ty;
+        }
+
+        Some(result)
+    }
+
+    /// Set the offsets of the members leading to `name` from a struct at `offset`, and return the
+    /// offset of the subobject the subscripts select
+    fn offsets(
+        &self,
+        struct_type: &'ast StructType,
+        name: &str,
+        indices: &[usize],
+        offset: usize,
+        fields: &mut [Field<'ast>],
+    ) -> Option<usize> {
+        let layouts = self.layouts.as_ref()?;
+        let mut members = layouts.record(struct_type).ok()?.members;
+        let path = layouts.member_path(&members, name)?;
+        let (mut offset, mut ty) = (offset, None);
+        for (field, &index) in fields.iter_mut().zip(&path) {
+            let member = members.get(index)?.clone();
+            offset += member.offset;
+            field.offset = Some(offset);
+            if let layout::Type::Record(s, _) = member.ty {
+                if field.declarator.is_none() {
+                    members = layouts.record(s).ok()?.members;
+                }
+            }
+            ty = Some(member.ty);
+        }
+        let mut ty = ty?;
+        for &index in indices {
+            ty = match ty {
+                layout::Type::Array(element, _) => {
+                    offset += index * layouts.layout(&element).ok()?.size;
+                    *element
+                }
+                _ => return None,
+            };
+        }
+        Some(offset)
+    }
+
+    fn parse_type(&self, ty: &str) -> Option<Type<'ast>> {
+        let mut words = ty.split_whitespace();
+        let (first, second) = (words.next(), words.next());
+        if words.next().is_some() {
+            return None;
+        }
+        let kind = match (first, second) {
+            (Some(name), None) => return self.typedefs.get(name).cloned(),
+            (Some("struct"), Some(_)) => StructKind::Struct,
+            (Some("union"), Some(_)) => StructKind::Union,
+            _ => return None,
+        };
+        self.structs.get(&(kind, second.unwrap())).map(|&s| Type {
+            base: Base::Struct(s),
+            derived: Vec::new(),
+        })
+    }
+
+    /// Follow typedefs until a type with derived declarators or a non-typedef specifier
+    fn resolve(&self, mut ty: Type<'ast>) -> Type<'ast> {
+        for _ in 0..self.typedefs.len() {
+            if !ty.derived.is_empty() {
+                break;
+            }
+            match ty.base {
+                Base::TypedefName(name) => match self.typedefs.get(name) {
+                    Some(t) => ty = t.clone(),
+                    None => break,
+                },
+                _ => break,
+            }
+        }
+        ty
+    }
+
+    fn struct_type(&self, ty: &Type<'ast>) -> Option<&'ast StructType> {
+        let ty = self.resolve(ty.clone());
+        if !ty.derived.is_empty() {
+            return None;
+        }
+        match ty.base {
+            Base::Struct(s) => match (&s.declarations, &s.identifier) {
+                (&Some(_), _) => Some(s),
+                (&None, Some(i)) => {
+                    let key = (s.kind.node.clone(), &*i.node.name);
+                    self.structs.get(&key).cloned()
+                }
+                (&None, &None) => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn subscript(&self, ty: Type<'ast>) -> Option<Type<'ast>> {
+        let mut ty = self.resolve(ty);
+        match ty.derived.pop() {
+            Some(&DerivedDeclarator::Array(_)) => Some(ty),
+            _ => None,
+        }
+    }
+
+    fn find_member(
+        &self,
+        struct_type: &'ast StructType,
+        name: &str,
+    ) -> Option<(Vec<Field<'ast>>, Type<'ast>)> {
+        let declarations = match struct_type.declarations {
+            Some(ref d) => d,
+            None => return None,
+        };
+        for declaration in declarations {
+            let field = match declaration.node {
+                StructDeclaration::Field(ref f) => f,
+                StructDeclaration::StaticAssert(_) => continue,
+            };
+            let base = field
+                .node
+                .specifiers
+                .iter()
+                .filter_map(|s| match s.node {
+                    SpecifierQualifier::TypeSpecifier(ref t) => Some(base_type(&t.node)),
+                    _ => None,
+                })
+                .fold(Base::Other, Base::or);
+
+            if field.node.declarators.is_empty() {
+                let anonymous = Type {
+                    base,
+                    derived: Vec::new(),
+                };
+                if let Some(inner) = self.struct_type(&anonymous) {
+                    if let Some((mut fields, ty)) = self.find_member(inner, name) {
+                        let member = Field {
+                            field,
+                            declarator: None,
+                            indices: Vec::new(),
+                            offset: None,
+                        };
+                        fields.insert(0, member);
+                        return Some((fields, ty));
+                    }
+                }
+                continue;
+            }
+
+            for declarator in &field.node.declarators {
+                let d = match declarator.node.declarator {
+                    Some(ref d) => d,
+                    None => continue,
+                };
+                if declarator_name(&d.node) == Some(name) {
+                    let member = Field {
+                        field,
+                        declarator: Some(declarator),
+                        indices: Vec::new(),
+                        offset: None,
+                    };
+                    let ty = Type {
+                        base,
+                        derived: derived_declarators(&d.node),
+                    };
+                    return Some((vec![member], ty));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// How the size of an array member is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArrayMember {
+    /// `[]` on the last member of a struct
+    Flexible,
+    /// `[0]`
+    ///
+    /// [GNU extension](https://gcc.gnu.org/onlinedocs/gcc/Zero-Length.html)
+    ZeroLength,
+    /// `[]` on a member that can not be flexible, not valid C
+    Incomplete,
+    /// Any other size, including sizes that can not be evaluated
+    Sized,
+}
+
+/// Classify the array members of a struct or union
+///
+/// Only members that are arrays themselves are reported, not pointers to arrays. A flexible
+/// array member has to be the last member of a struct, so any other member declared with `[]`,
+/// and every such union member, is incomplete.
+///
+/// ```
+/// use lang_c::ast::{DeclarationSpecifier, ExternalDeclaration, TypeSpecifier};
+/// use lang_c::driver::{parse_preprocessed, Config};
+/// use lang_c::fields::{array_members, ArrayMember};
+///
+/// let source = "struct s { int n; char (*p)[]; int zero[0]; int rest[][2]; };";
+/// let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+/// # let struct_type = match parse.unit.0[0].node {
+/// #     ExternalDeclaration::Declaration(ref d) => match d.node.specifiers[0].node {
+/// #         DeclarationSpecifier::TypeSpecifier(ref t) => match t.node {
+/// #             TypeSpecifier::Struct(ref s) => &s.node,
+/// #             _ => unreachable!(),
+/// #         },
+/// #         _ => unreachable!(),
+/// #     },
+/// #     _ => unreachable!(),
+/// # };
+///
+/// let kinds: Vec<_> = array_members(struct_type).iter().map(|&(_, kind)| kind).collect();
+/// assert_eq!(kinds, [ArrayMember::ZeroLength, ArrayMember::Flexible]);
+/// ```
+pub fn array_members(struct_type: &StructType) -> Vec<(&Node<StructDeclarator>, ArrayMember)> {
+    let declarators: Vec<_> = struct_type
+        .declarations
+        .iter()
+        .flatten()
+        .filter_map(|d| match d.node {
+            StructDeclaration::Field(ref f) => Some(f),
+            StructDeclaration::StaticAssert(_) => None,
+        })
+        .flat_map(|f| &f.node.declarators)
+        .collect();
+
+    let mut result = Vec::new();
+    for (i, declarator) in declarators.iter().enumerate() {
+        let d = match declarator.node.declarator {
+            Some(ref d) => d,
+            None => continue,
+        };
+        let size = match outermost_derivation(&d.node) {
+            Some(DerivedDeclarator::Array(a)) => &a.node.size,
+            _ => continue,
+        };
+        let last = i + 1 == declarators.len() && struct_type.kind.node == StructKind::Struct;
+        let kind = match *size {
+            ArraySize::Unknown if last => ArrayMember::Flexible,
+            ArraySize::Unknown => ArrayMember::Incomplete,
+            ArraySize::VariableExpression(ref e) => match eval(e, &|_| None) {
+                Ok(ref v) if !v.is_true() => ArrayMember::ZeroLength,
+                _ => ArrayMember::Sized,
+            },
+            _ => ArrayMember::Sized,
+        };
+        result.push((*declarator, kind));
+    }
+    result
+}
+
+impl<'ast> Visit<'ast> for FieldLookup<'ast> {
+    fn visit_declaration(&mut self, declaration: &'ast Declaration, span: &'ast Span) {
+        let is_typedef = declaration.specifiers.iter().any(|s| match s.node {
+            DeclarationSpecifier::StorageClass(ref s) => s.node == StorageClassSpecifier::Typedef,
+            _ => false,
+        });
+        if is_typedef {
+            let base = declaration
+                .specifiers
+                .iter()
+                .filter_map(|s| match s.node {
+                    DeclarationSpecifier::TypeSpecifier(ref t) => Some(base_type(&t.node)),
+                    _ => None,
+                })
+                .fold(Base::Other, Base::or);
+            for init in &declaration.declarators {
+                let d = &init.node.declarator.node;
+                if let Some(name) = declarator_name(d) {
+                    self.typedefs.entry(name).or_insert(Type {
+                        base,
+                        derived: derived_declarators(d),
+                    });
+                }
+            }
+        }
+        visit::visit_declaration(self, declaration, span);
+    }
+
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        if let (Some(_), Some(ref i)) = (&struct_type.declarations, &struct_type.identifier) {
+            self.structs
+                .entry((struct_type.kind.node.clone(), &i.node.name))
+                .or_insert(struct_type);
+        }
+        visit::visit_struct_type(self, struct_type, span);
+    }
+}
+
+impl<'ast> Base<'ast> {
+    fn or(self, other: Base<'ast>) -> Base<'ast> {
+        match self {
+            Base::Other => other,
+            _ => self,
+        }
+    }
+}
+
+fn base_type(t: &TypeSpecifier) -> Base<'_> {
+    match *t {
+        TypeSpecifier::Struct(ref s) => Base::Struct(&s.node),
+        TypeSpecifier::TypedefName(ref i) => Base::TypedefName(&i.node.name),
+        _ => Base::Other,
+    }
+}
+
+fn declarator_name(d: &Declarator) -> Option<&str> {
+    match d.kind.node {
+        DeclaratorKind::Abstract => None,
+        DeclaratorKind::Identifier(ref i) => Some(&i.node.name),
+        DeclaratorKind::Declarator(ref d) => declarator_name(&d.node),
+    }
+}
+
+fn derived_declarators(d: &Declarator) -> Vec<&DerivedDeclarator> {
+    let mut derived: Vec<_> = d.derived.iter().map(|d| &d.node).collect();
+    if let DeclaratorKind::Declarator(ref inner) = d.kind.node {
+        derived.extend(derived_declarators(&inner.node));
+    }
+    derived
+}
+
+/// Derivation applied last to the declared name, as the array in `int *a[4][2]`
+fn outermost_derivation(d: &Declarator) -> Option<&DerivedDeclarator> {
+    if let DeclaratorKind::Declarator(ref inner) = d.kind.node {
+        if let Some(derived) = outermost_derivation(&inner.node) {
+            return Some(derived);
+        }
+    }
+    // Array and function suffixes bind tighter than the pointers preceding the name
+    for derived in &d.derived {
+        if let DerivedDeclarator::Pointer(_) = derived.node {
+            continue;
+        }
+        return Some(&derived.node);
+    }
+    d.derived.last().map(|d| &d.node)
+}
+
+fn parse_component(component: &str) -> Option<(&str, Vec<usize>)> {
+    let (name, mut rest) = match component.find('[') {
+        Some(i) => (&component[..i], &component[i..]),
+        None => (component, ""),
+    };
+    if name.is_empty() {
+        return None;
+    }
+    let mut indices = Vec::new();
+    while !rest.is_empty() {
+        let end = match rest.find(']') {
+            Some(end) if rest.starts_with('[') => end,
+            _ => return None,
+        };
+        match rest[1..end].parse() {
+            Ok(index) => indices.push(index),
+            Err(_) => return None,
+        }
+        rest = &rest[end + 1..];
+    }
+    Some((name, indices))
+}
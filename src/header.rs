@@ -0,0 +1,563 @@
+//! Standalone headers for part of a translation unit
+//!
+//! `header` picks the declarations of a set of functions, variables, types and enumeration
+//! constants from a translation unit, adds the declarations of everything they refer to, and
+//! prints the result as a header that compiles on its own. This is useful for generating small
+//! headers for foreign function interfaces from large preprocessed ones.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::header::header;
+//!
+//! let config = Config::default();
+//! let source = r#"
+//!     typedef unsigned long size_t;
+//!     struct file;
+//!     enum mode { READ = 1, WRITE = 2 };
+//!     struct buffer { char *data; size_t len; struct file *owner; };
+//!     int unrelated(void);
+//!     int write(struct buffer *b, enum mode m) { return m; }
+//! "#;
+//! let parse = parse_preprocessed(&config, source.into()).unwrap();
+//!
+//! let h = header(&config, &parse.unit, &["write", "struct buffer", "puts"]);
+//! assert_eq!(
+//!     h.source,
+//!     "struct file;\n\
+//!      typedef unsigned long size_t;\n\
+//!      enum mode { READ = 1, WRITE = 2 };\n\
+//!      struct buffer {\n    char *data;\n    size_t len;\n    struct file *owner;\n};\n\
+//!      int write(struct buffer *b, enum mode m);\n"
+//! );
+//! assert_eq!(h.missing, ["puts"]);
+//! ```
+//!
+//! Symbols are names of ordinary identifiers, or tags written as `struct name`, `union name` or
+//! `enum name`. The first declaration of an identifier and the definition of a tag are used.
+//! Function definitions are replaced by their prototypes and variable definitions by `extern`
+//! declarations without initializers. Structs and unions that are only used through pointers,
+//! or in prototypes and `extern` declarations, are declared without their members. Declarations
+//! are kept in source order after these forward declarations; declarations inside linkage
+//! specifications are not searched.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::mem;
+
+use ast::*;
+use driver::Config;
+use span::{Node, Span};
+use typedeps::DependencyKind;
+use unparse::unparse;
+use visit::{self, Visit};
+
+/// Extracted declarations
+#[derive(Debug, Clone)]
+pub struct Header {
+    /// Forward declarations followed by the selected declarations
+    pub unit: TranslationUnit,
+    /// `unit` printed as C source
+    pub source: String,
+    /// Requested or referenced symbols that are not declared in the translation unit
+    pub missing: Vec<String>,
+}
+
+/// Extract the declarations of `symbols` and their dependencies from `unit`
+pub fn header(config: &Config, unit: &TranslationUnit, symbols: &[&str]) -> Header {
+    let index = Index::new(unit);
+    let mut words = HashMap::new();
+    let mut pending = Vec::new();
+    for s in symbols.iter().rev() {
+        let tag = TAG_WORDS
+            .iter()
+            .find(|w| s.starts_with(*w) && s[w.len()..].starts_with(' '));
+        let symbol = match tag {
+            Some(&word) => {
+                let name = s[word.len()..].trim().to_owned();
+                words.insert(name.clone(), word);
+                Symbol::Tag(name)
+            }
+            None => Symbol::Ordinary(s.trim().to_owned()),
+        };
+        pending.push((symbol, DependencyKind::Value));
+    }
+
+    let mut needs: HashMap<Symbol, DependencyKind> = HashMap::new();
+    let mut kept: BTreeMap<usize, Kept> = BTreeMap::new();
+    let mut missing = Vec::new();
+    while let Some((symbol, kind)) = pending.pop() {
+        match needs.get(&symbol) {
+            Some(&DependencyKind::Value) => continue,
+            Some(&k) if k == kind => continue,
+            _ => {}
+        }
+        needs.insert(symbol.clone(), kind);
+
+        let i = match symbol {
+            Symbol::Tag(ref name) => {
+                let enumeration = words.get(name) == Some(&"enum");
+                if kind == DependencyKind::Pointer && !enumeration {
+                    continue;
+                }
+                index.tags.get(name)
+            }
+            Symbol::Ordinary(ref name) => index.ordinary.get(name),
+        };
+        let i = match i {
+            Some(&i) => i,
+            None => {
+                missing.push(symbol.display(&words));
+                continue;
+            }
+        };
+        let item = kept.entry(i).or_insert_with(|| Kept {
+            names: BTreeSet::new(),
+            need: DependencyKind::Pointer,
+        });
+        if let Symbol::Ordinary(ref name) = symbol {
+            item.names.insert(name.clone());
+        }
+        if kind == DependencyKind::Value {
+            item.need = kind;
+        }
+
+        let declaration = prune(&unit.0[i].node, item);
+        let refs = references(&declaration, item.need);
+        words.extend(refs.words);
+        pending.extend(refs.refs);
+    }
+
+    let mut externals = Vec::new();
+    let mut forward: Vec<_> = needs
+        .iter()
+        .filter_map(|(symbol, &kind)| match *symbol {
+            Symbol::Tag(ref name) if kind == DependencyKind::Pointer => {
+                let kind = match words.get(name) {
+                    Some(&"union") => StructKind::Union,
+                    Some(&"struct") => StructKind::Struct,
+                    _ => return None,
+                };
+                Some((name.clone(), kind))
+            }
+            _ => None,
+        })
+        .collect();
+    forward.sort_by(|a, b| a.0.cmp(&b.0));
+    for (name, kind) in forward {
+        externals.push(node(ExternalDeclaration::Declaration(node(
+            forward_declaration(name, kind),
+        ))));
+    }
+    for (&i, item) in &kept {
+        let declaration = Node::new(prune(&unit.0[i].node, item), unit.0[i].span);
+        externals.push(Node::new(
+            ExternalDeclaration::Declaration(declaration),
+            unit.0[i].span,
+        ));
+    }
+
+    let unit = TranslationUnit(externals);
+    Header {
+        source: unparse(config, &unit),
+        unit,
+        missing,
+    }
+}
+
+const TAG_WORDS: &[&str] = &["struct", "union", "enum"];
+
+/// Name in the ordinary or the tag name space
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Symbol {
+    Ordinary(String),
+    Tag(String),
+}
+
+impl Symbol {
+    fn display(&self, words: &HashMap<String, &'static str>) -> String {
+        match *self {
+            Symbol::Ordinary(ref name) => name.clone(),
+            Symbol::Tag(ref name) => {
+                let word = words.get(name).cloned().unwrap_or("struct");
+                format!("{} {}", word, name)
+            }
+        }
+    }
+}
+
+/// Selected part of an external declaration
+struct Kept {
+    /// Declared identifiers to keep
+    names: BTreeSet<String>,
+    /// Whether types the declaration refers to must be complete
+    need: DependencyKind,
+}
+
+/// External declarations that declare identifiers and define tags
+struct Index {
+    ordinary: HashMap<String, usize>,
+    tags: HashMap<String, usize>,
+}
+
+impl Index {
+    fn new(unit: &TranslationUnit) -> Index {
+        let mut index = Index {
+            ordinary: HashMap::new(),
+            tags: HashMap::new(),
+        };
+        for (i, external) in unit.0.iter().enumerate() {
+            match external.node {
+                ExternalDeclaration::Declaration(ref d) => {
+                    for init in &d.node.declarators {
+                        if let Some(name) = declarator_name(&init.node.declarator.node) {
+                            index.ordinary.entry(name.to_owned()).or_insert(i);
+                        }
+                    }
+                    let mut definitions = Definitions {
+                        index: &mut index,
+                        item: i,
+                    };
+                    for s in &d.node.specifiers {
+                        definitions.visit_declaration_specifier(&s.node, &s.span);
+                    }
+                }
+                ExternalDeclaration::FunctionDefinition(ref f) => {
+                    if let Some(name) = declarator_name(&f.node.declarator.node) {
+                        index.ordinary.entry(name.to_owned()).or_insert(i);
+                    }
+                }
+                _ => {}
+            }
+        }
+        index
+    }
+}
+
+/// Tags and enumeration constants defined by declaration specifiers
+struct Definitions<'a> {
+    index: &'a mut Index,
+    item: usize,
+}
+
+impl<'a, 'ast> Visit<'ast> for Definitions<'a> {
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        if let (Some(i), &Some(_)) = (&struct_type.identifier, &struct_type.declarations) {
+            let name = i.node.name.clone();
+            self.index.tags.entry(name).or_insert(self.item);
+        }
+        visit::visit_struct_type(self, struct_type, span);
+    }
+
+    fn visit_enum_type(&mut self, enum_type: &'ast EnumType, _span: &'ast Span) {
+        if enum_type.enumerators.is_empty() {
+            return;
+        }
+        if let Some(ref i) = enum_type.identifier {
+            let name = i.node.name.clone();
+            self.index.tags.entry(name).or_insert(self.item);
+        }
+        for e in &enum_type.enumerators {
+            let name = e.node.identifier.node.name.clone();
+            self.index.ordinary.entry(name).or_insert(self.item);
+        }
+    }
+}
+
+/// Declaration with the kept declarators, or a prototype for a function definition
+fn prune(external: &ExternalDeclaration, kept: &Kept) -> Declaration {
+    match *external {
+        ExternalDeclaration::Declaration(ref d) => {
+            let d = &d.node;
+            let typedef = is_typedef(d);
+            let storage = d
+                .specifiers
+                .iter()
+                .any(|s| storage_class(&s.node).is_some());
+            let mut declarators: Vec<_> = d
+                .declarators
+                .iter()
+                .filter(|init| match declarator_name(&init.node.declarator.node) {
+                    Some(name) => kept.names.contains(name),
+                    None => false,
+                })
+                .map(|init| {
+                    let declarator = init.node.declarator.clone();
+                    Node::new(
+                        InitDeclarator {
+                            declarator,
+                            initializer: None,
+                        },
+                        init.span,
+                    )
+                })
+                .collect();
+            // A typedef without declarators is not valid
+            if typedef && declarators.is_empty() {
+                declarators = d.declarators.clone();
+            }
+            let mut specifiers = d.specifiers.clone();
+            let object = declarators
+                .iter()
+                .any(|init| !is_function(&init.node.declarator.node));
+            if !storage && object {
+                let s = node(StorageClassSpecifier::Extern);
+                specifiers.insert(0, node(DeclarationSpecifier::StorageClass(s)));
+            }
+            Declaration {
+                specifiers,
+                declarators,
+            }
+        }
+        ExternalDeclaration::FunctionDefinition(ref f) => {
+            let mut declarator = f.node.declarator.clone();
+            for d in &mut declarator.node.derived {
+                if let DerivedDeclarator::KRFunction(ref mut identifiers) = d.node {
+                    identifiers.clear();
+                }
+            }
+            let init = InitDeclarator {
+                declarator,
+                initializer: None,
+            };
+            Declaration {
+                specifiers: f.node.specifiers.clone(),
+                declarators: vec![Node::new(init, f.span)],
+            }
+        }
+        _ => unreachable!("only declarations and function definitions are indexed"),
+    }
+}
+
+fn forward_declaration(name: String, kind: StructKind) -> Declaration {
+    let struct_type = StructType {
+        kind: node(kind),
+        identifier: Some(node(Identifier { name })),
+        declarations: None,
+        extensions: Vec::new(),
+    };
+    let specifier = TypeSpecifier::Struct(node(struct_type));
+    Declaration {
+        specifiers: vec![node(DeclarationSpecifier::TypeSpecifier(node(specifier)))],
+        declarators: Vec::new(),
+    }
+}
+
+fn node<T>(value: T) -> Node<T> {
+    Node::new(value, Span::none())
+}
+
+/// Symbols a declaration refers to
+fn references(declaration: &Declaration, need: DependencyKind) -> References {
+    let typedef = is_typedef(declaration);
+    let mut refs = References {
+        refs: Vec::new(),
+        words: HashMap::new(),
+        kind: DependencyKind::Pointer,
+        defined: HashSet::new(),
+        local: HashSet::new(),
+    };
+    if declaration.declarators.is_empty() {
+        refs.kind = DependencyKind::Value;
+    }
+    for init in &declaration.declarators {
+        let d = &init.node.declarator.node;
+        let complete = if has_pointer(d) {
+            false
+        } else if typedef {
+            need == DependencyKind::Value
+        } else {
+            !is_function(d) && !d.derived.is_empty()
+        };
+        if complete {
+            refs.kind = DependencyKind::Value;
+        }
+    }
+    for s in &declaration.specifiers {
+        refs.visit_declaration_specifier(&s.node, &s.span);
+    }
+    refs.kind = DependencyKind::Pointer;
+    for init in &declaration.declarators {
+        refs.visit_init_declarator(&init.node, &init.span);
+    }
+
+    let defined = mem::replace(&mut refs.defined, HashSet::new());
+    let local = mem::replace(&mut refs.local, HashSet::new());
+    refs.refs.retain(|(symbol, _)| match *symbol {
+        Symbol::Tag(ref name) => !defined.contains(name),
+        Symbol::Ordinary(ref name) => !local.contains(name),
+    });
+    refs
+}
+
+struct References {
+    refs: Vec<(Symbol, DependencyKind)>,
+    words: HashMap<String, &'static str>,
+    /// Whether types used at this point must be complete
+    kind: DependencyKind,
+    /// Tags defined by the declaration itself
+    defined: HashSet<String>,
+    /// Parameters and enumeration constants declared by the declaration itself
+    local: HashSet<String>,
+}
+
+impl References {
+    fn with_kind<F: FnOnce(&mut Self)>(&mut self, kind: DependencyKind, f: F) {
+        let outer = self.kind;
+        self.kind = kind;
+        f(self);
+        self.kind = outer;
+    }
+}
+
+impl<'ast> Visit<'ast> for References {
+    fn visit_type_specifier(&mut self, type_specifier: &'ast TypeSpecifier, span: &'ast Span) {
+        if let TypeSpecifier::TypedefName(ref i) = *type_specifier {
+            let symbol = Symbol::Ordinary(i.node.name.clone());
+            self.refs.push((symbol, self.kind));
+        }
+        visit::visit_type_specifier(self, type_specifier, span);
+    }
+
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        if let Some(ref i) = struct_type.identifier {
+            let name = i.node.name.clone();
+            let word = match struct_type.kind.node {
+                StructKind::Struct => "struct",
+                StructKind::Union => "union",
+            };
+            self.words.insert(name.clone(), word);
+            if struct_type.declarations.is_some() {
+                self.defined.insert(name);
+            } else {
+                self.refs.push((Symbol::Tag(name), self.kind));
+            }
+        }
+        visit::visit_struct_type(self, struct_type, span);
+    }
+
+    fn visit_struct_field(&mut self, field: &'ast StructField, span: &'ast Span) {
+        if field.declarators.is_empty() {
+            return self.with_kind(DependencyKind::Value, |r| {
+                visit::visit_struct_field(r, field, span)
+            });
+        }
+        for declarator in &field.declarators {
+            let pointer = match declarator.node.declarator {
+                Some(ref d) => has_pointer(&d.node),
+                None => false,
+            };
+            let kind = if pointer {
+                DependencyKind::Pointer
+            } else {
+                DependencyKind::Value
+            };
+            self.with_kind(kind, |r| {
+                for s in &field.specifiers {
+                    r.visit_specifier_qualifier(&s.node, &s.span);
+                }
+                r.with_kind(DependencyKind::Pointer, |r| {
+                    r.visit_struct_declarator(&declarator.node, &declarator.span)
+                });
+            });
+        }
+    }
+
+    fn visit_enum_type(&mut self, enum_type: &'ast EnumType, span: &'ast Span) {
+        if let Some(ref i) = enum_type.identifier {
+            let name = i.node.name.clone();
+            self.words.insert(name.clone(), "enum");
+            if enum_type.enumerators.is_empty() {
+                self.refs.push((Symbol::Tag(name), DependencyKind::Value));
+            } else {
+                self.defined.insert(name);
+            }
+        }
+        for e in &enum_type.enumerators {
+            self.local.insert(e.node.identifier.node.name.clone());
+        }
+        visit::visit_enum_type(self, enum_type, span);
+    }
+
+    fn visit_parameter_declaration(
+        &mut self,
+        parameter_declaration: &'ast ParameterDeclaration,
+        span: &'ast Span,
+    ) {
+        if let Some(ref d) = parameter_declaration.declarator {
+            if let Some(name) = declarator_name(&d.node) {
+                self.local.insert(name.to_owned());
+            }
+        }
+        self.with_kind(DependencyKind::Pointer, |r| {
+            visit::visit_parameter_declaration(r, parameter_declaration, span)
+        });
+    }
+
+    fn visit_expression(&mut self, expression: &'ast Expression, span: &'ast Span) {
+        if let Expression::Identifier(ref i) = *expression {
+            let symbol = Symbol::Ordinary(i.node.name.clone());
+            self.refs.push((symbol, DependencyKind::Value));
+        }
+        self.with_kind(DependencyKind::Value, |r| {
+            visit::visit_expression(r, expression, span)
+        });
+    }
+}
+
+fn is_typedef(declaration: &Declaration) -> bool {
+    declaration
+        .specifiers
+        .iter()
+        .any(|s| storage_class(&s.node) == Some(&StorageClassSpecifier::Typedef))
+}
+
+fn storage_class(specifier: &DeclarationSpecifier) -> Option<&StorageClassSpecifier> {
+    match *specifier {
+        DeclarationSpecifier::StorageClass(ref s) => Some(&s.node),
+        _ => None,
+    }
+}
+
+fn has_pointer(d: &Declarator) -> bool {
+    for derived in &d.derived {
+        if let DerivedDeclarator::Pointer(_) = derived.node {
+            return true;
+        }
+    }
+    match d.kind.node {
+        DeclaratorKind::Declarator(ref d) => has_pointer(&d.node),
+        _ => false,
+    }
+}
+
+/// Check if a declarator declares a function rather than an object
+fn is_function(d: &Declarator) -> bool {
+    match first_derived(d) {
+        Some(&DerivedDeclarator::Function(_)) | Some(&DerivedDeclarator::KRFunction(_)) => true,
+        _ => false,
+    }
+}
+
+/// Derived declarator applied first to the declared identifier
+fn first_derived(d: &Declarator) -> Option<&DerivedDeclarator> {
+    if let DeclaratorKind::Declarator(ref inner) = d.kind.node {
+        if let Some(first) = first_derived(&inner.node) {
+            return Some(first);
+        }
+    }
+    // Array and function declarators bind tighter than pointers
+    for derived in &d.derived {
+        match derived.node {
+            DerivedDeclarator::Pointer(_) => {}
+            ref suffix => return Some(suffix),
+        }
+    }
+    d.derived.first().map(|d| &d.node)
+}
+
+fn declarator_name(d: &Declarator) -> Option<&str> {
+    match d.kind.node {
+        DeclaratorKind::Abstract => None,
+        DeclaratorKind::Identifier(ref i) => Some(&i.node.name),
+        DeclaratorKind::Declarator(ref d) => declarator_name(&d.node),
+    }
+}
@@ -0,0 +1,681 @@
+//! Source structure queries for editors and language servers
+
+use std::collections::HashMap;
+use std::mem;
+
+use ast::*;
+use driver::Parse;
+use eval::eval;
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// What a foldable region contains
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FoldKind {
+    /// Function body
+    Function,
+    /// Compound statement other than a function body
+    Block,
+    /// Struct or union member list
+    Struct,
+    /// Enumerator list
+    Enum,
+    /// Initializer list or compound literal
+    Initializer,
+}
+
+/// Region of source that an editor can collapse
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoldingRange {
+    pub kind: FoldKind,
+    pub span: Span,
+}
+
+/// Foldable regions of a parsed source, ordered by start position
+///
+/// Only regions that span more than one line are reported.
+///
+/// ```
+/// use lang_c::driver::{parse_preprocessed, Config};
+/// use lang_c::ide::{folding_ranges, FoldKind};
+///
+/// let source = "struct point {\n  int x, y;\n};\nint f(void) {\n  if (1) {\n    return 0;\n  }\n}\n";
+/// let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+/// let kinds: Vec<_> = folding_ranges(&parse).iter().map(|r| r.kind).collect();
+/// assert_eq!(kinds, [FoldKind::Struct, FoldKind::Function, FoldKind::Block]);
+/// ```
+pub fn folding_ranges(parse: &Parse) -> Vec<FoldingRange> {
+    let mut folding = Folding {
+        source: &parse.source,
+        body: None,
+        ranges: Vec::new(),
+    };
+    folding.visit_translation_unit(&parse.unit);
+    folding.ranges.sort_by_key(|r| (r.span.start, !r.span.end));
+    folding.ranges
+}
+
+struct Folding<'a> {
+    source: &'a str,
+    body: Option<usize>,
+    ranges: Vec<FoldingRange>,
+}
+
+impl<'a> Folding<'a> {
+    fn add(&mut self, kind: FoldKind, span: &Span) {
+        if self.source[span.start..span.end].contains('\n') {
+            self.ranges.push(FoldingRange { kind, span: *span });
+        }
+    }
+}
+
+impl<'a, 'ast> Visit<'ast> for Folding<'a> {
+    fn visit_function_definition(&mut self, f: &'ast FunctionDefinition, span: &'ast Span) {
+        self.add(FoldKind::Function, &f.statement.span);
+        self.body = Some(f.statement.span.start);
+        visit::visit_function_definition(self, f, span);
+    }
+
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        if let Statement::Compound(_) = *statement {
+            if self.body != Some(span.start) {
+                self.add(FoldKind::Block, span);
+            }
+        }
+        visit::visit_statement(self, statement, span);
+    }
+
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        if struct_type.declarations.is_some() {
+            self.add(FoldKind::Struct, span);
+        }
+        visit::visit_struct_type(self, struct_type, span);
+    }
+
+    fn visit_enum_type(&mut self, enum_type: &'ast EnumType, span: &'ast Span) {
+        if !enum_type.enumerators.is_empty() {
+            self.add(FoldKind::Enum, span);
+        }
+        visit::visit_enum_type(self, enum_type, span);
+    }
+
+    fn visit_initializer(&mut self, initializer: &'ast Initializer, span: &'ast Span) {
+        if let Initializer::List(_) = *initializer {
+            self.add(FoldKind::Initializer, span);
+        }
+        visit::visit_initializer(self, initializer, span);
+    }
+
+    fn visit_compound_literal(&mut self, literal: &'ast CompoundLiteral, span: &'ast Span) {
+        self.add(FoldKind::Initializer, span);
+        visit::visit_compound_literal(self, literal, span);
+    }
+}
+
+/// Role of an identifier for syntax highlighting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// Typedef name
+    Type,
+    /// Struct, union or enum tag
+    Tag,
+    /// Function name
+    Function,
+    /// Function parameter
+    Parameter,
+    /// Object that is not a parameter
+    Variable,
+    /// Struct or union member
+    Member,
+    /// Enumeration constant
+    Enumerator,
+    /// Goto label
+    Label,
+}
+
+/// Classified identifier
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticToken {
+    pub kind: TokenKind,
+    pub span: Span,
+    /// Identifier is being declared rather than referred to
+    pub declaration: bool,
+}
+
+/// Classify identifiers of a translation unit, ordered by position
+///
+/// Identifiers in expressions are resolved using the declarations visible at that point, taking
+/// block scopes into account. Identifiers without a visible declaration are not reported.
+///
+/// ```
+/// use lang_c::driver::{parse_preprocessed, Config};
+/// use lang_c::ide::{semantic_tokens, TokenKind};
+///
+/// let source = "typedef int T; enum { N }; T f(T x) { return x + N; }";
+/// let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+/// let kinds: Vec<_> = semantic_tokens(&parse.unit).iter().map(|t| t.kind).collect();
+/// assert_eq!(kinds, [
+///     TokenKind::Type, TokenKind::Enumerator, TokenKind::Type, TokenKind::Function,
+///     TokenKind::Type, TokenKind::Parameter, TokenKind::Parameter, TokenKind::Enumerator,
+/// ]);
+/// ```
+pub fn semantic_tokens(unit: &TranslationUnit) -> Vec<SemanticToken> {
+    let mut tokens = Tokens {
+        scopes: vec![HashMap::new()],
+        context: Context::Declaration(false),
+        tokens: Vec::new(),
+    };
+    tokens.visit_translation_unit(unit);
+    tokens.tokens.sort_by_key(|t| t.span.start);
+    tokens.tokens
+}
+
+/// What the declarators being visited declare
+#[derive(Clone, Copy)]
+enum Context {
+    /// Ordinary declaration, `true` for typedefs
+    Declaration(bool),
+    Parameter,
+    Member,
+}
+
+struct Tokens<'ast> {
+    scopes: Vec<HashMap<&'ast str, TokenKind>>,
+    context: Context,
+    tokens: Vec<SemanticToken>,
+}
+
+impl<'ast> Tokens<'ast> {
+    fn add(&mut self, kind: TokenKind, span: &Span, declaration: bool) {
+        self.tokens.push(SemanticToken {
+            kind,
+            span: *span,
+            declaration,
+        });
+    }
+
+    fn declare(&mut self, identifier: &'ast Node<Identifier>, kind: TokenKind) {
+        self.add(kind, &identifier.span, true);
+        if kind != TokenKind::Member {
+            let scope = self.scopes.last_mut().expect("at least one scope");
+            scope.insert(&identifier.node.name, kind);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<TokenKind> {
+        self.scopes
+            .iter()
+            .rev()
+            .filter_map(|s| s.get(name))
+            .next()
+            .cloned()
+    }
+
+    fn with_context<F: FnOnce(&mut Self)>(&mut self, context: Context, f: F) {
+        let saved = mem::replace(&mut self.context, context);
+        f(self);
+        self.context = saved;
+    }
+
+    fn scoped<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        self.scopes.push(HashMap::new());
+        f(self);
+        self.scopes.pop();
+    }
+}
+
+impl<'ast> Visit<'ast> for Tokens<'ast> {
+    fn visit_declaration(&mut self, declaration: &'ast Declaration, span: &'ast Span) {
+        let typedef = declaration.specifiers.iter().any(|s| match s.node {
+            DeclarationSpecifier::StorageClass(ref s) => s.node == StorageClassSpecifier::Typedef,
+            _ => false,
+        });
+        self.with_context(Context::Declaration(typedef), |t| {
+            visit::visit_declaration(t, declaration, span)
+        });
+    }
+
+    fn visit_function_definition(&mut self, f: &'ast FunctionDefinition, _span: &'ast Span) {
+        self.with_context(Context::Declaration(false), |t| {
+            for specifier in &f.specifiers {
+                t.visit_declaration_specifier(&specifier.node, &specifier.span);
+            }
+            t.visit_declarator(&f.declarator.node, &f.declarator.span);
+        });
+        self.scoped(|t| {
+            for parameter in function_parameters(&f.declarator.node) {
+                let scope = t.scopes.last_mut().expect("at least one scope");
+                scope.insert(&parameter.node.name, TokenKind::Parameter);
+            }
+            t.with_context(Context::Parameter, |t| {
+                for declaration in &f.declarations {
+                    for specifier in &declaration.node.specifiers {
+                        t.visit_declaration_specifier(&specifier.node, &specifier.span);
+                    }
+                    for init in &declaration.node.declarators {
+                        t.visit_init_declarator(&init.node, &init.span);
+                    }
+                }
+            });
+            t.visit_statement(&f.statement.node, &f.statement.span);
+        });
+    }
+
+    fn visit_parameter_declaration(&mut self, p: &'ast ParameterDeclaration, span: &'ast Span) {
+        self.with_context(Context::Parameter, |t| {
+            visit::visit_parameter_declaration(t, p, span)
+        });
+    }
+
+    fn visit_struct_declarator(&mut self, d: &'ast StructDeclarator, span: &'ast Span) {
+        self.with_context(Context::Member, |t| {
+            visit::visit_struct_declarator(t, d, span)
+        });
+    }
+
+    fn visit_function_declarator(&mut self, f: &'ast FunctionDeclarator, span: &'ast Span) {
+        self.scoped(|t| visit::visit_function_declarator(t, f, span));
+    }
+
+    fn visit_declarator(&mut self, declarator: &'ast Declarator, span: &'ast Span) {
+        if let DeclaratorKind::Identifier(ref identifier) = declarator.kind.node {
+            let mut function = false;
+            if let Some(d) = declarator.derived.last() {
+                if let DerivedDeclarator::Function(_) = d.node {
+                    function = true;
+                }
+                if let DerivedDeclarator::KRFunction(_) = d.node {
+                    function = true;
+                }
+            }
+            let kind = match self.context {
+                Context::Declaration(true) => TokenKind::Type,
+                Context::Member => TokenKind::Member,
+                _ if function => TokenKind::Function,
+                Context::Parameter => TokenKind::Parameter,
+                Context::Declaration(false) => TokenKind::Variable,
+            };
+            self.declare(identifier, kind);
+        }
+        visit::visit_declarator(self, declarator, span);
+    }
+
+    fn visit_derived_declarator(&mut self, derived: &'ast DerivedDeclarator, span: &'ast Span) {
+        match *derived {
+            DerivedDeclarator::KRFunction(ref identifiers) => {
+                for identifier in identifiers {
+                    self.add(TokenKind::Parameter, &identifier.span, true);
+                }
+            }
+            _ => visit::visit_derived_declarator(self, derived, span),
+        }
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &'ast Enumerator, _span: &'ast Span) {
+        self.declare(&enumerator.identifier, TokenKind::Enumerator);
+        if let Some(ref expression) = enumerator.expression {
+            self.visit_expression(&expression.node, &expression.span);
+        }
+    }
+
+    fn visit_type_specifier(&mut self, type_specifier: &'ast TypeSpecifier, span: &'ast Span) {
+        if let TypeSpecifier::TypedefName(ref identifier) = *type_specifier {
+            self.add(TokenKind::Type, &identifier.span, false);
+        }
+        visit::visit_type_specifier(self, type_specifier, span);
+    }
+
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        if let Some(ref identifier) = struct_type.identifier {
+            let declaration = struct_type.declarations.is_some();
+            self.add(TokenKind::Tag, &identifier.span, declaration);
+        }
+        self.with_context(Context::Member, |t| {
+            visit::visit_struct_type(t, struct_type, span)
+        });
+    }
+
+    fn visit_enum_type(&mut self, enum_type: &'ast EnumType, span: &'ast Span) {
+        if let Some(ref identifier) = enum_type.identifier {
+            let declaration = !enum_type.enumerators.is_empty();
+            self.add(TokenKind::Tag, &identifier.span, declaration);
+        }
+        visit::visit_enum_type(self, enum_type, span);
+    }
+
+    fn visit_expression(&mut self, expression: &'ast Expression, span: &'ast Span) {
+        match *expression {
+            Expression::Identifier(ref identifier) => {
+                if let Some(kind) = self.lookup(&identifier.node.name) {
+                    self.add(kind, &identifier.span, false);
+                }
+            }
+            Expression::LabelAddress(ref identifier) => {
+                self.add(TokenKind::Label, &identifier.span, false)
+            }
+            _ => {}
+        }
+        visit::visit_expression(self, expression, span);
+    }
+
+    fn visit_member_expression(&mut self, member: &'ast MemberExpression, _span: &'ast Span) {
+        self.add(TokenKind::Member, &member.identifier.span, false);
+        self.visit_expression(&member.expression.node, &member.expression.span);
+    }
+
+    fn visit_designator(&mut self, designator: &'ast Designator, span: &'ast Span) {
+        if let Designator::Member(ref identifier) = *designator {
+            self.add(TokenKind::Member, &identifier.span, false);
+        }
+        visit::visit_designator(self, designator, span);
+    }
+
+    fn visit_offset_member(&mut self, member: &'ast OffsetMember, span: &'ast Span) {
+        match *member {
+            OffsetMember::Member(ref identifier) | OffsetMember::IndirectMember(ref identifier) => {
+                self.add(TokenKind::Member, &identifier.span, false)
+            }
+            OffsetMember::Index(_) => {}
+        }
+        visit::visit_offset_member(self, member, span);
+    }
+
+    fn visit_label(&mut self, label: &'ast Label, span: &'ast Span) {
+        if let Label::Identifier(ref identifier) = *label {
+            self.add(TokenKind::Label, &identifier.span, true);
+        }
+        visit::visit_label(self, label, span);
+    }
+
+    fn visit_block_item(&mut self, item: &'ast BlockItem, span: &'ast Span) {
+        if let BlockItem::LocalLabels(ref labels) = *item {
+            for label in labels {
+                self.add(TokenKind::Label, &label.span, true);
+            }
+        }
+        visit::visit_block_item(self, item, span);
+    }
+
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        match *statement {
+            Statement::Goto(ref identifier) => self.add(TokenKind::Label, &identifier.span, false),
+            Statement::Compound(_) | Statement::For(_) => {
+                return self.scoped(|t| visit::visit_statement(t, statement, span));
+            }
+            _ => {}
+        }
+        visit::visit_statement(self, statement, span);
+    }
+}
+
+/// Parameter names of the function a declarator defines
+fn function_parameters(declarator: &Declarator) -> Vec<&Node<Identifier>> {
+    let mut parameters = Vec::new();
+    match declarator.kind.node {
+        DeclaratorKind::Declarator(ref d) => return function_parameters(&d.node),
+        DeclaratorKind::Identifier(_) => {}
+        DeclaratorKind::Abstract => return parameters,
+    }
+    match declarator.derived.last().map(|d| &d.node) {
+        Some(DerivedDeclarator::Function(f)) => {
+            for p in &f.node.parameters {
+                if let Some(ref d) = p.node.declarator {
+                    parameters.extend(declarator_identifier(&d.node));
+                }
+            }
+        }
+        Some(DerivedDeclarator::KRFunction(identifiers)) => parameters.extend(identifiers),
+        _ => {}
+    }
+    parameters
+}
+
+fn declarator_identifier(d: &Declarator) -> Option<&Node<Identifier>> {
+    match d.kind.node {
+        DeclaratorKind::Abstract => None,
+        DeclaratorKind::Identifier(ref i) => Some(i),
+        DeclaratorKind::Declarator(ref d) => declarator_identifier(&d.node),
+    }
+}
+
+/// Call surrounding a position and the signature of its callee
+#[derive(Debug, Clone)]
+pub struct SignatureHelp<'ast> {
+    /// Innermost call whose argument list contains the position
+    pub call: Node<&'ast CallExpression>,
+    /// Parameter list of the callee, `None` if it could not be resolved
+    pub signature: Option<&'ast Node<FunctionDeclarator>>,
+    /// Index of the argument being edited
+    ///
+    /// May be equal to the number of arguments when the position follows a trailing comma or
+    /// the argument list is empty.
+    pub active_argument: usize,
+    /// Locations of the callee's parameter declarations
+    pub parameters: Vec<Span>,
+    /// Array form of each of the callee's parameters, `None` for parameters of other types
+    pub arrays: Vec<Option<ArrayParameter<'ast>>>,
+}
+
+/// Find the call at a byte offset and resolve the callee's parameter list
+///
+/// Callees are resolved by name to the last function declarator or function pointer declarator
+/// with that name preceding the call, without regard to scope. Calls through other expressions
+/// are reported with no signature.
+///
+/// ```
+/// use lang_c::driver::{parse_preprocessed, Config};
+/// use lang_c::ide::signature_help;
+///
+/// let source = "int max(int a, int b); int f(void) { return max(1, 2); }";
+/// let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+///
+/// let offset = source.find("2)").unwrap();
+/// let help = signature_help(&parse, offset).unwrap();
+/// assert_eq!(help.active_argument, 1);
+/// let names: Vec<_> = help.parameters.iter().map(|s| &source[s.start..s.end]).collect();
+/// assert_eq!(names, ["int a", "int b"]);
+///
+/// assert!(signature_help(&parse, 0).is_none());
+/// ```
+pub fn signature_help(parse: &Parse, offset: usize) -> Option<SignatureHelp<'_>> {
+    let mut calls = Calls {
+        offset,
+        call: None,
+        functions: Vec::new(),
+    };
+    calls.visit_translation_unit(&parse.unit);
+    let call = calls.call?;
+
+    let mut active = 0;
+    for argument in &call.node.arguments {
+        let end = argument.span.end;
+        if end <= offset && parse.source[end..offset].trim_start().starts_with(',') {
+            active += 1;
+        }
+    }
+
+    let signature = match call.node.callee.node {
+        Expression::Identifier(ref i) => calls
+            .functions
+            .iter()
+            .rev()
+            .filter(|&&(name, _, span)| name == i.node.name && span.start < call.span.start)
+            .map(|&(_, f, _)| f)
+            .next(),
+        _ => None,
+    };
+
+    Some(SignatureHelp {
+        call,
+        signature,
+        active_argument: active,
+        parameters: signature.map_or(Vec::new(), |f| {
+            f.node.parameters.iter().map(|p| p.span).collect()
+        }),
+        arrays: signature.map_or(Vec::new(), |f| {
+            f.node
+                .parameters
+                .iter()
+                .map(|p| array_parameter(&p.node))
+                .collect()
+        }),
+    })
+}
+
+/// Parameter declared as an array, which is adjusted to a pointer to its element type
+///
+/// (C11 6.7.6.3)
+#[derive(Debug, Clone)]
+pub struct ArrayParameter<'ast> {
+    /// Qualifiers between the brackets, which apply to the adjusted pointer
+    pub qualifiers: &'ast [Node<TypeQualifier>],
+    /// Qualifiers among the declaration specifiers, which apply to the elements pointed to
+    pub element_qualifiers: Vec<&'ast Node<TypeQualifier>>,
+    /// `static`, the argument must point to the first of at least as many elements as the size
+    pub is_static: bool,
+    /// Size given with `static` if it is a constant expression
+    pub minimum_length: Option<u64>,
+    /// Array derivations other than the outermost one that have `static` or qualifiers
+    ///
+    /// These are constraint violations (C11 6.7.6.2), as in `int a[4][static 4]`.
+    pub misplaced: Vec<Span>,
+}
+
+/// Classify a parameter declared with an array type
+///
+/// Returns `None` if the parameter is not an array, including pointers to arrays.
+///
+/// ```
+/// use lang_c::driver::{parse_preprocessed, Config};
+/// use lang_c::ide::signature_help;
+///
+/// let source = "void fill(const char buf[restrict static 16], int n); \
+///               void f(char *p) { fill(p, 0); }";
+/// let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+///
+/// let help = signature_help(&parse, source.find("0)").unwrap()).unwrap();
+/// let buf = help.arrays[0].as_ref().unwrap();
+/// assert!(buf.is_static);
+/// assert_eq!(buf.minimum_length, Some(16));
+/// assert_eq!((buf.qualifiers.len(), buf.element_qualifiers.len()), (1, 1));
+/// assert!(help.arrays[1].is_none());
+/// ```
+pub fn array_parameter(parameter: &ParameterDeclaration) -> Option<ArrayParameter<'_>> {
+    let declarator = parameter.declarator.as_ref()?;
+    let derived = type_derivations(&declarator.node);
+    let (outermost, inner) = derived.split_first()?;
+    let array = match outermost.node {
+        DerivedDeclarator::Array(ref a) => &a.node,
+        _ => return None,
+    };
+
+    let (is_static, minimum_length) = match array.size {
+        ArraySize::StaticExpression(ref e) => match eval(e, &|_| None).map(|v| v.integer()) {
+            Ok(Some(i)) if i >= 0 => (true, Some(i as u64)),
+            _ => (true, None),
+        },
+        _ => (false, None),
+    };
+
+    let mut misplaced = Vec::new();
+    for derived in inner {
+        if let DerivedDeclarator::Array(ref a) = derived.node {
+            match a.node.size {
+                ArraySize::StaticExpression(_) => misplaced.push(a.span),
+                _ if !a.node.qualifiers.is_empty() => misplaced.push(a.span),
+                _ => {}
+            }
+        }
+    }
+
+    Some(ArrayParameter {
+        qualifiers: &array.qualifiers,
+        element_qualifiers: parameter
+            .specifiers
+            .iter()
+            .filter_map(|s| match s.node {
+                DeclarationSpecifier::TypeQualifier(ref q) => Some(q),
+                _ => None,
+            })
+            .collect(),
+        is_static,
+        minimum_length,
+        misplaced,
+    })
+}
+
+/// Derivations of a declarator from the outermost type derivation to the innermost
+fn type_derivations(declarator: &Declarator) -> Vec<&Node<DerivedDeclarator>> {
+    let mut derived = match declarator.kind.node {
+        DeclaratorKind::Declarator(ref inner) => type_derivations(&inner.node),
+        _ => Vec::new(),
+    };
+    // Array and function suffixes bind tighter than the pointers preceding the name
+    let mut pointers = Vec::new();
+    for d in &declarator.derived {
+        if let DerivedDeclarator::Pointer(_) = d.node {
+            pointers.push(d);
+        } else {
+            derived.push(d);
+        }
+    }
+    derived.extend(pointers.into_iter().rev());
+    derived
+}
+
+struct Calls<'ast> {
+    offset: usize,
+    call: Option<Node<&'ast CallExpression>>,
+    functions: Vec<(&'ast str, &'ast Node<FunctionDeclarator>, Span)>,
+}
+
+impl<'ast> Visit<'ast> for Calls<'ast> {
+    fn visit_call_expression(&mut self, call: &'ast CallExpression, span: &'ast Span) {
+        // Nested calls are visited later and replace the enclosing one
+        if call.callee.span.end < self.offset && self.offset < span.end {
+            self.call = Some(Node::new(call, *span));
+        }
+        visit::visit_call_expression(self, call, span);
+    }
+
+    fn visit_declarator(&mut self, declarator: &'ast Declarator, span: &'ast Span) {
+        let identifier = match declarator.kind.node {
+            DeclaratorKind::Identifier(ref i) => Some(i),
+            DeclaratorKind::Declarator(ref d) => pointer_identifier(&d.node),
+            DeclaratorKind::Abstract => None,
+        };
+        if let (Some(i), Some(f)) = (identifier, function_declarator(declarator)) {
+            self.functions.push((&i.node.name, f, *span));
+        }
+        visit::visit_declarator(self, declarator, span);
+    }
+}
+
+/// Identifier of a declarator that only declares a pointer, as in `(*fp)`
+fn pointer_identifier(declarator: &Declarator) -> Option<&Node<Identifier>> {
+    if declarator.derived.is_empty() {
+        return None;
+    }
+    for derived in &declarator.derived {
+        if let DerivedDeclarator::Pointer(_) = derived.node {
+            continue;
+        }
+        return None;
+    }
+    match declarator.kind.node {
+        DeclaratorKind::Identifier(ref i) => Some(i),
+        _ => None,
+    }
+}
+
+/// Outermost parameter list of a declarator, possibly behind pointers
+fn function_declarator(declarator: &Declarator) -> Option<&Node<FunctionDeclarator>> {
+    for derived in declarator.derived.iter().rev() {
+        match derived.node {
+            DerivedDeclarator::Function(ref f) => return Some(f),
+            DerivedDeclarator::Pointer(_) => continue,
+            _ => return None,
+        }
+    }
+    None
+}
@@ -0,0 +1,404 @@
+//! Byte images of initialized variables
+//!
+//! Lays out the type of a variable for a target data model and serializes its initializer into
+//! the bytes the variable occupies in memory, for example to compare a firmware image with the
+//! source or to decode a data section in a binary.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::image::image;
+//! use lang_c::layout::{Endianness, Target};
+//!
+//! let source = r#"
+//!     enum { RED = 1, GREEN };
+//!     struct pixel { unsigned char color; short x; };
+//!     struct pixel pixels[] = { { GREEN, -2 }, [2].x = 0x102 };
+//!     unsigned a = ~0u >> 1;
+//!     unsigned b = -1 / 2u;
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//!
+//! let big = image(&parse.unit, "pixels", &Target::ilp32(Endianness::Big)).unwrap();
+//! assert_eq!(big.bytes, [2, 0, 0xff, 0xfe, 0, 0, 0, 0, 0, 0, 1, 2]);
+//! assert_eq!(big.alignment, 2);
+//! for name in &["a", "b"] {
+//!     let big = image(&parse.unit, name, &Target::ilp32(Endianness::Big)).unwrap();
+//!     assert_eq!(big.bytes, [0x7f, 0xff, 0xff, 0xff]);
+//! }
+//!
+//! let little = image(&parse.unit, "pixels", &Target::lp64(Endianness::Little)).unwrap();
+//! assert_eq!(&little.bytes[8..], [0, 0, 2, 1]);
+//! ```
+//!
+//! Types are laid out as described in the `layout` module. Initializers are evaluated with the
+//! widths of `int` and `long` on the target, and pointers can only be initialized with integer
+//! constants, since addresses are assigned by the linker. Plain character constants and string
+//! literals are encoded in the execution character set of the target.
+
+use std::error;
+use std::fmt;
+
+use ast::*;
+use eval::{EvalError, Value};
+use initializers::{resolve, string_units, Initialized, InitializerError};
+use layout::{Endianness, LayoutError, Layouts, Target, Type};
+use span::{Node, Span};
+
+/// Memory contents of a variable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub bytes: Vec<u8>,
+    /// Alignment of the variable in bytes
+    pub alignment: usize,
+}
+
+/// Reason a variable could not be serialized
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageError {
+    /// No definition of the variable in the translation unit
+    UnknownVariable,
+    /// Type is incomplete, variably modified or not supported
+    UnsupportedType(Span),
+    /// Initializer needs the address of an object or function
+    Address(Span),
+    /// Initializer is not a constant expression
+    Eval(EvalError),
+    /// Initializer does not match the type of the object it initializes
+    InvalidInitializer(Span),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ImageError::UnknownVariable => write!(fmt, "variable is not defined"),
+            ImageError::UnsupportedType(_) => write!(fmt, "type has no known layout"),
+            ImageError::Address(_) => write!(fmt, "initializer needs an address"),
+            ImageError::Eval(ref e) => write!(fmt, "{}", e),
+            ImageError::InvalidInitializer(_) => write!(fmt, "invalid initializer"),
+        }
+    }
+}
+
+impl error::Error for ImageError {
+    fn description(&self) -> &str {
+        "image serialization error"
+    }
+}
+
+impl From<EvalError> for ImageError {
+    fn from(e: EvalError) -> ImageError {
+        ImageError::Eval(e)
+    }
+}
+
+impl From<LayoutError> for ImageError {
+    fn from(e: LayoutError) -> ImageError {
+        match e {
+            LayoutError::UnsupportedType(span) | LayoutError::InvalidSize(span) => {
+                ImageError::UnsupportedType(span)
+            }
+        }
+    }
+}
+
+impl From<InitializerError> for ImageError {
+    fn from(e: InitializerError) -> ImageError {
+        match e {
+            InitializerError::Layout(e) => ImageError::from(e),
+            InitializerError::Eval(e) => ImageError::Eval(e),
+            InitializerError::Invalid(span) => ImageError::InvalidInitializer(span),
+        }
+    }
+}
+
+/// Serialize the initializer of the variable `name` for `target`
+///
+/// The last definition of the variable with an initializer is used. A definition without an
+/// initializer gives an image of zero bytes, and the size of an array declared without one is
+/// taken from its initializer.
+///
+/// ```
+/// use lang_c::driver::{parse_preprocessed, Config};
+/// use lang_c::image::{image, ImageError};
+/// use lang_c::layout::{Endianness, Target};
+///
+/// let source = r#"
+///     struct flags { unsigned a : 3, b : 6; unsigned char c; } f = { 5, 33, 7 };
+///     struct split { unsigned char a : 5, b : 5; signed char n : 4; } s = { 1, 2, -1 };
+///     union u { unsigned char b[4]; unsigned i; } u1 = { .i = 0x01020304 }, u2 = { { 9 } };
+///     short a[] = { [3] = 3, 1, [1] = 7, [1] = 8 };
+///     struct p { char c; short x; } ps[2] = { [1].x = 1, [1].c = 2, [0] = { .x = 3 } };
+///     int x, *ptr = &x, many[2] = { 1, 2, 3 };
+///     long double ld = 1;
+/// "#;
+/// let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+/// let little = Target::ilp32(Endianness::Little);
+/// let big = Target::ilp32(Endianness::Big);
+/// let bytes = |name: &str, target: &Target| image(&parse.unit, name, target).unwrap().bytes;
+///
+/// // Bit-fields fill units from the least or the most significant bit
+/// assert_eq!(bytes("f", &little), [0x0d, 0x01, 7, 0]);
+/// assert_eq!(bytes("f", &big), [0xb0, 0x80, 7, 0]);
+/// assert_eq!(bytes("s", &little), [1, 2, 0x0f]);
+/// assert_eq!(bytes("s", &big), [0x08, 0x10, 0xf0]);
+///
+/// // A union is initialized through its first member unless a designator names another
+/// assert_eq!(bytes("u1", &little), [4, 3, 2, 1]);
+/// assert_eq!(bytes("u1", &big), [1, 2, 3, 4]);
+/// assert_eq!(bytes("u2", &big), [9, 0, 0, 0]);
+///
+/// // Designators move the current position, and later initializers override earlier ones
+/// assert_eq!(bytes("a", &little), [0, 0, 8, 0, 0, 0, 3, 0, 1, 0]);
+/// assert_eq!(bytes("ps", &big), [0, 0, 0, 3, 2, 0, 0, 1]);
+///
+/// let error = |name: &str| image(&parse.unit, name, &little).unwrap_err();
+/// assert_eq!(error("missing"), ImageError::UnknownVariable);
+/// match error("ptr") {
+///     ImageError::Address(_) => {}
+///     e => panic!("unexpected error {:?}", e),
+/// }
+/// match error("many") {
+///     ImageError::InvalidInitializer(_) => {}
+///     e => panic!("unexpected error {:?}", e),
+/// }
+/// match error("ld") {
+///     ImageError::UnsupportedType(_) => {}
+///     e => panic!("unexpected error {:?}", e),
+/// }
+/// ```
+pub fn image(unit: &TranslationUnit, name: &str, target: &Target) -> Result<Image, ImageError> {
+    let types = Layouts::new(unit, *target);
+
+    let mut definition = None;
+    for external in &unit.0 {
+        let declaration = match external.node {
+            ExternalDeclaration::Declaration(ref d) => &d.node,
+            _ => continue,
+        };
+        let storage = declaration.specifiers.iter().find_map(|s| match s.node {
+            DeclarationSpecifier::StorageClass(ref s) => Some(&s.node),
+            _ => None,
+        });
+        if storage == Some(&StorageClassSpecifier::Typedef) {
+            continue;
+        }
+        for init in &declaration.declarators {
+            let declarator = &init.node.declarator.node;
+            if declarator_name(declarator) != Some(name) {
+                continue;
+            }
+            let initialized = init.node.initializer.is_some();
+            let tentative = storage != Some(&StorageClassSpecifier::Extern);
+            if initialized || (tentative && definition.is_none()) {
+                definition = Some((declaration, declarator, &init.node.initializer));
+            }
+        }
+    }
+    let (declaration, declarator, initializer) = match definition {
+        Some(d) => d,
+        None => return Err(ImageError::UnknownVariable),
+    };
+
+    let specifiers = declaration.specifiers.iter().filter_map(|s| match s.node {
+        DeclarationSpecifier::TypeSpecifier(ref t) => Some(t),
+        _ => None,
+    });
+    let ty = types.declarator(types.base(specifiers), declarator);
+    let mut writer = Writer {
+        types: &types,
+        bytes: Vec::new(),
+        length: 0,
+    };
+    if let Some(ref init) = *initializer {
+        let initialization = try!(resolve(&types, &ty, init));
+        writer.length = initialization.length.unwrap_or(0);
+        for subobject in &initialization.subobjects {
+            try!(writer.expression(subobject));
+        }
+    }
+    let (size, alignment) = match (&ty, initializer) {
+        (&Type::Array(ref element, None), &Some(_)) => {
+            let element = try!(types.layout(element));
+            (element.size * writer.length, element.alignment)
+        }
+        _ => {
+            let layout = try!(types.layout(&ty));
+            (layout.size, layout.alignment)
+        }
+    };
+    writer.bytes.resize(size, 0);
+    Ok(Image {
+        bytes: writer.bytes,
+        alignment,
+    })
+}
+
+/// Initializer serialization into a growing buffer
+struct Writer<'t, 'ast: 't> {
+    types: &'t Layouts<'ast>,
+    bytes: Vec<u8>,
+    /// Number of elements initialized in an array of unknown size
+    length: usize,
+}
+
+impl<'t, 'ast> Writer<'t, 'ast> {
+    /// Store the value of the expression that initializes a subobject
+    fn expression(&mut self, object: &Initialized<'ast>) -> Result<(), ImageError> {
+        let e = object.expression;
+        let value = match object.ty {
+            Type::Array(ref element, length) => {
+                return match string_literal(e) {
+                    Some(s) => self.string(element, length, object.offset, s, e.span),
+                    None => Err(ImageError::InvalidInitializer(e.span)),
+                };
+            }
+            Type::Record(..) => return Err(ImageError::InvalidInitializer(e.span)),
+            Type::Unsupported(span) => return Err(ImageError::UnsupportedType(span)),
+            Type::Pointer => {
+                let mut e = e;
+                while let Expression::Cast(ref c) = e.node {
+                    e = &c.node.expression;
+                }
+                // Names of arrays and functions are unknown to the evaluator
+                match self.eval(e) {
+                    Err(ImageError::Eval(EvalError::UnknownIdentifier(_))) if is_address(e) => {
+                        return Err(ImageError::Address(e.span))
+                    }
+                    value => integer_bits(try!(value)),
+                }
+            }
+            Type::Bool => try!(self.eval(e)).is_true() as u64,
+            Type::Integer(_) => integer_bits(try!(self.eval(e))),
+            Type::Float(size) => {
+                let value = try!(self.eval(e)).float();
+                if size == 4 {
+                    (value as f32).to_bits() as u64
+                } else {
+                    value.to_bits()
+                }
+            }
+        };
+        match object.bits {
+            Some((bit, width)) => self.write_bits(object.offset * 8 + bit, width, value),
+            None => {
+                let size = try!(self.types.layout(&object.ty)).size;
+                self.write(object.offset, size, value);
+            }
+        }
+        Ok(())
+    }
+
+    fn eval(&self, e: &Node<Expression>) -> Result<Value, ImageError> {
+        self.types.eval(e).map_err(|err| match err {
+            EvalError::Unsupported(_) if is_address(e) => ImageError::Address(e.span),
+            err => ImageError::Eval(err),
+        })
+    }
+
+    /// Character array initialized by a string literal
+    fn string(
+        &mut self,
+        element: &Type<'ast>,
+        length: Option<usize>,
+        offset: usize,
+        s: &StringLiteral,
+        span: Span,
+    ) -> Result<(), ImageError> {
+        let (size, (unit, units)) = match (element, string_units(s, self.types.target().charset)) {
+            (&Type::Integer(size), Some(units)) => (size, units),
+            _ => return Err(ImageError::InvalidInitializer(span)),
+        };
+        if unit != size && unit != 0 {
+            return Err(ImageError::InvalidInitializer(span));
+        }
+        let length = match length {
+            Some(length) if units.len() > length => {
+                return Err(ImageError::InvalidInitializer(span))
+            }
+            Some(length) => length.min(units.len() + 1),
+            None => units.len() + 1,
+        };
+        for (index, &unit) in units.iter().chain(Some(&0)).take(length).enumerate() {
+            self.write(offset + index * size, size, unit);
+        }
+        Ok(())
+    }
+
+    /// Store the low `size` bytes of `value` in target byte order
+    fn write(&mut self, offset: usize, size: usize, value: u64) {
+        if self.bytes.len() < offset + size {
+            self.bytes.resize(offset + size, 0);
+        }
+        for index in 0..size {
+            let shift = 8 * index as u32;
+            let byte = value.checked_shr(shift).unwrap_or(0) as u8;
+            let position = match self.types.target().endianness {
+                Endianness::Little => offset + index,
+                Endianness::Big => offset + size - 1 - index,
+            };
+            self.bytes[position] = byte;
+        }
+    }
+
+    /// Store the low `width` bits of `value` starting at bit `bit` of the buffer
+    fn write_bits(&mut self, bit: usize, width: usize, value: u64) {
+        let end = (bit + width + 7) / 8;
+        if self.bytes.len() < end {
+            self.bytes.resize(end, 0);
+        }
+        for index in 0..width {
+            let position = bit + index;
+            let (value_bit, mask) = match self.types.target().endianness {
+                Endianness::Little => (index, 1 << (position % 8)),
+                Endianness::Big => (width - 1 - index, 0x80 >> (position % 8)),
+            };
+            if value.checked_shr(value_bit as u32).unwrap_or(0) & 1 != 0 {
+                self.bytes[position / 8] |= mask;
+            } else {
+                self.bytes[position / 8] &= !mask;
+            }
+        }
+    }
+}
+
+fn declarator_name(d: &Declarator) -> Option<&str> {
+    match d.kind.node {
+        DeclaratorKind::Abstract => None,
+        DeclaratorKind::Identifier(ref i) => Some(&i.node.name),
+        DeclaratorKind::Declarator(ref d) => declarator_name(&d.node),
+    }
+}
+
+fn string_literal(e: &Node<Expression>) -> Option<&StringLiteral> {
+    match e.node {
+        Expression::StringLiteral(ref s) => Some(&s.node),
+        _ => None,
+    }
+}
+
+/// Two's complement bits of an integer value, truncating floating point values
+fn integer_bits(value: Value) -> u64 {
+    match value.integer() {
+        Some(i) => i as u64,
+        None if value.float() < 0.0 => value.float() as i64 as u64,
+        None => value.float() as u64,
+    }
+}
+
+/// Whether an expression refers to the address of an object or function
+fn is_address(e: &Node<Expression>) -> bool {
+    match e.node {
+        Expression::StringLiteral(_)
+        | Expression::Identifier(_)
+        | Expression::CompoundLiteral(_) => true,
+        Expression::UnaryOperator(ref u) => u.node.operator.node == UnaryOperator::Address,
+        Expression::Cast(ref c) => is_address(&c.node.expression),
+        Expression::BinaryOperator(ref b) => match b.node.operator.node {
+            BinaryOperator::Plus | BinaryOperator::Minus => {
+                is_address(&b.node.lhs) || is_address(&b.node.rhs)
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
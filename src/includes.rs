@@ -0,0 +1,480 @@
+//! Include structure recovered from line markers
+//!
+//! Preprocessors annotate their output with line markers such as `# 1 "stdio.h" 1` recording
+//! where each part of the text came from. The flags following the file name mark entering an
+//! included file (`1`), returning to the including file (`2`) and system headers (`3`). This
+//! module rebuilds the include tree from those markers, and `line_markers` lists the markers
+//! themselves.
+//!
+//! ```
+//! use lang_c::includes::IncludeGraph;
+//!
+//! let source = concat!(
+//!     "# 1 \"main.c\"\n",
+//!     "# 1 \"util.h\" 1\n",
+//!     "# 1 \"/usr/include/stddef.h\" 1 3 4\n",
+//!     "typedef unsigned long size_t;\n",
+//!     "# 2 \"util.h\" 2\n",
+//!     "size_t len(const char *s);\n",
+//!     "# 2 \"main.c\" 2\n",
+//!     "int main(void) { return 0; }\n",
+//! );
+//! let graph = IncludeGraph::new(source);
+//!
+//! let names: Vec<_> = graph.files.iter().map(|f| (&*f.name, f.depth)).collect();
+//! assert_eq!(names, [("main.c", 0), ("util.h", 1), ("/usr/include/stddef.h", 2)]);
+//! assert_eq!(graph.files[2].parent, Some(1));
+//! assert!(graph.files[2].system);
+//!
+//! assert_eq!(graph.file_at(source.find("size_t len").unwrap()), Some(1));
+//! assert_eq!(graph.file_at(source.find("int main").unwrap()), Some(0));
+//! ```
+//!
+//! Markers without flags only adjust line numbers when they name a file that is currently being
+//! included. Otherwise they switch to a top-level file, which is how preprocessors introduce
+//! pseudo-files such as `<built-in>` and `<command-line>`. A header included several times has
+//! one entry per inclusion.
+
+use std::collections::HashMap;
+
+use ast::*;
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// Single inclusion of a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct File {
+    /// File name as written in the line marker
+    pub name: String,
+    /// Index of the including file, `None` for top-level files
+    pub parent: Option<usize>,
+    /// Number of includes between this file and a top-level file
+    pub depth: usize,
+    /// File is a system header, flagged by the preprocessor or by `add_system_dirs`
+    pub system: bool,
+    /// Line marker that entered the file
+    pub span: Span,
+}
+
+/// Path separators in file names of line markers
+const SEPARATORS: &[char] = &['/', '\\'];
+
+/// Whether text was written by the user or comes from a system header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Origin {
+    User,
+    System,
+}
+
+/// Files of a preprocessed source and the text each of them contributed
+#[derive(Debug, Clone, Default)]
+pub struct IncludeGraph {
+    /// Inclusions in the order they were entered
+    pub files: Vec<File>,
+    /// Start offsets of text regions and the file each belongs to, ordered by offset
+    regions: Vec<(usize, usize)>,
+}
+
+/// Line marker or `#line` directive
+///
+/// The flags are only written by GCC-compatible preprocessors, in line markers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineMarker {
+    /// Number of the line following the marker
+    pub line: usize,
+    /// File name, `None` for a `#line` directive that only sets the line number
+    pub file: Option<String>,
+    /// Start of an included file, flag `1`
+    pub enter: bool,
+    /// Return to a file after an include, flag `2`
+    pub leave: bool,
+    /// Text from a system header, flag `3`
+    pub system: bool,
+    /// Text to be treated as wrapped in `extern "C"`, flag `4`
+    pub extern_c: bool,
+    /// Written as `#line` rather than as a preprocessor line marker
+    pub directive: bool,
+}
+
+/// Line markers and `#line` directives of a preprocessed source, in source order
+///
+/// Each span covers the marker line without its line break.
+///
+/// ```
+/// use lang_c::includes::line_markers;
+///
+/// let source = "# 1 \"main.c\"\n# 1 \"/usr/include/errno.h\" 1 3 4\nint errno;\n#line 7\n";
+/// let markers = line_markers(source);
+/// assert_eq!(markers.len(), 3);
+/// assert_eq!(markers[1].node.file, Some("/usr/include/errno.h".into()));
+/// assert!(markers[1].node.enter && markers[1].node.system && markers[1].node.extern_c);
+/// assert_eq!((markers[2].node.line, markers[2].node.directive), (7, true));
+/// ```
+pub fn line_markers(source: &str) -> Vec<Node<LineMarker>> {
+    let mut markers = Vec::new();
+    let mut start = 0;
+    for line in source.split('\n') {
+        if let Some(marker) = parse_marker(line) {
+            markers.push(Node::new(marker, Span::span(start, start + line.len())));
+        }
+        start += line.len() + 1;
+    }
+    markers
+}
+
+impl IncludeGraph {
+    /// Scan a preprocessed source for line markers
+    pub fn new(source: &str) -> IncludeGraph {
+        let mut graph = IncludeGraph::default();
+        let mut stack: Vec<usize> = Vec::new();
+        let mut start = 0;
+
+        for line in source.split('\n') {
+            let end = start + line.len();
+            let next = (end + 1).min(source.len());
+            // Directives without a file name only renumber lines
+            let (name, enter, leave, system) = match parse_marker(line) {
+                Some(LineMarker {
+                    file: Some(name),
+                    enter,
+                    leave,
+                    system,
+                    ..
+                }) => (name, enter, leave, system),
+                _ => {
+                    start = next;
+                    continue;
+                }
+            };
+            let span = Span::span(start, end);
+
+            if enter {
+                let parent = stack.last().cloned();
+                let index = graph.add(name, parent, stack.len(), system, span);
+                stack.push(index);
+            } else {
+                let position = stack.iter().rposition(|&i| graph.files[i].name == name);
+                match position {
+                    Some(p) => stack.truncate(p + 1),
+                    None if leave => {
+                        stack.pop();
+                    }
+                    None => {
+                        let root = graph
+                            .files
+                            .iter()
+                            .position(|f| f.parent.is_none() && f.name == name);
+                        let index = match root {
+                            Some(index) => index,
+                            None => graph.add(name, None, 0, system, span),
+                        };
+                        stack.clear();
+                        stack.push(index);
+                    }
+                }
+            }
+
+            if let Some(&current) = stack.last() {
+                graph.regions.push((next, current));
+            }
+            start = next;
+        }
+
+        graph
+    }
+
+    /// Index of the file that contributed the text at an offset
+    ///
+    /// Returns `None` for text preceding the first line marker.
+    pub fn file_at(&self, offset: usize) -> Option<usize> {
+        let position = match self
+            .regions
+            .binary_search_by_key(&offset, |&(start, _)| start)
+        {
+            Ok(i) => i,
+            Err(0) => return None,
+            Err(i) => i - 1,
+        };
+        Some(self.regions[position].1)
+    }
+
+    /// Files included directly by a file
+    pub fn includes_of<'a>(&'a self, file: usize) -> impl Iterator<Item = usize> + 'a {
+        (0..self.files.len()).filter(move |&i| self.files[i].parent == Some(file))
+    }
+
+    /// Treat files under any of the given directories as system headers
+    ///
+    /// For preprocessors that do not flag system headers, or to treat third-party headers the
+    /// same way. A directory matches file names that start with it followed by a path separator.
+    pub fn add_system_dirs<S: AsRef<str>>(&mut self, dirs: &[S]) {
+        for file in &mut self.files {
+            let name = &file.name;
+            let under = |dir: &str| {
+                let dir = dir.trim_end_matches(SEPARATORS);
+                name.starts_with(dir) && name[dir.len()..].starts_with(SEPARATORS)
+            };
+            if dirs.iter().any(|d| under(d.as_ref())) {
+                file.system = true;
+            }
+        }
+    }
+
+    /// Whether the text at an offset comes from user code or a system header
+    ///
+    /// Text preceding the first line marker is user code.
+    pub fn origin(&self, offset: usize) -> Origin {
+        match self.file_at(offset) {
+            Some(file) if self.files[file].system => Origin::System,
+            _ => Origin::User,
+        }
+    }
+
+    /// External declarations of a translation unit that come from user code
+    ///
+    /// A declaration is classified by the file it starts in.
+    ///
+    /// ```
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    ///
+    /// let source = concat!(
+    ///     "# 1 \"main.c\"\n",
+    ///     "# 1 \"/usr/include/stdio.h\" 1 3 4\n",
+    ///     "int puts(const char *s);\n",
+    ///     "# 2 \"main.c\" 2\n",
+    ///     "# 1 \"/opt/sdk/include/sdk.h\" 1\n",
+    ///     "void sdk_init(void);\n",
+    ///     "# 3 \"main.c\" 2\n",
+    ///     "int main(void) { sdk_init(); return puts(\"hi\"); }\n",
+    /// );
+    /// let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+    /// let mut graph = parse.includes();
+    /// assert_eq!(graph.user_declarations(&parse.unit).len(), 2);
+    ///
+    /// graph.add_system_dirs(&["/opt/sdk/include"]);
+    /// assert_eq!(graph.user_declarations(&parse.unit).len(), 1);
+    /// ```
+    pub fn user_declarations<'ast>(
+        &self,
+        unit: &'ast TranslationUnit,
+    ) -> Vec<&'ast Node<ExternalDeclaration>> {
+        unit.0
+            .iter()
+            .filter(|e| self.origin(e.span.start) == Origin::User)
+            .collect()
+    }
+
+    /// Files included from a top-level file that no declaration was used from
+    ///
+    /// A header counts as used when an identifier, typedef name or tag declared in it or in a
+    /// header it includes is referred to or redeclared outside of it. Headers that only provide
+    /// macros are reported as unused, since macros do not survive preprocessing.
+    ///
+    /// ```
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    ///
+    /// let source = concat!(
+    ///     "# 1 \"main.c\"\n",
+    ///     "# 1 \"size.h\" 1\n",
+    ///     "typedef unsigned long size_t;\n",
+    ///     "# 2 \"main.c\" 2\n",
+    ///     "# 1 \"point.h\" 1\n",
+    ///     "struct point { int x, y; };\n",
+    ///     "# 3 \"main.c\" 2\n",
+    ///     "size_t count;\n",
+    /// );
+    /// let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+    /// let graph = parse.includes();
+    ///
+    /// let unused = graph.unused_includes(&parse.unit);
+    /// assert_eq!(unused.len(), 1);
+    /// assert_eq!(graph.files[unused[0]].name, "point.h");
+    /// ```
+    pub fn unused_includes(&self, unit: &TranslationUnit) -> Vec<usize> {
+        let mut usage = Usage {
+            declarations: HashMap::new(),
+            references: Vec::new(),
+        };
+        usage.visit_translation_unit(unit);
+
+        let mut used = vec![false; self.files.len()];
+        for &(ref key, offset) in &usage.references {
+            let declared = match usage.declarations.get(key) {
+                Some(declared) => declared,
+                None => continue,
+            };
+            let from = match self.file_at(offset) {
+                Some(from) => self.ancestors(from),
+                None => Vec::new(),
+            };
+            for &offset in declared {
+                if let Some(file) = self.file_at(offset) {
+                    for a in self.ancestors(file) {
+                        if !from.contains(&a) {
+                            used[a] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        (0..self.files.len())
+            .filter(|&i| match self.files[i].parent {
+                Some(p) => self.files[p].parent.is_none() && !self.files[p].name.starts_with('<'),
+                None => false,
+            })
+            .filter(|&i| !used[i])
+            .collect()
+    }
+
+    /// A file and the files that included it, innermost first
+    fn ancestors(&self, file: usize) -> Vec<usize> {
+        let mut result = vec![file];
+        while let Some(parent) = self.files[*result.last().unwrap()].parent {
+            result.push(parent);
+        }
+        result
+    }
+
+    fn add(
+        &mut self,
+        name: String,
+        parent: Option<usize>,
+        depth: usize,
+        system: bool,
+        span: Span,
+    ) -> usize {
+        self.files.push(File {
+            name,
+            parent,
+            depth,
+            system,
+            span,
+        });
+        self.files.len() - 1
+    }
+}
+
+/// Parse `# <line> "<file>" <flags>...` or `#line <line> ["<file>"]`
+fn parse_marker(line: &str) -> Option<LineMarker> {
+    let mut rest = line.trim_start();
+    if !rest.starts_with('#') {
+        return None;
+    }
+    rest = rest[1..].trim_start();
+    let directive = rest.starts_with("line");
+    if directive {
+        rest = rest[4..].trim_start();
+    }
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let number = rest[..digits].parse().ok()?;
+    rest = rest[digits..].trim_start();
+    if directive && rest.is_empty() {
+        return Some(LineMarker {
+            line: number,
+            file: None,
+            enter: false,
+            leave: false,
+            system: false,
+            extern_c: false,
+            directive: true,
+        });
+    }
+    if !rest.starts_with('"') {
+        return None;
+    }
+
+    let mut name = String::new();
+    let mut chars = rest[1..].char_indices();
+    let end = loop {
+        match chars.next()? {
+            (i, '"') => break i + 2,
+            (_, '\\') => name.push(chars.next()?.1),
+            (_, c) => name.push(c),
+        }
+    };
+
+    let flags: Vec<_> = rest[end..].split_whitespace().collect();
+    Some(LineMarker {
+        line: number,
+        file: Some(name),
+        enter: flags.contains(&"1"),
+        leave: flags.contains(&"2"),
+        system: flags.contains(&"3"),
+        extern_c: flags.contains(&"4"),
+        directive,
+    })
+}
+
+/// Identifiers share one namespace, struct, union and enum tags another
+#[derive(PartialEq, Eq, Hash)]
+enum Name<'ast> {
+    Ordinary(&'ast str),
+    Tag(&'ast str),
+}
+
+/// Declared names and references to them with their offsets
+struct Usage<'ast> {
+    declarations: HashMap<Name<'ast>, Vec<usize>>,
+    references: Vec<(Name<'ast>, usize)>,
+}
+
+impl<'ast> Usage<'ast> {
+    fn declare(&mut self, name: Name<'ast>, offset: usize) {
+        self.declarations.entry(name).or_default().push(offset);
+    }
+}
+
+impl<'ast> Visit<'ast> for Usage<'ast> {
+    fn visit_declarator(&mut self, declarator: &'ast Declarator, span: &'ast Span) {
+        if let DeclaratorKind::Identifier(ref i) = declarator.kind.node {
+            // A redeclaration, such as a definition matching a prototype, uses the header
+            self.declare(Name::Ordinary(&i.node.name), i.span.start);
+            self.references
+                .push((Name::Ordinary(&i.node.name), i.span.start));
+        }
+        visit::visit_declarator(self, declarator, span);
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &'ast Enumerator, span: &'ast Span) {
+        let i = &enumerator.identifier;
+        self.declare(Name::Ordinary(&i.node.name), i.span.start);
+        visit::visit_enumerator(self, enumerator, span);
+    }
+
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        if let Some(ref i) = struct_type.identifier {
+            self.declare(Name::Tag(&i.node.name), i.span.start);
+            self.references
+                .push((Name::Tag(&i.node.name), i.span.start));
+        }
+        visit::visit_struct_type(self, struct_type, span);
+    }
+
+    fn visit_enum_type(&mut self, enum_type: &'ast EnumType, span: &'ast Span) {
+        if let Some(ref i) = enum_type.identifier {
+            self.declare(Name::Tag(&i.node.name), i.span.start);
+            self.references
+                .push((Name::Tag(&i.node.name), i.span.start));
+        }
+        visit::visit_enum_type(self, enum_type, span);
+    }
+
+    fn visit_type_specifier(&mut self, type_specifier: &'ast TypeSpecifier, span: &'ast Span) {
+        if let TypeSpecifier::TypedefName(ref i) = *type_specifier {
+            self.references
+                .push((Name::Ordinary(&i.node.name), i.span.start));
+        }
+        visit::visit_type_specifier(self, type_specifier, span);
+    }
+
+    fn visit_expression(&mut self, expression: &'ast Expression, span: &'ast Span) {
+        if let Expression::Identifier(ref i) = *expression {
+            self.references
+                .push((Name::Ordinary(&i.node.name), i.span.start));
+        }
+        visit::visit_expression(self, expression, span);
+    }
+}
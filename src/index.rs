@@ -0,0 +1,483 @@
+//! Symbols shared between translation units
+//!
+//! Collects definitions, declarations and references of file scope identifiers, typedef names
+//! and tags from any number of translation units, so that a symbol can be followed from one
+//! file to another.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::index::{Role, SymbolIndex, SymbolKind};
+//!
+//! let lib = "int counter; int next(void) { return ++counter; }";
+//! let main = "int next(void); int main(void) { return next(); }";
+//!
+//! let mut index = SymbolIndex::new();
+//! for &(name, source) in &[("lib.c", lib), ("main.c", main)] {
+//!     let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//!     index.add(name, &parse.unit);
+//! }
+//!
+//! let next: Vec<_> = index
+//!     .occurrences("next")
+//!     .iter()
+//!     .map(|o| (&*index.units()[o.unit], o.role))
+//!     .collect();
+//! assert_eq!(next, [
+//!     ("lib.c", Role::Definition),
+//!     ("main.c", Role::Declaration),
+//!     ("main.c", Role::Reference),
+//! ]);
+//! assert_eq!(index.occurrences("counter")[0].kind, SymbolKind::Variable);
+//! ```
+//!
+//! Local variables, parameters and tags defined inside functions are not indexed. References
+//! are only recorded for names with a file scope declaration earlier in the same translation
+//! unit, except for tags which are recorded wherever they are used.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use ast::*;
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// What kind of entity a symbol names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    Function,
+    Variable,
+    Typedef,
+    /// Struct, union or enum tag
+    Tag,
+    Enumerator,
+}
+
+/// How an occurrence uses a symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Function with a body, object definition, typedef, tag with a member list or enumerator
+    Definition,
+    /// Function prototype or `extern` object declaration
+    Declaration,
+    Reference,
+}
+
+/// Single occurrence of a symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct Occurrence {
+    /// Index of the translation unit in `SymbolIndex::units`
+    pub unit: usize,
+    pub kind: SymbolKind,
+    pub role: Role,
+    /// Symbol has internal linkage and is private to its translation unit
+    pub internal: bool,
+    /// Location of the identifier within the translation unit
+    pub span: Span,
+}
+
+/// Symbols of a set of translation units
+#[derive(Debug, Clone, Default)]
+pub struct SymbolIndex {
+    units: Vec<String>,
+    symbols: BTreeMap<String, Vec<Occurrence>>,
+}
+
+impl SymbolIndex {
+    pub fn new() -> SymbolIndex {
+        SymbolIndex::default()
+    }
+
+    /// Add the symbols of a translation unit and return its index
+    pub fn add(&mut self, name: &str, unit: &TranslationUnit) -> usize {
+        let index = self.units.len();
+        self.units.push(name.to_string());
+
+        let mut collector = Collector {
+            unit: index,
+            occurrences: Vec::new(),
+            globals: HashMap::new(),
+            locals: Vec::new(),
+            declared: None,
+        };
+        collector.visit_translation_unit(unit);
+        for (name, occurrence) in collector.occurrences {
+            self.symbols
+                .entry(name.to_string())
+                .or_default()
+                .push(occurrence);
+        }
+        index
+    }
+
+    /// Names of the translation units in the order they were added
+    pub fn units(&self) -> &[String] {
+        &self.units
+    }
+
+    /// Names of all indexed symbols in sorted order
+    pub fn symbols<'a>(&'a self) -> impl Iterator<Item = &'a str> + 'a {
+        self.symbols.keys().map(|s| &**s)
+    }
+
+    /// Occurrences of a symbol, ordered by translation unit and position
+    ///
+    /// Tags share names with ordinary identifiers; use `kind` to tell them apart.
+    pub fn occurrences(&self, name: &str) -> &[Occurrence] {
+        match self.symbols.get(name) {
+            Some(occurrences) => occurrences,
+            None => &[],
+        }
+    }
+
+    /// Definitions of a symbol across all translation units
+    pub fn definitions<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a Occurrence> + 'a {
+        self.occurrences(name)
+            .iter()
+            .filter(|o| o.role == Role::Definition)
+    }
+
+    /// Find external symbols that translation units disagree about
+    ///
+    /// Symbols with internal linkage are private to their translation unit and never conflict.
+    /// Object definitions are not checked for duplicates, since a definition without an
+    /// initializer is only tentative. Parameter and object types are not compared.
+    ///
+    /// ```
+    /// use lang_c::driver::{parse_preprocessed, Config};
+    /// use lang_c::index::{ConflictKind, SymbolIndex};
+    ///
+    /// let mut index = SymbolIndex::new();
+    /// for &(name, source) in &[
+    ///     ("a.c", "int size; int run(void) { return 0; }"),
+    ///     ("b.c", "int size(void); int run(void) { return 1; }"),
+    ///     ("c.c", "static int run(void) { return 2; }"),
+    /// ] {
+    ///     let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+    ///     index.add(name, &parse.unit);
+    /// }
+    ///
+    /// let conflicts: Vec<_> = index.conflicts().iter().map(|c| (c.name, c.kind)).collect();
+    /// assert_eq!(conflicts, [
+    ///     ("run", ConflictKind::MultipleDefinitions),
+    ///     ("size", ConflictKind::FunctionAndObject),
+    /// ]);
+    /// ```
+    pub fn conflicts(&self) -> Vec<Conflict<'_>> {
+        let mut conflicts = Vec::new();
+        for (name, occurrences) in &self.symbols {
+            let external: Vec<_> = occurrences
+                .iter()
+                .filter(|o| !o.internal && o.role != Role::Reference)
+                .filter(|o| o.kind == SymbolKind::Function || o.kind == SymbolKind::Variable)
+                .collect();
+
+            let functions = external.iter().any(|o| o.kind == SymbolKind::Function);
+            let objects = external.iter().any(|o| o.kind == SymbolKind::Variable);
+            if functions && objects {
+                conflicts.push(Conflict {
+                    name,
+                    kind: ConflictKind::FunctionAndObject,
+                    occurrences: external.clone(),
+                });
+            }
+
+            let definitions: Vec<_> = external
+                .iter()
+                .cloned()
+                .filter(|o| o.kind == SymbolKind::Function && o.role == Role::Definition)
+                .collect();
+            let first = definitions.first().map(|o| o.unit);
+            if definitions.iter().any(|o| Some(o.unit) != first) {
+                conflicts.push(Conflict {
+                    name,
+                    kind: ConflictKind::MultipleDefinitions,
+                    occurrences: definitions,
+                });
+            }
+        }
+        conflicts
+    }
+}
+
+/// Way in which translation units disagree about an external symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConflictKind {
+    /// Declared as a function in one translation unit and as an object in another
+    FunctionAndObject,
+    /// Function with a body in more than one translation unit
+    MultipleDefinitions,
+}
+
+/// Inconsistent declarations of an external symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict<'a> {
+    pub name: &'a str,
+    pub kind: ConflictKind,
+    /// Declarations and definitions involved in the conflict
+    pub occurrences: Vec<&'a Occurrence>,
+}
+
+/// Storage class of the declaration being visited
+#[derive(Clone, Copy, Default)]
+struct Declared {
+    typedef: bool,
+    external: bool,
+    internal: bool,
+    initialized: bool,
+    body: bool,
+}
+
+struct Collector<'ast> {
+    unit: usize,
+    occurrences: Vec<(&'ast str, Occurrence)>,
+    /// File scope symbols declared so far
+    globals: HashMap<&'ast str, (SymbolKind, bool)>,
+    /// Names declared in enclosing block scopes
+    locals: Vec<HashSet<&'ast str>>,
+    /// Declaration whose declarators are being visited, `None` for members and type names
+    declared: Option<Declared>,
+}
+
+impl<'ast> Collector<'ast> {
+    fn add(&mut self, name: &'ast Node<Identifier>, kind: SymbolKind, role: Role, internal: bool) {
+        self.occurrences.push((
+            &name.node.name,
+            Occurrence {
+                unit: self.unit,
+                kind,
+                role,
+                internal,
+                span: name.span,
+            },
+        ));
+    }
+
+    fn declare(
+        &mut self,
+        name: &'ast Node<Identifier>,
+        kind: SymbolKind,
+        role: Role,
+        internal: bool,
+    ) {
+        match self.locals.last_mut() {
+            Some(scope) => {
+                scope.insert(&name.node.name);
+            }
+            None => {
+                self.globals.insert(&name.node.name, (kind, internal));
+                self.add(name, kind, role, internal);
+            }
+        }
+    }
+
+    fn refer(&mut self, name: &'ast Node<Identifier>) {
+        if self.locals.iter().any(|s| s.contains(&*name.node.name)) {
+            return;
+        }
+        if let Some(&(kind, internal)) = self.globals.get(&*name.node.name) {
+            self.add(name, kind, Role::Reference, internal);
+        }
+    }
+
+    fn scoped<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        self.locals.push(HashSet::new());
+        f(self);
+        self.locals.pop();
+    }
+
+    fn with_declared<F: FnOnce(&mut Self)>(&mut self, declared: Option<Declared>, f: F) {
+        let saved = self.declared;
+        self.declared = declared;
+        f(self);
+        self.declared = saved;
+    }
+}
+
+impl<'ast> Visit<'ast> for Collector<'ast> {
+    fn visit_declaration(&mut self, declaration: &'ast Declaration, _span: &'ast Span) {
+        let declared = storage(&declaration.specifiers);
+        for specifier in &declaration.specifiers {
+            self.visit_declaration_specifier(&specifier.node, &specifier.span);
+        }
+        for init in &declaration.declarators {
+            let declared = Declared {
+                initialized: init.node.initializer.is_some(),
+                ..declared
+            };
+            self.with_declared(Some(declared), |c| {
+                c.visit_init_declarator(&init.node, &init.span)
+            });
+        }
+    }
+
+    fn visit_function_definition(&mut self, f: &'ast FunctionDefinition, _span: &'ast Span) {
+        let declared = Declared {
+            body: true,
+            ..storage(&f.specifiers)
+        };
+        for specifier in &f.specifiers {
+            self.visit_declaration_specifier(&specifier.node, &specifier.span);
+        }
+        self.with_declared(Some(declared), |c| {
+            c.visit_declarator(&f.declarator.node, &f.declarator.span)
+        });
+        self.scoped(|c| {
+            for parameter in function_parameters(&f.declarator.node) {
+                c.locals.last_mut().unwrap().insert(&parameter.node.name);
+            }
+            for declaration in &f.declarations {
+                c.visit_declaration(&declaration.node, &declaration.span);
+            }
+            c.visit_statement(&f.statement.node, &f.statement.span);
+        });
+    }
+
+    fn visit_parameter_declaration(&mut self, p: &'ast ParameterDeclaration, span: &'ast Span) {
+        self.with_declared(Some(Declared::default()), |c| {
+            visit::visit_parameter_declaration(c, p, span)
+        });
+    }
+
+    fn visit_struct_declarator(&mut self, d: &'ast StructDeclarator, span: &'ast Span) {
+        self.with_declared(None, |c| visit::visit_struct_declarator(c, d, span));
+    }
+
+    fn visit_type_name(&mut self, type_name: &'ast TypeName, span: &'ast Span) {
+        self.with_declared(None, |c| visit::visit_type_name(c, type_name, span));
+    }
+
+    fn visit_function_declarator(&mut self, f: &'ast FunctionDeclarator, span: &'ast Span) {
+        self.scoped(|c| visit::visit_function_declarator(c, f, span));
+    }
+
+    fn visit_declarator(&mut self, declarator: &'ast Declarator, span: &'ast Span) {
+        if let (DeclaratorKind::Identifier(ref name), Some(d)) =
+            (&declarator.kind.node, self.declared)
+        {
+            let mut function = false;
+            if let Some(derived) = declarator.derived.last() {
+                if let DerivedDeclarator::Function(_) = derived.node {
+                    function = true;
+                }
+                if let DerivedDeclarator::KRFunction(_) = derived.node {
+                    function = true;
+                }
+            }
+            let (kind, role) = if d.typedef {
+                (SymbolKind::Typedef, Role::Definition)
+            } else if function && d.body {
+                (SymbolKind::Function, Role::Definition)
+            } else if function {
+                (SymbolKind::Function, Role::Declaration)
+            } else if d.external && !d.initialized {
+                (SymbolKind::Variable, Role::Declaration)
+            } else {
+                (SymbolKind::Variable, Role::Definition)
+            };
+            self.declare(name, kind, role, d.internal);
+        }
+        visit::visit_declarator(self, declarator, span);
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &'ast Enumerator, span: &'ast Span) {
+        self.declare(
+            &enumerator.identifier,
+            SymbolKind::Enumerator,
+            Role::Definition,
+            false,
+        );
+        visit::visit_enumerator(self, enumerator, span);
+    }
+
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        if let Some(ref name) = struct_type.identifier {
+            self.tag(name, struct_type.declarations.is_some());
+        }
+        visit::visit_struct_type(self, struct_type, span);
+    }
+
+    fn visit_enum_type(&mut self, enum_type: &'ast EnumType, span: &'ast Span) {
+        if let Some(ref name) = enum_type.identifier {
+            self.tag(name, !enum_type.enumerators.is_empty());
+        }
+        visit::visit_enum_type(self, enum_type, span);
+    }
+
+    fn visit_type_specifier(&mut self, type_specifier: &'ast TypeSpecifier, span: &'ast Span) {
+        if let TypeSpecifier::TypedefName(ref name) = *type_specifier {
+            self.refer(name);
+        }
+        visit::visit_type_specifier(self, type_specifier, span);
+    }
+
+    fn visit_expression(&mut self, expression: &'ast Expression, span: &'ast Span) {
+        if let Expression::Identifier(ref name) = *expression {
+            self.refer(name);
+        }
+        visit::visit_expression(self, expression, span);
+    }
+
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        match *statement {
+            Statement::Compound(_) | Statement::For(_) => {
+                self.scoped(|c| visit::visit_statement(c, statement, span))
+            }
+            _ => visit::visit_statement(self, statement, span),
+        }
+    }
+}
+
+impl<'ast> Collector<'ast> {
+    fn tag(&mut self, name: &'ast Node<Identifier>, defined: bool) {
+        let role = match (defined, self.locals.is_empty()) {
+            (true, true) => Role::Definition,
+            (true, false) => return,
+            (false, _) => Role::Reference,
+        };
+        self.add(name, SymbolKind::Tag, role, false);
+    }
+}
+
+fn storage(specifiers: &[Node<DeclarationSpecifier>]) -> Declared {
+    let mut declared = Declared::default();
+    for specifier in specifiers {
+        if let DeclarationSpecifier::StorageClass(ref s) = specifier.node {
+            match s.node {
+                StorageClassSpecifier::Typedef => declared.typedef = true,
+                StorageClassSpecifier::Extern => declared.external = true,
+                StorageClassSpecifier::Static => declared.internal = true,
+                _ => {}
+            }
+        }
+    }
+    declared
+}
+
+/// Parameter names of the function a declarator defines
+fn function_parameters(declarator: &Declarator) -> Vec<&Node<Identifier>> {
+    let mut parameters = Vec::new();
+    match declarator.kind.node {
+        DeclaratorKind::Declarator(ref d) => return function_parameters(&d.node),
+        DeclaratorKind::Identifier(_) => {}
+        DeclaratorKind::Abstract => return parameters,
+    }
+    match declarator.derived.last().map(|d| &d.node) {
+        Some(DerivedDeclarator::Function(f)) => {
+            for p in &f.node.parameters {
+                if let Some(ref d) = p.node.declarator {
+                    parameters.extend(declarator_identifier(&d.node));
+                }
+            }
+        }
+        Some(DerivedDeclarator::KRFunction(identifiers)) => parameters.extend(identifiers),
+        _ => {}
+    }
+    parameters
+}
+
+fn declarator_identifier(d: &Declarator) -> Option<&Node<Identifier>> {
+    match d.kind.node {
+        DeclaratorKind::Abstract => None,
+        DeclaratorKind::Identifier(ref i) => Some(i),
+        DeclaratorKind::Declarator(ref d) => declarator_identifier(&d.node),
+    }
+}
@@ -0,0 +1,513 @@
+//! Subobjects initialized by initializer lists
+//!
+//! Resolves each item of an initializer to the subobjects it initializes, following C11 6.7.9:
+//! items without a designation initialize the next subobject, designators move the current
+//! position, braces around nested aggregates may be left out, and a union is initialized
+//! through its first member unless a designator names another. A GNU range designator
+//! initializes each element of the range with the same expression.
+//!
+//! ```
+//! use lang_c::ast::{DeclarationSpecifier, ExternalDeclaration};
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::initializers::{resolve, Step};
+//! use lang_c::layout::{Endianness, Layouts, Target};
+//!
+//! let source = r#"
+//!     struct point { int x, y; };
+//!     struct shape { struct point at; int corners[3]; } s = { 1, 2, .corners[1] = 7, 8 };
+//!     short squares[] = { [2] = 4, 9, [0 ... 1] = 1 };
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//! let layouts = Layouts::new(&parse.unit, Target::lp64(Endianness::Little));
+//! let resolved = |i: usize| {
+//!     let declaration = match parse.unit.0[i].node {
+//!         ExternalDeclaration::Declaration(ref d) => &d.node,
+//!         _ => unreachable!(),
+//!     };
+//!     let specifiers = declaration.specifiers.iter().filter_map(|s| match s.node {
+//!         DeclarationSpecifier::TypeSpecifier(ref t) => Some(t),
+//!         _ => None,
+//!     });
+//!     let init = &declaration.declarators[0].node;
+//!     let ty = layouts.declarator(layouts.base(specifiers), &init.declarator.node);
+//!     resolve(&layouts, &ty, init.initializer.as_ref().unwrap()).unwrap()
+//! };
+//!
+//! let s = resolved(1);
+//! let offsets: Vec<_> = s.subobjects.iter().map(|s| s.offset).collect();
+//! assert_eq!(offsets, [0, 4, 12, 16]);
+//! assert_eq!(s.subobjects[1].path, [Step::Member(0), Step::Member(1)]);
+//! assert_eq!(s.subobjects[3].path, [Step::Member(1), Step::Index(2)]);
+//!
+//! let squares = resolved(2);
+//! let paths: Vec<_> = squares.subobjects.iter().map(|s| s.path[0]).collect();
+//! assert_eq!(
+//!     paths,
+//!     [Step::Index(2), Step::Index(3), Step::Index(0), Step::Index(1)]
+//! );
+//! assert_eq!(squares.length, Some(4));
+//! ```
+//!
+//! Subobjects are listed in initialization order; an item that initializes a subobject again
+//! overrides the earlier one. Whether an expression is valid for the type of its subobject is
+//! left to the caller, except that a string literal initializing a character array is kept as
+//! one subobject.
+
+use std::error;
+use std::fmt;
+
+use ast::*;
+use eval::{Charset, EvalError};
+use layout::{LayoutError, Layouts, Member, Type};
+use span::{Node, Span};
+
+/// Step from an aggregate to one of its subobjects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Array element
+    Index(usize),
+    /// Index in the members of a struct or union, as returned by `Layouts::record`
+    Member(usize),
+}
+
+/// Subobject initialized by an expression
+#[derive(Debug, Clone)]
+pub struct Initialized<'ast> {
+    /// Elements and members leading from the object to the subobject
+    pub path: Vec<Step>,
+    pub ty: Type<'ast>,
+    /// Offset from the start of the object in bytes
+    pub offset: usize,
+    /// Bit offset within the byte at `offset` and width of a bit-field
+    pub bits: Option<(usize, usize)>,
+    pub expression: &'ast Node<Expression>,
+}
+
+/// Subobjects initialized by an initializer
+#[derive(Debug, Clone)]
+pub struct Initialization<'ast> {
+    /// Initialized subobjects in initialization order
+    pub subobjects: Vec<Initialized<'ast>>,
+    /// Number of elements an array of unknown size gets from the initializer, `None` for other
+    /// types
+    pub length: Option<usize>,
+}
+
+/// Reason an initializer could not be resolved
+#[derive(Debug, Clone, PartialEq)]
+pub enum InitializerError {
+    /// Type of the object or of one of its subobjects has no layout
+    Layout(LayoutError),
+    /// Array index in a designator is not a constant expression
+    Eval(EvalError),
+    /// Initializer does not match the type of the object it initializes
+    Invalid(Span),
+}
+
+impl fmt::Display for InitializerError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InitializerError::Layout(ref e) => write!(fmt, "{}", e),
+            InitializerError::Eval(ref e) => write!(fmt, "{}", e),
+            InitializerError::Invalid(_) => write!(fmt, "invalid initializer"),
+        }
+    }
+}
+
+impl error::Error for InitializerError {
+    fn description(&self) -> &str {
+        "initializer resolution error"
+    }
+}
+
+impl From<LayoutError> for InitializerError {
+    fn from(e: LayoutError) -> InitializerError {
+        InitializerError::Layout(e)
+    }
+}
+
+impl From<EvalError> for InitializerError {
+    fn from(e: EvalError) -> InitializerError {
+        InitializerError::Eval(e)
+    }
+}
+
+/// Subobjects of an object of type `ty` that `initializer` initializes
+pub fn resolve<'ast>(
+    layouts: &Layouts<'ast>,
+    ty: &Type<'ast>,
+    initializer: &'ast Node<Initializer>,
+) -> Result<Initialization<'ast>, InitializerError> {
+    let mut resolver = Resolver {
+        layouts,
+        subobjects: Vec::new(),
+        length: None,
+    };
+    let object = Object {
+        path: Vec::new(),
+        ty: ty.clone(),
+        offset: 0,
+        bits: None,
+    };
+    try!(resolver.initializer(&object, initializer));
+    Ok(Initialization {
+        subobjects: resolver.subobjects,
+        length: resolver.length,
+    })
+}
+
+/// Object or subobject being initialized
+struct Object<'ast> {
+    path: Vec<Step>,
+    ty: Type<'ast>,
+    offset: usize,
+    bits: Option<(usize, usize)>,
+}
+
+/// Subobjects of an array, struct or union, in initialization order
+enum Aggregate<'ast> {
+    Array(Type<'ast>, usize, Option<usize>),
+    Record(Vec<Member<'ast>>, StructKind),
+}
+
+impl<'ast> Aggregate<'ast> {
+    fn count(&self) -> Option<usize> {
+        match *self {
+            Aggregate::Array(_, _, length) => length,
+            Aggregate::Record(ref members, StructKind::Union) => Some(members.len().min(1)),
+            Aggregate::Record(ref members, StructKind::Struct) => Some(members.len()),
+        }
+    }
+
+    fn child(&self, parent: &Object<'ast>, index: usize) -> Object<'ast> {
+        let mut path = parent.path.clone();
+        match *self {
+            Aggregate::Array(ref element, size, _) => {
+                path.push(Step::Index(index));
+                Object {
+                    path,
+                    ty: element.clone(),
+                    offset: parent.offset + index * size,
+                    bits: None,
+                }
+            }
+            Aggregate::Record(ref members, _) => {
+                path.push(Step::Member(index));
+                Object {
+                    path,
+                    ty: members[index].ty.clone(),
+                    offset: parent.offset + members[index].offset,
+                    bits: members[index].bits,
+                }
+            }
+        }
+    }
+}
+
+/// Subobjects of an array, struct or union type
+fn subobjects<'ast>(
+    layouts: &Layouts<'ast>,
+    ty: &Type<'ast>,
+) -> Result<Option<Aggregate<'ast>>, LayoutError> {
+    match *ty {
+        Type::Array(ref element, length) => {
+            let size = try!(layouts.layout(element)).size;
+            Ok(Some(Aggregate::Array((**element).clone(), size, length)))
+        }
+        Type::Record(s, _) => {
+            let record = try!(layouts.record(s));
+            Ok(Some(Aggregate::Record(record.members, record.kind)))
+        }
+        _ => Ok(None),
+    }
+}
+
+struct Resolver<'l, 'ast: 'l> {
+    layouts: &'l Layouts<'ast>,
+    subobjects: Vec<Initialized<'ast>>,
+    /// Number of elements initialized in an array of unknown size
+    length: Option<usize>,
+}
+
+impl<'l, 'ast> Resolver<'l, 'ast> {
+    fn initializer(
+        &mut self,
+        object: &Object<'ast>,
+        initializer: &'ast Node<Initializer>,
+    ) -> Result<(), InitializerError> {
+        match initializer.node {
+            Initializer::Expression(ref e) => {
+                self.expression(object, e);
+                Ok(())
+            }
+            Initializer::List(ref items) => self.list(object, items),
+        }
+    }
+
+    /// Braced initializer list for an object
+    fn list(
+        &mut self,
+        object: &Object<'ast>,
+        items: &'ast [Node<InitializerListItem>],
+    ) -> Result<(), InitializerError> {
+        let aggregate = match try!(subobjects(self.layouts, &object.ty)) {
+            Some(a) => a,
+            None => {
+                return match items.first() {
+                    Some(item) if !item.node.designation.is_empty() => {
+                        Err(InitializerError::Invalid(item.span))
+                    }
+                    Some(item) => self.initializer(object, &item.node.initializer),
+                    None => Ok(()),
+                }
+            }
+        };
+        if let [ref item] = *items {
+            if let Initializer::Expression(ref e) = item.node.initializer.node {
+                if item.node.designation.is_empty() && is_string_literal(e) {
+                    self.expression(object, e);
+                    return Ok(());
+                }
+            }
+        }
+        let (mut i, mut next, mut length) = (0, 0, 0);
+        while i < items.len() {
+            if !items[i].node.designation.is_empty() {
+                let designation = &items[i].node.designation;
+                next = try!(self.designation(&aggregate, object, &[], designation, items, &mut i));
+            } else {
+                if let Some(count) = aggregate.count() {
+                    if next >= count {
+                        return Err(InitializerError::Invalid(items[i].span));
+                    }
+                }
+                try!(self.element(&aggregate.child(object, next), items, &mut i));
+                next += 1;
+            }
+            length = length.max(next);
+        }
+        if let Aggregate::Array(_, _, None) = aggregate {
+            self.length = Some(length);
+        }
+        Ok(())
+    }
+
+    /// Subobject initialized by the item at `i`, and by the items after it if braces are elided
+    fn element(
+        &mut self,
+        object: &Object<'ast>,
+        items: &'ast [Node<InitializerListItem>],
+        i: &mut usize,
+    ) -> Result<(), InitializerError> {
+        let initializer = &items[*i].node.initializer;
+        if let Initializer::Expression(ref e) = initializer.node {
+            if !is_string_literal(e) {
+                if let Some(aggregate) = try!(subobjects(self.layouts, &object.ty)) {
+                    return self.elided(&aggregate, object, 0, items, i, true);
+                }
+            }
+        }
+        *i += 1;
+        self.initializer(object, initializer)
+    }
+
+    /// Subobjects from `start` on initialized by consecutive items without braces
+    ///
+    /// Stops at the end of the aggregate, of the items or at the next designated item, unless
+    /// it is the first item and so belongs to the aggregate.
+    fn elided(
+        &mut self,
+        aggregate: &Aggregate<'ast>,
+        object: &Object<'ast>,
+        start: usize,
+        items: &'ast [Node<InitializerListItem>],
+        i: &mut usize,
+        mut first: bool,
+    ) -> Result<(), InitializerError> {
+        for index in start..aggregate.count().unwrap_or(0) {
+            if *i >= items.len() || (!first && !items[*i].node.designation.is_empty()) {
+                break;
+            }
+            first = false;
+            try!(self.element(&aggregate.child(object, index), items, i));
+        }
+        Ok(())
+    }
+
+    /// Initialize the subobject named by a designation, returning the index of the next one
+    ///
+    /// `path` holds member indices left over from a member found in an anonymous struct or
+    /// union, which are followed before the remaining designators.
+    fn designation(
+        &mut self,
+        aggregate: &Aggregate<'ast>,
+        object: &Object<'ast>,
+        path: &[usize],
+        designators: &'ast [Node<Designator>],
+        items: &'ast [Node<InitializerListItem>],
+        i: &mut usize,
+    ) -> Result<usize, InitializerError> {
+        let (from, to, path, designators) = match (path.split_first(), designators.split_first()) {
+            (Some((&index, path)), _) => (index, index, path.to_vec(), designators),
+            (None, Some((designator, rest))) => {
+                let invalid = InitializerError::Invalid(designator.span);
+                let (from, to, path) = match (&designator.node, aggregate) {
+                    (Designator::Index(e), &Aggregate::Array(..)) => {
+                        let index = try!(self.index(e));
+                        (index, index, Vec::new())
+                    }
+                    (Designator::Range(r), &Aggregate::Array(..)) => {
+                        let from = try!(self.index(&r.node.from));
+                        let to = try!(self.index(&r.node.to));
+                        (from, to, Vec::new())
+                    }
+                    (Designator::Member(m), Aggregate::Record(members, _)) => {
+                        let mut path = match self.layouts.member_path(members, &m.node.name) {
+                            Some(p) => p,
+                            None => return Err(invalid),
+                        };
+                        let index = path.remove(0);
+                        (index, index, path)
+                    }
+                    _ => return Err(invalid),
+                };
+                let out_of_bounds = match (aggregate, aggregate.count()) {
+                    (&Aggregate::Array(..), Some(count)) => to >= count,
+                    _ => false,
+                };
+                if from > to || out_of_bounds {
+                    return Err(invalid);
+                }
+                (from, to, path, rest)
+            }
+            (None, None) => unreachable!(),
+        };
+
+        let start = *i;
+        for index in from..to + 1 {
+            *i = start;
+            let child = aggregate.child(object, index);
+            if path.is_empty() && designators.is_empty() {
+                try!(self.element(&child, items, i));
+                continue;
+            }
+            let inner = match try!(subobjects(self.layouts, &child.ty)) {
+                Some(inner) => inner,
+                None => return Err(InitializerError::Invalid(items[start].span)),
+            };
+            let next = try!(self.designation(&inner, &child, &path, designators, items, i));
+            try!(self.elided(&inner, &child, next, items, i, false));
+        }
+        Ok(to + 1)
+    }
+
+    fn expression(&mut self, object: &Object<'ast>, e: &'ast Node<Expression>) {
+        if let (&Type::Array(_, None), &Expression::StringLiteral(ref s)) = (&object.ty, &e.node) {
+            let charset = self.layouts.target().charset;
+            self.length = string_units(&s.node, charset).map(|(_, units)| units.len() + 1);
+        }
+        self.subobjects.push(Initialized {
+            path: object.path.clone(),
+            ty: object.ty.clone(),
+            offset: object.offset,
+            bits: object.bits,
+            expression: e,
+        });
+    }
+
+    /// Array index in a designator
+    fn index(&self, e: &Node<Expression>) -> Result<usize, InitializerError> {
+        match try!(self.layouts.eval(e)).integer() {
+            Some(i) if i >= 0 => Ok(i as usize),
+            _ => Err(InitializerError::Invalid(e.span)),
+        }
+    }
+}
+
+fn is_string_literal(e: &Node<Expression>) -> bool {
+    match e.node {
+        Expression::StringLiteral(_) => true,
+        _ => false,
+    }
+}
+
+/// Size of the code units of a string literal and their values, without the terminating null
+///
+/// The size is 0 for `L` strings, whose units take the size of the array elements. Strings
+/// without a prefix are encoded in `charset`.
+pub fn string_units(s: &StringLiteral, charset: Charset) -> Option<(usize, Vec<u64>)> {
+    let mut unit = 1;
+    let mut charset = charset;
+    for token in s {
+        let prefix = &token[..token.find('"')?];
+        unit = match prefix {
+            "" => continue,
+            "u8" => {
+                charset = Charset::Utf8;
+                continue;
+            }
+            "u" => 2,
+            "U" => 4,
+            "L" => 0,
+            _ => return None,
+        };
+    }
+    let mut units = Vec::new();
+    for token in s {
+        let body = &token[token.find('"')? + 1..token.len() - 1];
+        let mut chars = body.chars().peekable();
+        while let Some(c) = chars.next() {
+            let (value, raw) = if c == '\\' {
+                let (digits, radix, first) = match chars.next()? {
+                    'x' => (std::usize::MAX, 16, 0),
+                    'u' => (4, 16, 0),
+                    'U' => (8, 16, 0),
+                    d if d.is_digit(8) => (2, 8, d.to_digit(8)?),
+                    // Simple escape sequences stand for a character, without digits to read
+                    e => {
+                        let value = match e {
+                            'n' => '\n' as u32,
+                            't' => '\t' as u32,
+                            'r' => '\r' as u32,
+                            'a' => 7,
+                            'b' => 8,
+                            'f' => 12,
+                            'v' => 11,
+                            e => e as u32,
+                        };
+                        (0, 0, value)
+                    }
+                };
+                let mut value = first;
+                for _ in 0..digits {
+                    match chars.peek().and_then(|d| d.to_digit(radix)) {
+                        Some(d) => value = value.wrapping_mul(radix).wrapping_add(d),
+                        None => break,
+                    }
+                    chars.next();
+                }
+                // Numeric escapes give a code unit, universal character names a character
+                (value, radix == 8 || digits == std::usize::MAX)
+            } else {
+                (c as u32, false)
+            };
+            if raw {
+                units.push(value as u64);
+                continue;
+            }
+            let c = std::char::from_u32(value)?;
+            match unit {
+                1 => {
+                    let bytes = charset.encode(c)?;
+                    units.extend(bytes.into_iter().map(u64::from));
+                }
+                2 => {
+                    let mut buffer = [0; 2];
+                    units.extend(c.encode_utf16(&mut buffer).iter().map(|&u| u as u64));
+                }
+                _ => units.push(value as u64),
+            }
+        }
+    }
+    Some((unit, units))
+}
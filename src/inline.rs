@@ -0,0 +1,953 @@
+//! Function inlining
+//!
+//! `inline` replaces calls to small functions defined in the same translation unit with copies
+//! of their bodies, for example to flatten a header-only library into the code that uses it.
+//! The result is a syntax tree that can be printed with the `unparse` module.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::inline::{inline, DEFAULT_MAX_STATEMENTS};
+//! use lang_c::unparse::unparse;
+//!
+//! let config = Config::default();
+//! let source = r#"
+//!     static int square(int x) { return x * x; }
+//!     int f(int x) { int y = square(x + 1); return y; }
+//! "#;
+//! let parse = parse_preprocessed(&config, source.into()).unwrap();
+//!
+//! let inlining = inline(&parse.unit, DEFAULT_MAX_STATEMENTS);
+//! assert_eq!(inlining.calls, 1);
+//! assert_eq!(
+//!     unparse(&config, &inlining.unit),
+//!     "static int square(int x) {\n    return x * x;\n}\n\
+//!      int f(int x) {\n    int square_result_1;\n    {\n        int x_1 = x + 1;\n        \
+//!      {\n            int x = x_1;\n            square_result_1 = (int)(x * x);\n        \
+//!      }\n    }\n    int y = square_result_1;\n    return y;\n}\n"
+//! );
+//! ```
+//!
+//! A function is inlined if it is defined before the caller, is not variadic, is neither
+//! recursive nor has its address taken, and its body has at most the given number of statements
+//! and declarations. Its body may only return at the end and may not contain labels or `static`
+//! variables. Only calls that make up a whole statement are replaced: `f(...);`,
+//! `x = f(...);`, `return f(...);` and a declaration initialized with `f(...)`.
+//!
+//! The copy of the body is placed in a block that declares the parameters as variables
+//! initialized with the arguments, so the names of the body cannot clash with names at the call
+//! site. Arguments that refer to a parameter name and assignment targets that refer to a name
+//! declared by the body are passed through variables with fresh names. Calls are left alone
+//! where the caller declares a name that the body or the types of the parameters and the result
+//! take from file scope. A scalar return value is cast to the return type of the function,
+//! struct and union values are assigned as they are. Function definitions are kept unchanged.
+
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use ast::*;
+use expr::{var, Expr};
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// Largest number of statements and declarations of a body that `inline` copies by default
+pub const DEFAULT_MAX_STATEMENTS: usize = 8;
+
+/// Result of inlining
+#[derive(Debug, Clone)]
+pub struct Inlining {
+    /// Translation unit with calls replaced by function bodies
+    pub unit: TranslationUnit,
+    /// Number of calls replaced
+    pub calls: usize,
+}
+
+/// Replace calls to small functions with their bodies
+///
+/// `max_statements` limits the size of the inlined bodies, see the module documentation for the
+/// other conditions. Inlined bodies are copied from the original functions, calls within them
+/// are not inlined.
+///
+/// ```
+/// use lang_c::driver::{parse_preprocessed, Config};
+/// use lang_c::inline::{inline, DEFAULT_MAX_STATEMENTS};
+/// use lang_c::unparse::unparse;
+///
+/// let config = Config::default();
+/// let inlined = |source: &str| {
+///     let parse = parse_preprocessed(&config, source.into()).unwrap();
+///     let inlining = inline(&parse.unit, DEFAULT_MAX_STATEMENTS);
+///     (inlining.calls, unparse(&config, &inlining.unit))
+/// };
+///
+/// // The caller shadows a typedef name used by the parameter and the return type
+/// let source = "typedef int T; static T id(T x) { return x; } int f(void) { int T = 2; return id(T); }";
+/// assert_eq!(inlined(source).0, 0);
+///
+/// // Struct values are not cast
+/// let source = "struct s { int a; }; typedef struct s S;\n\
+///               static S make(int a) { S r; r.a = a; return r; }\n\
+///               int f(void) { S v; v = make(1); return v.a; }";
+/// let (calls, text) = inlined(source);
+/// assert_eq!(calls, 1);
+/// assert!(text.contains("        v = r;\n"));
+///
+/// // Scalar values are converted to the return type
+/// let source = "typedef char C; static C low(int x) { return x; } int f(void) { return low(300); }";
+/// assert!(inlined(source).1.contains("return (C)x;"));
+///
+/// // Qualifiers of the returned value are not kept on the result variable and the cast
+/// let source = "static const int one(void) { return 1; }\n\
+///               static const char *const name(void) { return \"x\"; }\n\
+///               int f(void) { int x = one(); const char *s = name(); return x + *s; }";
+/// let (calls, text) = inlined(source);
+/// assert_eq!(calls, 2);
+/// assert!(text.contains("    int one_result_1;\n"));
+/// assert!(text.contains("one_result_1 = (int)1;"));
+/// assert!(text.contains("    const char *name_result_1;\n"));
+/// assert!(text.contains("name_result_1 = (const char *)\"x\";"));
+///
+/// // Recursion, address taken, defined after the caller, early return, goto, nested call
+/// let source = "static int fact(int n) { return n ? n * fact(n - 1) : 1; }\n\
+///               static int addressed(void) { return 1; }\n\
+///               static int early(int x) { if (x) return 1; return 0; }\n\
+///               static int jumps(int x) { goto out; out: return x; }\n\
+///               static int one(void) { return 1; }\n\
+///               int f(int x) {\n\
+///                   int (*p)(void) = addressed;\n\
+///                   x = fact(x);\n\
+///                   x = early(x);\n\
+///                   x = jumps(x);\n\
+///                   x = later(x);\n\
+///                   return one() + 1;\n\
+///               }\n\
+///               int later(int x) { return x; }";
+/// assert_eq!(inlined(source).0, 0);
+/// ```
+pub fn inline(unit: &TranslationUnit, max_statements: usize) -> Inlining {
+    let mut uses = Uses::default();
+    uses.visit_translation_unit(unit);
+
+    let mut definitions = Vec::new();
+    functions(&unit.0, &mut definitions);
+    let typedefs = typedefs(&unit.0);
+    let mut candidates = HashMap::new();
+    for (index, f) in definitions.iter().enumerate() {
+        if let Some(c) = candidate(f, index, max_statements, &typedefs) {
+            let recursive = uses.reaches(c.name, c.name, &mut HashSet::new());
+            if !recursive && !uses.addressed.contains(c.name) {
+                candidates.insert(c.name, c);
+            }
+        }
+    }
+
+    let mut inliner = Inliner {
+        candidates,
+        names: uses.names.iter().map(|&n| n.to_owned()).collect(),
+        calls: 0,
+    };
+    let mut result = unit.clone();
+    inliner.externals(&mut result.0, &mut 0);
+    Inlining {
+        unit: result,
+        calls: inliner.calls,
+    }
+}
+
+/// Function definitions in source order
+fn functions<'ast>(
+    externals: &'ast [Node<ExternalDeclaration>],
+    definitions: &mut Vec<&'ast FunctionDefinition>,
+) {
+    for external in externals {
+        match external.node {
+            ExternalDeclaration::FunctionDefinition(ref f) => definitions.push(&f.node),
+            ExternalDeclaration::LinkageSpecification(ref l) => {
+                functions(&l.node.declarations, definitions)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// File scope typedef names, mapped to whether they name a scalar type
+fn typedefs(externals: &[Node<ExternalDeclaration>]) -> HashMap<&str, bool> {
+    let mut typedefs = HashMap::new();
+    for external in externals {
+        let declaration = match external.node {
+            ExternalDeclaration::Declaration(ref d) => &d.node,
+            _ => continue,
+        };
+        let typedef = declaration.specifiers.iter().any(|s| match s.node {
+            DeclarationSpecifier::StorageClass(ref c) => c.node == StorageClassSpecifier::Typedef,
+            _ => false,
+        });
+        if !typedef {
+            continue;
+        }
+        for d in &declaration.declarators {
+            let d = &d.node.declarator.node;
+            if let DeclaratorKind::Identifier(ref i) = d.kind.node {
+                let scalar = is_scalar(&declaration.specifiers, &d.derived, &typedefs);
+                typedefs.insert(&*i.node.name, scalar);
+            }
+        }
+    }
+    typedefs
+}
+
+/// Check if a type can be the target of a cast
+///
+/// Types that are not known to be scalar, such as typedef names declared elsewhere, are not.
+fn is_scalar(
+    specifiers: &[Node<DeclarationSpecifier>],
+    derived: &[Node<DerivedDeclarator>],
+    typedefs: &HashMap<&str, bool>,
+) -> bool {
+    if !derived.is_empty() {
+        return only_pointers(derived);
+    }
+    for s in specifiers {
+        if let DeclarationSpecifier::TypeSpecifier(ref t) = s.node {
+            match t.node {
+                TypeSpecifier::Struct(_) => return false,
+                TypeSpecifier::TypedefName(ref i) => {
+                    return typedefs.get(&*i.node.name) == Some(&true)
+                }
+                _ => {}
+            }
+        }
+    }
+    true
+}
+
+/// Function that can be inlined
+struct Candidate<'ast> {
+    name: &'ast str,
+    /// Position among the function definitions of the unit
+    index: usize,
+    parameters: Vec<&'ast ParameterDeclaration>,
+    /// Return type, `None` for `void`
+    return_type: Option<ReturnType>,
+    /// Body without the final `return`
+    body: &'ast [Node<BlockItem>],
+    /// Expression of the final `return`
+    result: Option<&'ast Node<Expression>>,
+    /// Names declared by the parameters and the body
+    declared: HashSet<&'ast str>,
+    /// Names the body and the types of the parameters and the result take from file scope
+    free: HashSet<&'ast str>,
+}
+
+/// Type specifiers and qualifiers and pointer derivations of a return type
+#[derive(Clone)]
+struct ReturnType {
+    specifiers: Vec<Node<DeclarationSpecifier>>,
+    pointers: Vec<Node<DerivedDeclarator>>,
+    /// Values can be cast to the type, which is not the case for structs and unions
+    scalar: bool,
+}
+
+impl ReturnType {
+    fn type_name(&self) -> TypeName {
+        let specifiers = self.specifiers.iter().filter_map(|s| match s.node {
+            DeclarationSpecifier::TypeSpecifier(ref t) => {
+                Some(SpecifierQualifier::TypeSpecifier(t.clone()))
+            }
+            DeclarationSpecifier::TypeQualifier(ref q) => {
+                Some(SpecifierQualifier::TypeQualifier(q.clone()))
+            }
+            _ => None,
+        });
+        let declarator = Declarator {
+            kind: node(DeclaratorKind::Abstract),
+            derived: self.pointers.clone(),
+            extensions: Vec::new(),
+        };
+        TypeName {
+            specifiers: specifiers.map(node).collect(),
+            declarator: if self.pointers.is_empty() {
+                None
+            } else {
+                Some(node(declarator))
+            },
+        }
+    }
+
+    /// Declaration of a variable of this type without initializer
+    fn declare(&self, name: &str) -> Node<BlockItem> {
+        declare(self.specifiers.clone(), name, self.pointers.clone(), None)
+    }
+}
+
+fn candidate<'ast>(
+    f: &'ast FunctionDefinition,
+    index: usize,
+    max: usize,
+    typedefs: &HashMap<&str, bool>,
+) -> Option<Candidate<'ast>> {
+    if !f.declarations.is_empty() {
+        return None;
+    }
+    let name = match f.declarator.node.kind.node {
+        DeclaratorKind::Identifier(ref i) => &*i.node.name,
+        _ => return None,
+    };
+    let (last, pointers) = f.declarator.node.derived.split_last()?;
+    let function = match last.node {
+        DerivedDeclarator::Function(ref f) if f.node.ellipsis == Ellipsis::None => &f.node,
+        _ => return None,
+    };
+    if !only_pointers(pointers) {
+        return None;
+    }
+
+    let mut parameters = Vec::new();
+    for p in &function.parameters {
+        match p.node.declarator {
+            Some(ref d) if only_pointers(&d.node.derived) => match d.node.kind.node {
+                DeclaratorKind::Identifier(_) => parameters.push(&p.node),
+                _ => return None,
+            },
+            None if is_void(&p.node.specifiers) && function.parameters.len() == 1 => {}
+            _ => return None,
+        }
+    }
+
+    let return_type = if is_void(&f.specifiers) && pointers.is_empty() {
+        None
+    } else {
+        // The result is assigned to a temporary, so the qualifiers of the returned value itself
+        // are dropped: those of the specifiers, or of the outermost pointer
+        let specifiers = f.specifiers.iter().filter_map(|s| match s.node {
+            DeclarationSpecifier::TypeSpecifier(_) => Some(s.clone()),
+            DeclarationSpecifier::TypeQualifier(_) if !pointers.is_empty() => Some(s.clone()),
+            _ => None,
+        });
+        let mut unqualified = pointers.to_vec();
+        if let Some(last) = unqualified.last_mut() {
+            if let DerivedDeclarator::Pointer(ref mut qualifiers) = last.node {
+                qualifiers.retain(|q| match q.node {
+                    PointerQualifier::TypeQualifier(_) => false,
+                    PointerQualifier::Extension(_) => true,
+                });
+            }
+        }
+        Some(ReturnType {
+            specifiers: specifiers.collect(),
+            pointers: unqualified,
+            scalar: is_scalar(&f.specifiers, pointers, typedefs),
+        })
+    };
+
+    let items = match f.statement.node {
+        Statement::Compound(ref items) => items,
+        _ => return None,
+    };
+    let (body, result) = match items.split_last() {
+        Some((last, rest)) => match last.node {
+            BlockItem::Statement(ref s) => match s.node {
+                Statement::Return(Some(ref e)) if return_type.is_some() => (rest, Some(&**e)),
+                Statement::Return(None) if return_type.is_none() => (rest, None),
+                _ if return_type.is_none() => (&items[..], None),
+                _ => return None,
+            },
+            _ if return_type.is_none() => (&items[..], None),
+            _ => return None,
+        },
+        None if return_type.is_none() => (&items[..], None),
+        None => return None,
+    };
+
+    let mut scan = Body::default();
+    let mut scope = HashSet::new();
+    for p in &parameters {
+        if let Some(ref d) = p.declarator {
+            if let DeclaratorKind::Identifier(ref i) = d.node.kind.node {
+                scope.insert(&*i.node.name);
+                scan.declared.insert(&*i.node.name);
+            }
+        }
+    }
+    scan.scopes.push(scope);
+    scan.visit_statement(&f.statement.node, &f.statement.span);
+    // The copied parameter declarations and the result variable use these types at the call site
+    let mut types = Names::default();
+    for s in f
+        .specifiers
+        .iter()
+        .chain(parameters.iter().flat_map(|p| &p.specifiers))
+    {
+        types.visit_declaration_specifier(&s.node, &s.span);
+    }
+    scan.free.extend(types.0);
+    let returns = if body.len() < items.len() { 1 } else { 0 };
+    if scan.unsupported || scan.returns != returns || scan.statements > max {
+        return None;
+    }
+
+    Some(Candidate {
+        name,
+        index,
+        parameters,
+        return_type,
+        body,
+        result,
+        declared: scan.declared,
+        free: scan.free,
+    })
+}
+
+fn only_pointers(derived: &[Node<DerivedDeclarator>]) -> bool {
+    for d in derived {
+        match d.node {
+            DerivedDeclarator::Pointer(_) => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+fn is_void(specifiers: &[Node<DeclarationSpecifier>]) -> bool {
+    specifiers.iter().any(|s| match s.node {
+        DeclarationSpecifier::TypeSpecifier(ref t) => t.node == TypeSpecifier::Void,
+        _ => false,
+    })
+}
+
+fn parameter_name(p: &ParameterDeclaration) -> &str {
+    match p.declarator {
+        Some(ref d) => match d.node.kind.node {
+            DeclaratorKind::Identifier(ref i) => &i.node.name,
+            _ => "",
+        },
+        None => "",
+    }
+}
+
+/// Where the value of an inlined call goes
+#[derive(PartialEq)]
+enum Target {
+    /// Expression statement, the value is discarded
+    Discard,
+    /// Assignment to an expression
+    Assign(Node<Expression>),
+    /// `return` statement of the caller
+    Return,
+}
+
+/// Function definition calls are inlined into
+struct Caller {
+    index: usize,
+    /// Names declared by the parameters and the body
+    declared: HashSet<String>,
+}
+
+struct Inliner<'ast> {
+    candidates: HashMap<&'ast str, Candidate<'ast>>,
+    /// Names used in the unit, including those introduced by inlining
+    names: HashSet<String>,
+    calls: usize,
+}
+
+impl<'ast> Inliner<'ast> {
+    fn externals(&mut self, externals: &mut [Node<ExternalDeclaration>], index: &mut usize) {
+        for external in externals {
+            match external.node {
+                ExternalDeclaration::FunctionDefinition(ref mut f) => {
+                    let mut declared = Declared::default();
+                    declared.visit_function_definition(&f.node, &f.span);
+                    let caller = Caller {
+                        index: *index,
+                        declared: declared.0.iter().map(|&n| n.to_owned()).collect(),
+                    };
+                    *index += 1;
+                    self.statement(&mut f.node.statement, &caller);
+                }
+                ExternalDeclaration::LinkageSpecification(ref mut l) => {
+                    self.externals(&mut l.node.declarations, index)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn statement(&mut self, statement: &mut Node<Statement>, caller: &Caller) {
+        let replacement = match statement.node {
+            Statement::Compound(ref mut items) => {
+                for mut item in mem::replace(items, Vec::new()) {
+                    match item.node {
+                        BlockItem::Declaration(ref d) => {
+                            if let Some(replacement) = self.declaration(&d.node, caller) {
+                                items.extend(replacement);
+                                continue;
+                            }
+                        }
+                        BlockItem::Statement(ref mut s) => self.statement(s, caller),
+                        BlockItem::StaticAssert(_)
+                        | BlockItem::LocalLabels(_)
+                        | BlockItem::FunctionDefinition(_) => {}
+                    }
+                    items.push(item);
+                }
+                None
+            }
+            Statement::Labeled(ref mut l) => {
+                self.statement(&mut l.node.statement, caller);
+                None
+            }
+            Statement::Attributed(ref mut a) => {
+                self.statement(&mut a.node.statement, caller);
+                None
+            }
+            Statement::If(ref mut i) => {
+                self.statement(&mut i.node.then_statement, caller);
+                if let Some(ref mut e) = i.node.else_statement {
+                    self.statement(e, caller);
+                }
+                None
+            }
+            Statement::Switch(ref mut s) => {
+                self.statement(&mut s.node.statement, caller);
+                None
+            }
+            Statement::While(ref mut w) => {
+                self.statement(&mut w.node.statement, caller);
+                None
+            }
+            Statement::DoWhile(ref mut d) => {
+                self.statement(&mut d.node.statement, caller);
+                None
+            }
+            Statement::For(ref mut f) => {
+                self.statement(&mut f.node.statement, caller);
+                None
+            }
+            Statement::Expression(Some(ref e)) => match e.node {
+                Expression::Call(ref c) => self.call(&c.node, Target::Discard, caller),
+                Expression::BinaryOperator(ref b)
+                    if b.node.operator.node == BinaryOperator::Assign =>
+                {
+                    match b.node.rhs.node {
+                        Expression::Call(ref c) => {
+                            let target = Target::Assign((*b.node.lhs).clone());
+                            self.call(&c.node, target, caller)
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            },
+            Statement::Return(Some(ref e)) => match e.node {
+                Expression::Call(ref c) => self.call(&c.node, Target::Return, caller),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(items) = replacement {
+            statement.node = Statement::Compound(items);
+        }
+    }
+
+    /// Items replacing a declaration of a single variable initialized with an inlined call
+    fn declaration(&mut self, d: &Declaration, caller: &Caller) -> Option<Vec<Node<BlockItem>>> {
+        let call = match d.declarators[..] {
+            [ref init] => match init.node.initializer {
+                Some(ref i) => match i.node {
+                    Initializer::Expression(ref e) => match e.node {
+                        Expression::Call(ref c) => c,
+                        _ => return None,
+                    },
+                    _ => return None,
+                },
+                None => return None,
+            },
+            _ => return None,
+        };
+        let return_type = self.candidate(&call.node, caller)?.return_type.clone()?;
+        let result = self.fresh(&format!("{}_result", callee_name(&call.node.callee)?));
+        let block = self.call(&call.node, Target::Assign(var(&result).into()), caller)?;
+
+        let mut declaration = d.clone();
+        declaration.declarators[0].node.initializer = Some(Node::new(
+            Initializer::Expression(Box::new(var(&result).into())),
+            Span::none(),
+        ));
+        Some(vec![
+            return_type.declare(&result),
+            node(BlockItem::Statement(node(Statement::Compound(block)))),
+            node(BlockItem::Declaration(node(declaration))),
+        ])
+    }
+
+    fn candidate(&self, call: &CallExpression, caller: &Caller) -> Option<&Candidate<'ast>> {
+        let candidate = self.candidates.get(callee_name(&call.callee)?)?;
+        let shadowed = caller.declared.contains(candidate.name)
+            || candidate.free.iter().any(|&n| caller.declared.contains(n));
+        if candidate.index >= caller.index
+            || candidate.parameters.len() != call.arguments.len()
+            || shadowed
+        {
+            return None;
+        }
+        Some(candidate)
+    }
+
+    /// Block items replacing a call statement
+    fn call(
+        &mut self,
+        call: &CallExpression,
+        target: Target,
+        caller: &Caller,
+    ) -> Option<Vec<Node<BlockItem>>> {
+        let (name, parameters, return_type, body, result, declared) = {
+            let c = self.candidate(call, caller)?;
+            (
+                c.name,
+                c.parameters.clone(),
+                c.return_type.clone(),
+                c.body,
+                c.result,
+                c.declared.clone(),
+            )
+        };
+        let return_type = match (return_type, &target) {
+            (Some(t), _) => Some(t),
+            (None, &Target::Discard) | (None, &Target::Return) => None,
+            (None, &Target::Assign(_)) => return None,
+        };
+
+        let discard = target == Target::Discard;
+        let mut outer = Vec::new();
+        let mut inner = Vec::new();
+
+        let parameter_names: HashSet<&str> = parameters.iter().map(|p| parameter_name(p)).collect();
+        let mut argument_names = Names::default();
+        for a in &call.arguments {
+            argument_names.visit_expression(&a.node, &a.span);
+        }
+        let through = argument_names.0.iter().any(|n| parameter_names.contains(n));
+        for (p, a) in parameters.iter().zip(&call.arguments) {
+            let name = parameter_name(p);
+            let derived = match p.declarator {
+                Some(ref d) => d.node.derived.clone(),
+                None => Vec::new(),
+            };
+            let value = if through {
+                let temporary = self.fresh(name);
+                outer.push(declare(
+                    p.specifiers.clone(),
+                    &temporary,
+                    derived.clone(),
+                    Some(a.clone()),
+                ));
+                var(&temporary).into()
+            } else {
+                a.clone()
+            };
+            inner.push(declare(p.specifiers.clone(), name, derived, Some(value)));
+        }
+        inner.extend(body.iter().cloned());
+
+        let value = match (result, return_type.clone()) {
+            (Some(e), None) => Some(Expr::from(e.clone())),
+            (Some(e), _) if discard => Some(Expr::from(e.clone())),
+            (Some(e), Some(ref t)) if t.scalar => Some(Expr::from(e.clone()).cast(t.type_name())),
+            (Some(e), Some(_)) => Some(Expr::from(e.clone())),
+            (None, _) => None,
+        };
+
+        let mut after = None;
+        match (target, value) {
+            (Target::Discard, Some(value)) => inner.push(expression_statement(value)),
+            (Target::Discard, None) => {}
+            (Target::Return, value) => inner.push(node(BlockItem::Statement(node(
+                Statement::Return(value.map(|v| Box::new(v.into()))),
+            )))),
+            (Target::Assign(lhs), Some(value)) => {
+                let mut lhs_names = Names::default();
+                lhs_names.visit_expression(&lhs.node, &lhs.span);
+                if lhs_names.0.iter().any(|n| declared.contains(n)) {
+                    let temporary = self.fresh(&format!("{}_result", name));
+                    outer.insert(0, return_type?.declare(&temporary));
+                    inner.push(expression_statement(var(&temporary).assign(value)));
+                    after = Some(expression_statement(
+                        Expr::from(lhs).assign(var(&temporary)),
+                    ));
+                } else {
+                    inner.push(expression_statement(Expr::from(lhs).assign(value)));
+                }
+            }
+            (Target::Assign(_), None) => return None,
+        }
+
+        self.calls += 1;
+        if outer.is_empty() && after.is_none() {
+            return Some(inner);
+        }
+        outer.push(node(BlockItem::Statement(node(Statement::Compound(inner)))));
+        outer.extend(after);
+        Some(outer)
+    }
+
+    /// Name that is not used anywhere in the unit
+    fn fresh(&mut self, base: &str) -> String {
+        let mut n = 1;
+        loop {
+            let name = format!("{}_{}", base, n);
+            if self.names.insert(name.clone()) {
+                return name;
+            }
+            n += 1;
+        }
+    }
+}
+
+fn callee_name(callee: &Node<Expression>) -> Option<&str> {
+    match callee.node {
+        Expression::Identifier(ref i) => Some(&i.node.name),
+        _ => None,
+    }
+}
+
+fn declare(
+    specifiers: Vec<Node<DeclarationSpecifier>>,
+    name: &str,
+    derived: Vec<Node<DerivedDeclarator>>,
+    initializer: Option<Node<Expression>>,
+) -> Node<BlockItem> {
+    let identifier = Identifier {
+        name: name.to_owned(),
+    };
+    let declarator = Declarator {
+        kind: node(DeclaratorKind::Identifier(node(identifier))),
+        derived,
+        extensions: Vec::new(),
+    };
+    let init = InitDeclarator {
+        declarator: node(declarator),
+        initializer: initializer.map(|e| node(Initializer::Expression(Box::new(e)))),
+    };
+    node(BlockItem::Declaration(node(Declaration {
+        specifiers,
+        declarators: vec![node(init)],
+    })))
+}
+
+fn expression_statement(e: Expr) -> Node<BlockItem> {
+    let statement = Statement::Expression(Some(Box::new(e.into())));
+    node(BlockItem::Statement(node(statement)))
+}
+
+fn node<T>(value: T) -> Node<T> {
+    Node::new(value, Span::none())
+}
+
+/// Identifiers, calls and function names used as values
+#[derive(Default)]
+struct Uses<'ast> {
+    names: HashSet<&'ast str>,
+    /// Names used other than as the callee of a call
+    addressed: HashSet<&'ast str>,
+    /// Functions called by each function definition
+    calls: HashMap<&'ast str, HashSet<&'ast str>>,
+    function: Option<&'ast str>,
+}
+
+impl<'ast> Uses<'ast> {
+    /// Check if `from` calls `to`, directly or through other functions
+    fn reaches(&self, from: &'ast str, to: &str, seen: &mut HashSet<&'ast str>) -> bool {
+        if !seen.insert(from) {
+            return false;
+        }
+        match self.calls.get(from) {
+            Some(callees) => callees
+                .iter()
+                .any(|&c| c == to || self.reaches(c, to, seen)),
+            None => false,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Uses<'ast> {
+    fn visit_identifier(&mut self, identifier: &'ast Identifier, _span: &'ast Span) {
+        self.names.insert(&identifier.name);
+    }
+
+    fn visit_function_definition(&mut self, f: &'ast FunctionDefinition, span: &'ast Span) {
+        let outer = self.function;
+        if let DeclaratorKind::Identifier(ref i) = f.declarator.node.kind.node {
+            self.function = Some(&i.node.name);
+        }
+        visit::visit_function_definition(self, f, span);
+        self.function = outer;
+    }
+
+    fn visit_call_expression(&mut self, call: &'ast CallExpression, span: &'ast Span) {
+        if let Expression::Identifier(ref i) = call.callee.node {
+            self.names.insert(&i.node.name);
+            if let Some(function) = self.function {
+                let callees = self.calls.entry(function).or_default();
+                callees.insert(&i.node.name);
+            }
+            for argument in &call.arguments {
+                self.visit_expression(&argument.node, &argument.span);
+            }
+        } else {
+            visit::visit_call_expression(self, call, span);
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &'ast Expression, span: &'ast Span) {
+        if let Expression::Identifier(ref i) = *expression {
+            self.addressed.insert(&i.node.name);
+        }
+        visit::visit_expression(self, expression, span);
+    }
+}
+
+/// Scan of a function body
+#[derive(Default)]
+struct Body<'ast> {
+    scopes: Vec<HashSet<&'ast str>>,
+    declared: HashSet<&'ast str>,
+    free: HashSet<&'ast str>,
+    statements: usize,
+    returns: usize,
+    /// Body contains a construct that can not be copied to another function
+    unsupported: bool,
+    /// Visiting struct members, which are not variables
+    member: bool,
+}
+
+impl<'ast> Body<'ast> {
+    fn declare(&mut self, name: &'ast str) {
+        self.scopes.last_mut().expect("scope").insert(name);
+        self.declared.insert(name);
+    }
+
+    fn reference(&mut self, name: &'ast str) {
+        if name == "__func__" || name == "__FUNCTION__" || name == "__PRETTY_FUNCTION__" {
+            self.unsupported = true;
+        }
+        if !self.scopes.iter().any(|s| s.contains(name)) {
+            self.free.insert(name);
+        }
+    }
+
+    fn scoped<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        self.scopes.push(HashSet::new());
+        f(self);
+        self.scopes.pop();
+    }
+}
+
+impl<'ast> Visit<'ast> for Body<'ast> {
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        match *statement {
+            Statement::Compound(_) | Statement::For(_) => {
+                return self.scoped(|b| visit::visit_statement(b, statement, span));
+            }
+            Statement::Return(_) => self.returns += 1,
+            Statement::Goto(_) | Statement::GotoIndirect(_) => self.unsupported = true,
+            _ => {}
+        }
+        self.statements += 1;
+        visit::visit_statement(self, statement, span);
+    }
+
+    fn visit_label(&mut self, label: &'ast Label, span: &'ast Span) {
+        if let Label::Identifier(_) = *label {
+            self.unsupported = true;
+        }
+        visit::visit_label(self, label, span);
+    }
+
+    fn visit_declaration(&mut self, declaration: &'ast Declaration, span: &'ast Span) {
+        self.statements += 1;
+        visit::visit_declaration(self, declaration, span);
+    }
+
+    fn visit_storage_class_specifier(&mut self, s: &'ast StorageClassSpecifier, _: &'ast Span) {
+        if *s == StorageClassSpecifier::Static || *s == StorageClassSpecifier::ThreadLocal {
+            self.unsupported = true;
+        }
+    }
+
+    fn visit_declarator(&mut self, declarator: &'ast Declarator, span: &'ast Span) {
+        if let DeclaratorKind::Identifier(ref i) = declarator.kind.node {
+            if !self.member {
+                self.declare(&i.node.name);
+            }
+        }
+        visit::visit_declarator(self, declarator, span);
+    }
+
+    fn visit_function_declarator(&mut self, f: &'ast FunctionDeclarator, span: &'ast Span) {
+        self.scoped(|b| visit::visit_function_declarator(b, f, span));
+    }
+
+    fn visit_struct_declarator(&mut self, d: &'ast StructDeclarator, span: &'ast Span) {
+        self.member = true;
+        visit::visit_struct_declarator(self, d, span);
+        self.member = false;
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &'ast Enumerator, span: &'ast Span) {
+        self.declare(&enumerator.identifier.node.name);
+        visit::visit_enumerator(self, enumerator, span);
+    }
+
+    fn visit_type_specifier(&mut self, type_specifier: &'ast TypeSpecifier, span: &'ast Span) {
+        if let TypeSpecifier::TypedefName(ref i) = *type_specifier {
+            self.reference(&i.node.name);
+        }
+        visit::visit_type_specifier(self, type_specifier, span);
+    }
+
+    fn visit_expression(&mut self, expression: &'ast Expression, span: &'ast Span) {
+        match *expression {
+            Expression::Identifier(ref i) => self.reference(&i.node.name),
+            Expression::LabelAddress(_) => self.unsupported = true,
+            _ => {}
+        }
+        visit::visit_expression(self, expression, span);
+    }
+}
+
+/// Names declared anywhere in a function, including its parameters
+#[derive(Default)]
+struct Declared<'ast>(HashSet<&'ast str>);
+
+impl<'ast> Visit<'ast> for Declared<'ast> {
+    fn visit_declarator(&mut self, declarator: &'ast Declarator, span: &'ast Span) {
+        if let DeclaratorKind::Identifier(ref i) = declarator.kind.node {
+            self.0.insert(&i.node.name);
+        }
+        visit::visit_declarator(self, declarator, span);
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &'ast Enumerator, span: &'ast Span) {
+        self.0.insert(&enumerator.identifier.node.name);
+        visit::visit_enumerator(self, enumerator, span);
+    }
+}
+
+/// Identifiers and typedef names referred to by an expression
+#[derive(Default)]
+struct Names<'ast>(HashSet<&'ast str>);
+
+impl<'ast> Visit<'ast> for Names<'ast> {
+    fn visit_expression(&mut self, expression: &'ast Expression, span: &'ast Span) {
+        if let Expression::Identifier(ref i) = *expression {
+            self.0.insert(&i.node.name);
+        }
+        visit::visit_expression(self, expression, span);
+    }
+
+    fn visit_type_specifier(&mut self, type_specifier: &'ast TypeSpecifier, span: &'ast Span) {
+        if let TypeSpecifier::TypedefName(ref i) = *type_specifier {
+            self.0.insert(&i.node.name);
+        }
+        visit::visit_type_specifier(self, type_specifier, span);
+    }
+}
@@ -0,0 +1,624 @@
+//! Memory layout of types
+//!
+//! Computes sizes and alignments of types, and offsets of struct and union members, for a target
+//! data model from the declarations of a translation unit.
+//!
+//! ```
+//! use lang_c::ast::StructKind;
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::layout::{Endianness, Layout, Layouts, Target};
+//!
+//! let source = r#"
+//!     typedef unsigned long size_t;
+//!     struct header { char tag; size_t length; unsigned flags : 3, kind : 5; char name[]; };
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//!
+//! let layouts = Layouts::new(&parse.unit, Target::lp64(Endianness::Little));
+//! let header = layouts.tag(StructKind::Struct, "header").unwrap();
+//! let record = layouts.record(header).unwrap();
+//! let members: Vec<_> = record
+//!     .members
+//!     .iter()
+//!     .map(|m| (m.name.unwrap(), m.offset, m.bits))
+//!     .collect();
+//! assert_eq!(
+//!     members,
+//!     [
+//!         ("tag", 0, None),
+//!         ("length", 8, None),
+//!         ("flags", 16, Some((0, 3))),
+//!         ("kind", 16, Some((3, 5))),
+//!     ]
+//! );
+//! assert_eq!(record.layout, Layout { size: 24, alignment: 8 });
+//!
+//! let layouts = Layouts::new(&parse.unit, Target::ilp32(Endianness::Little));
+//! let header = layouts.tag(StructKind::Struct, "header").unwrap();
+//! assert_eq!(layouts.record(header).unwrap().layout, Layout { size: 12, alignment: 4 });
+//! ```
+//!
+//! Scalars are aligned to their size, `long double`, complex and atomic types are not supported,
+//! and attributes such as `packed` and `aligned` are ignored. A flexible array member is not part
+//! of the members, but its alignment applies to the struct.
+//!
+//! Bit-fields are allocated in units of their declared type. With System V rules a bit-field
+//! starts a new unit when it would otherwise cross one, and only named bit-fields affect the
+//! alignment of the struct. With Microsoft rules a bit-field shares a unit only with the
+//! bit-fields right before it whose types have the same size, and a zero-width bit-field only
+//! closes the unit of a bit-field before it. Bits are assigned from the least significant bit
+//! of a unit on little-endian targets and from the most significant bit on big-endian ones.
+//!
+//! ```
+//! # use lang_c::ast::StructKind;
+//! # use lang_c::driver::{parse_preprocessed, Config};
+//! # use lang_c::layout::{BitFields, Endianness, Layouts, Target};
+//! let source = "struct s { char a : 4; int b : 4; char c : 4; };";
+//! # let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//! let offsets = |target: Target| {
+//!     let layouts = Layouts::new(&parse.unit, target);
+//!     let record = layouts.record(layouts.tag(StructKind::Struct, "s").unwrap()).unwrap();
+//!     let offsets: Vec<_> = record.members.iter().map(|m| (m.offset, m.bits)).collect();
+//!     (offsets, record.layout.size)
+//! };
+//!
+//! let system_v = Target::lp64(Endianness::Little);
+//! let (members, size) = offsets(system_v);
+//! assert_eq!(members, [(0, Some((0, 4))), (0, Some((4, 4))), (1, Some((0, 4)))]);
+//! assert_eq!(size, 4);
+//!
+//! let microsoft = Target::llp64(Endianness::Little);
+//! assert_eq!(microsoft.bit_fields, BitFields::Microsoft);
+//! let (members, size) = offsets(microsoft);
+//! assert_eq!(members, [(0, Some((0, 4))), (4, Some((0, 4))), (8, Some((0, 4)))]);
+//! assert_eq!(size, 12);
+//! ```
+//!
+//! Tags and typedef names are collected from the whole translation unit without regard to
+//! scope; the first definition of each name is used.
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+
+use ast::*;
+use eval::{eval_with, Charset, EvalError, Value};
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// Byte order of a target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// Rules for allocating bit-fields in a struct
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitFields {
+    /// System V and Itanium C++ ABIs, used by GCC and Clang outside Windows
+    SystemV,
+    /// Microsoft ABI, used by MSVC and by GCC with `-mms-bitfields`
+    Microsoft,
+}
+
+/// Data model of a target
+///
+/// Sizes of integer types are at most 8 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Target {
+    pub endianness: Endianness,
+    /// Size of pointers in bytes
+    pub pointer_size: usize,
+    /// Size of `int` in bytes
+    pub int_size: usize,
+    /// Size of `long` in bytes
+    pub long_size: usize,
+    /// Encoding of plain character constants and string literals
+    pub charset: Charset,
+    pub bit_fields: BitFields,
+}
+
+impl Target {
+    /// 32-bit `int`, `long` and pointers
+    ///
+    /// The constructors use UTF-8 as the execution character set.
+    pub fn ilp32(endianness: Endianness) -> Target {
+        Target {
+            endianness,
+            pointer_size: 4,
+            int_size: 4,
+            long_size: 4,
+            charset: Charset::Utf8,
+            bit_fields: BitFields::SystemV,
+        }
+    }
+
+    /// 32-bit `int`, 64-bit `long` and pointers
+    pub fn lp64(endianness: Endianness) -> Target {
+        Target {
+            endianness,
+            pointer_size: 8,
+            int_size: 4,
+            long_size: 8,
+            charset: Charset::Utf8,
+            bit_fields: BitFields::SystemV,
+        }
+    }
+
+    /// 32-bit `int` and `long`, 64-bit pointers, with Microsoft bit-fields as on Windows
+    pub fn llp64(endianness: Endianness) -> Target {
+        Target {
+            endianness,
+            pointer_size: 8,
+            int_size: 4,
+            long_size: 4,
+            charset: Charset::Utf8,
+            bit_fields: BitFields::Microsoft,
+        }
+    }
+}
+
+/// Type of an object, reduced to what determines its layout
+#[derive(Debug, Clone)]
+pub enum Type<'ast> {
+    Bool,
+    /// Integer of the given size in bytes, including enumerations
+    Integer(usize),
+    /// Floating point number of the given size in bytes
+    Float(usize),
+    Pointer,
+    /// Array with its element type and length, `None` if the length is not given
+    Array(Box<Type<'ast>>, Option<usize>),
+    /// Struct or union, completed by a definition with the same tag if it has no members
+    Record(&'ast StructType, Span),
+    /// Type without a layout, with the location of the construct that prevents one
+    Unsupported(Span),
+}
+
+/// Size and alignment of a type in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub size: usize,
+    pub alignment: usize,
+}
+
+/// Struct or union member that can be initialized
+#[derive(Debug, Clone)]
+pub struct Member<'ast> {
+    /// Name of the member, `None` for an anonymous struct or union
+    pub name: Option<&'ast str>,
+    pub ty: Type<'ast>,
+    /// Offset from the start of the struct or union in bytes
+    pub offset: usize,
+    /// Bit offset within the byte at `offset` and width of a bit-field
+    pub bits: Option<(usize, usize)>,
+}
+
+/// Members and layout of a struct or union
+#[derive(Debug, Clone)]
+pub struct Record<'ast> {
+    pub kind: StructKind,
+    /// Members in declaration order, without unnamed bit-fields and flexible array members
+    pub members: Vec<Member<'ast>>,
+    pub layout: Layout,
+}
+
+/// Reason a type has no layout
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutError {
+    /// Type is incomplete, variably modified or not supported
+    UnsupportedType(Span),
+    /// Array length or bit-field width is not a non-negative constant
+    InvalidSize(Span),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LayoutError::UnsupportedType(_) => write!(fmt, "type has no known layout"),
+            LayoutError::InvalidSize(_) => write!(fmt, "invalid array length or bit-field width"),
+        }
+    }
+}
+
+impl error::Error for LayoutError {
+    fn description(&self) -> &str {
+        "layout error"
+    }
+}
+
+/// Tags, typedefs and enumeration constants of a translation unit
+pub struct Layouts<'ast> {
+    target: Target,
+    structs: HashMap<(StructKind, &'ast str), &'ast StructType>,
+    typedefs: HashMap<&'ast str, Type<'ast>>,
+    constants: HashMap<&'ast str, Value>,
+}
+
+impl<'ast> Layouts<'ast> {
+    /// Collect definitions from a translation unit
+    pub fn new(unit: &'ast TranslationUnit, target: Target) -> Layouts<'ast> {
+        let mut layouts = Layouts {
+            target,
+            structs: HashMap::new(),
+            typedefs: HashMap::new(),
+            constants: HashMap::new(),
+        };
+        layouts.visit_translation_unit(unit);
+        layouts
+    }
+
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+
+    /// Definition of a struct or union tag
+    pub fn tag(&self, kind: StructKind, name: &str) -> Option<&'ast StructType> {
+        self.structs.get(&(kind, name)).cloned()
+    }
+
+    /// Type a typedef name stands for
+    pub fn typedef(&self, name: &str) -> Option<&Type<'ast>> {
+        self.typedefs.get(name)
+    }
+
+    /// Evaluate a constant expression, with the values of the enumeration constants
+    pub fn eval(&self, e: &Node<Expression>) -> Result<Value, EvalError> {
+        eval_with(e, &|name| self.constants.get(name).cloned(), &self.target)
+    }
+
+    /// Type given by the type specifiers of a declaration
+    pub fn base<I>(&self, specifiers: I) -> Type<'ast>
+    where
+        I: IntoIterator<Item = &'ast Node<TypeSpecifier>>,
+    {
+        let (mut size, mut float, mut longs) = (None, None, 0);
+        let mut span = Span::none();
+        for specifier in specifiers {
+            span = specifier.span;
+            match specifier.node {
+                TypeSpecifier::Char => size = Some(1),
+                TypeSpecifier::Short => size = Some(2),
+                TypeSpecifier::Int | TypeSpecifier::Signed | TypeSpecifier::Unsigned => {}
+                TypeSpecifier::Long => longs += 1,
+                TypeSpecifier::Int128 => size = Some(16),
+                TypeSpecifier::Float => float = Some(4),
+                TypeSpecifier::Double if longs == 0 => float = Some(8),
+                TypeSpecifier::Bool => return Type::Bool,
+                TypeSpecifier::Struct(ref s) => return Type::Record(&s.node, s.span),
+                TypeSpecifier::Enum(ref e) => {
+                    return match e.node.underlying {
+                        Some(ref u) => self.base(u.iter().filter_map(type_specifier)),
+                        None => Type::Integer(self.target.int_size),
+                    }
+                }
+                TypeSpecifier::TypedefName(ref i) => {
+                    return match self.typedefs.get(&*i.node.name) {
+                        Some(ty) => ty.clone(),
+                        None => Type::Unsupported(i.span),
+                    }
+                }
+                _ => return Type::Unsupported(specifier.span),
+            }
+        }
+        match (float, longs) {
+            (Some(size), 0) => Type::Float(size),
+            (None, 0) => Type::Integer(size.unwrap_or(self.target.int_size)),
+            (None, 1) => Type::Integer(self.target.long_size),
+            (None, _) => Type::Integer(8),
+            (Some(_), _) => Type::Unsupported(span),
+        }
+    }
+
+    /// Apply the derivations of a declarator to its base type
+    pub fn declarator(&self, base: Type<'ast>, d: &'ast Declarator) -> Type<'ast> {
+        let mut ty = base;
+        // Pointers bind to the base type before the array and function suffixes
+        for derived in &d.derived {
+            if let DerivedDeclarator::Pointer(_) = derived.node {
+                ty = Type::Pointer;
+            }
+        }
+        for derived in d.derived.iter().rev() {
+            match derived.node {
+                DerivedDeclarator::Pointer(_) => {}
+                DerivedDeclarator::Array(ref a) => {
+                    let length = match a.node.size {
+                        ArraySize::Unknown => None,
+                        ArraySize::VariableExpression(ref e)
+                        | ArraySize::StaticExpression(ref e) => match self.size(e) {
+                            Ok(n) => Some(n),
+                            Err(_) => return Type::Unsupported(e.span),
+                        },
+                        ArraySize::VariableUnknown => return Type::Unsupported(a.span),
+                    };
+                    ty = Type::Array(Box::new(ty), length);
+                }
+                DerivedDeclarator::Function(_) | DerivedDeclarator::KRFunction(_) => {
+                    ty = Type::Unsupported(derived.span);
+                }
+            }
+        }
+        match d.kind.node {
+            DeclaratorKind::Declarator(ref inner) => self.declarator(ty, &inner.node),
+            _ => ty,
+        }
+    }
+
+    /// Size and alignment of a type
+    pub fn layout(&self, ty: &Type<'ast>) -> Result<Layout, LayoutError> {
+        let scalar = |size| {
+            Ok(Layout {
+                size,
+                alignment: size,
+            })
+        };
+        match *ty {
+            Type::Bool => scalar(1),
+            Type::Integer(size) | Type::Float(size) => scalar(size),
+            Type::Pointer => scalar(self.target.pointer_size),
+            Type::Array(ref element, Some(length)) => {
+                let element = try!(self.layout(element));
+                Ok(Layout {
+                    size: element.size * length,
+                    alignment: element.alignment,
+                })
+            }
+            Type::Array(_, None) => Err(LayoutError::UnsupportedType(Span::none())),
+            Type::Record(s, _) => self.record(s).map(|r| r.layout),
+            Type::Unsupported(span) => Err(LayoutError::UnsupportedType(span)),
+        }
+    }
+
+    /// Members of a struct or union with their offsets, and its layout
+    ///
+    /// A struct or union declared without members is completed by the definition of its tag.
+    pub fn record(&self, s: &'ast StructType) -> Result<Record<'ast>, LayoutError> {
+        let s = try!(self.complete(s));
+        let union = s.kind.node == StructKind::Union;
+        let mut members = Vec::new();
+        // Next free bit for struct members, end of the largest member for unions
+        let (mut bit, mut end, mut alignment) = (0, 0, 1);
+        // Start and size in bits of the unit the last Microsoft bit-field was allocated in
+        let mut unit: Option<(usize, usize)> = None;
+        let declarations = s.declarations.as_ref().map_or(&[][..], |d| &d[..]);
+        for declaration in declarations {
+            let field = match declaration.node {
+                StructDeclaration::Field(ref f) => &f.node,
+                StructDeclaration::StaticAssert(_) => continue,
+            };
+            let base = self.base(field.specifiers.iter().filter_map(type_specifier));
+            if field.declarators.is_empty() {
+                if let Type::Record(..) = base {
+                    if let Some((start, size)) = unit.take() {
+                        bit = start + size;
+                    }
+                    let layout = try!(self.layout(&base));
+                    let start = if union {
+                        0
+                    } else {
+                        round_up(bit, layout.alignment * 8)
+                    };
+                    members.push(Member {
+                        name: None,
+                        ty: base,
+                        offset: start / 8,
+                        bits: None,
+                    });
+                    bit = start + layout.size * 8;
+                    end = end.max(bit);
+                    alignment = alignment.max(layout.alignment);
+                }
+                continue;
+            }
+            for declarator in &field.declarators {
+                let (ty, name) = match declarator.node.declarator {
+                    Some(ref d) => (
+                        self.declarator(base.clone(), &d.node),
+                        declarator_name(&d.node),
+                    ),
+                    None => (base.clone(), None),
+                };
+                if let Type::Array(ref element, None) = ty {
+                    // Flexible array member, not part of the size and not initializable
+                    alignment = alignment.max(try!(self.layout(element)).alignment);
+                    continue;
+                }
+                let layout = try!(self.layout(&ty));
+                let width = match declarator.node.bit_width {
+                    Some(ref w) => try!(self.size(w)),
+                    None => {
+                        if let Some((start, size)) = unit.take() {
+                            bit = start + size;
+                        }
+                        let start = if union {
+                            0
+                        } else {
+                            round_up(bit, layout.alignment * 8)
+                        };
+                        members.push(Member {
+                            name,
+                            ty,
+                            offset: start / 8,
+                            bits: None,
+                        });
+                        bit = start + layout.size * 8;
+                        end = end.max(bit);
+                        alignment = alignment.max(layout.alignment);
+                        continue;
+                    }
+                };
+                let size = layout.size * 8;
+                let start = match self.target.bit_fields {
+                    BitFields::SystemV => {
+                        let start = if union { 0 } else { bit };
+                        if width == 0 || start / size != (start + width - 1) / size {
+                            round_up(start, size)
+                        } else {
+                            start
+                        }
+                    }
+                    // Bit-fields share a unit only with the ones before it of the same size
+                    BitFields::Microsoft => match unit {
+                        Some((start, s))
+                            if !union && s == size && width > 0 && bit + width <= start + s =>
+                        {
+                            bit
+                        }
+                        _ => {
+                            if let Some((start, s)) = unit.take() {
+                                bit = start + s;
+                            }
+                            if width == 0 {
+                                continue;
+                            }
+                            let start = if union { 0 } else { round_up(bit, size) };
+                            unit = Some((start, size));
+                            end = end.max(start + size);
+                            alignment = alignment.max(layout.alignment);
+                            start
+                        }
+                    },
+                };
+                bit = start + width;
+                end = end.max(bit);
+                if name.is_some() {
+                    alignment = alignment.max(layout.alignment);
+                    members.push(Member {
+                        name,
+                        ty,
+                        offset: start / 8,
+                        bits: Some((start % 8, width)),
+                    });
+                }
+            }
+        }
+        Ok(Record {
+            kind: s.kind.node.clone(),
+            members,
+            layout: Layout {
+                size: round_up((end + 7) / 8, alignment),
+                alignment,
+            },
+        })
+    }
+
+    /// Indices of the members leading to `name`, through anonymous structs and unions
+    pub fn member_path(&self, members: &[Member<'ast>], name: &str) -> Option<Vec<usize>> {
+        for (index, member) in members.iter().enumerate() {
+            match (member.name, &member.ty) {
+                (Some(n), _) if n == name => return Some(vec![index]),
+                (None, &Type::Record(s, _)) => {
+                    let inner = match self.record(s) {
+                        Ok(inner) => inner,
+                        Err(_) => continue,
+                    };
+                    if let Some(mut path) = self.member_path(&inner.members, name) {
+                        path.insert(0, index);
+                        return Some(path);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn complete(&self, s: &'ast StructType) -> Result<&'ast StructType, LayoutError> {
+        match (&s.declarations, &s.identifier) {
+            (&Some(_), _) => Ok(s),
+            (&None, &Some(ref i)) => match self.tag(s.kind.node.clone(), &i.node.name) {
+                Some(s) => Ok(s),
+                None => Err(LayoutError::UnsupportedType(i.span)),
+            },
+            (&None, &None) => Err(LayoutError::UnsupportedType(Span::none())),
+        }
+    }
+
+    /// Array length or bit-field width
+    fn size(&self, e: &Node<Expression>) -> Result<usize, LayoutError> {
+        match self.eval(e).map(|v| v.integer()) {
+            Ok(Some(i)) if i >= 0 => Ok(i as usize),
+            _ => Err(LayoutError::InvalidSize(e.span)),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Layouts<'ast> {
+    fn visit_declaration(&mut self, declaration: &'ast Declaration, span: &'ast Span) {
+        visit::visit_declaration(self, declaration, span);
+        let is_typedef = declaration.specifiers.iter().any(|s| match s.node {
+            DeclarationSpecifier::StorageClass(ref s) => s.node == StorageClassSpecifier::Typedef,
+            _ => false,
+        });
+        if !is_typedef {
+            return;
+        }
+        let specifiers = declaration.specifiers.iter().filter_map(|s| match s.node {
+            DeclarationSpecifier::TypeSpecifier(ref t) => Some(t),
+            _ => None,
+        });
+        let base = self.base(specifiers);
+        for init in &declaration.declarators {
+            let d = &init.node.declarator.node;
+            if let Some(name) = declarator_name(d) {
+                let ty = self.declarator(base.clone(), d);
+                self.typedefs.entry(name).or_insert(ty);
+            }
+        }
+    }
+
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        if let (Some(_), Some(ref i)) = (&struct_type.declarations, &struct_type.identifier) {
+            self.structs
+                .entry((struct_type.kind.node.clone(), &i.node.name))
+                .or_insert(struct_type);
+        }
+        visit::visit_struct_type(self, struct_type, span);
+    }
+
+    fn visit_enum_type(&mut self, enum_type: &'ast EnumType, span: &'ast Span) {
+        let mut next = Value::Int(0);
+        for enumerator in &enum_type.enumerators {
+            let value = match enumerator.node.expression {
+                Some(ref e) => match self.eval(e) {
+                    Ok(v) => v,
+                    Err(_) => break,
+                },
+                None => next,
+            };
+            self.constants
+                .insert(&enumerator.node.identifier.node.name, value);
+            next = match value {
+                Value::Int(i) => Value::Int(i.wrapping_add(1)),
+                Value::Unsigned(u) => Value::Unsigned(u.wrapping_add(1)),
+                Value::Long(i) => Value::Long(i.wrapping_add(1)),
+                Value::UnsignedLong(u) => Value::UnsignedLong(u.wrapping_add(1)),
+                Value::LongLong(i) => Value::LongLong(i.wrapping_add(1)),
+                Value::UnsignedLongLong(u) => Value::UnsignedLongLong(u.wrapping_add(1)),
+                Value::Float(_) => break,
+            };
+        }
+        visit::visit_enum_type(self, enum_type, span);
+    }
+}
+
+fn round_up(n: usize, multiple: usize) -> usize {
+    (n + multiple - 1) / multiple * multiple
+}
+
+fn type_specifier(s: &Node<SpecifierQualifier>) -> Option<&Node<TypeSpecifier>> {
+    match s.node {
+        SpecifierQualifier::TypeSpecifier(ref t) => Some(t),
+        _ => None,
+    }
+}
+
+fn declarator_name(d: &Declarator) -> Option<&str> {
+    match d.kind.node {
+        DeclaratorKind::Abstract => None,
+        DeclaratorKind::Identifier(ref i) => Some(&i.node.name),
+        DeclaratorKind::Declarator(ref d) => declarator_name(&d.node),
+    }
+}
@@ -10,17 +10,62 @@
 //! ```
 
 #![allow(deprecated)]
+// Lints added to rustc and clippy after the code they fire on was written
+#![allow(unknown_lints)]
+#![allow(mismatched_lifetime_syntaxes)]
+#![allow(clippy::large_enum_variant)]
+#![allow(clippy::len_zero)]
+#![allow(clippy::match_ref_pats)]
+#![allow(clippy::needless_borrowed_reference)]
+#![allow(clippy::needless_lifetimes)]
+#![allow(clippy::redundant_field_names)]
+#![allow(clippy::redundant_static_lifetimes)]
+#![allow(clippy::self_named_constructors)]
+#![allow(clippy::single_match)]
+#![allow(clippy::writeln_empty_string)]
 
+pub mod annotations;
+pub mod asm;
 pub mod ast;
+pub mod cfg;
+pub mod constructors;
 pub mod driver;
+pub mod eval;
+pub mod expr;
+pub mod fields;
+pub mod header;
+pub mod ide;
+pub mod image;
+pub mod includes;
+pub mod index;
+pub mod initializers;
+pub mod inline;
+pub mod layout;
+pub mod limits;
+pub mod loops;
+pub mod pipeline;
 pub mod print;
+pub mod reduce;
+pub mod reserved;
+pub mod size;
+pub mod snippet;
 pub mod span;
+pub mod stack;
+pub mod structure;
+pub mod switches;
+pub mod typedeps;
+pub mod unparse;
 pub mod visit;
 
 mod astutil;
+#[cfg_attr(test, allow(dead_code))]
 mod env;
+#[allow(clippy::all)]
 mod parser;
 mod strings;
 
 #[cfg(test)]
+#[allow(clippy::needless_borrow)]
+#[allow(clippy::question_mark)]
+#[allow(clippy::single_char_add_str)]
 mod tests;
@@ -0,0 +1,435 @@
+//! Translation limits
+//!
+//! C11 5.2.4.1 lists minimum limits that every implementation must support, such as 127 nesting
+//! levels of blocks or 4095 characters in a string literal. Code that stays within them is more
+//! likely to be accepted by compilers for small targets. This module measures the constructs
+//! those limits apply to and reports the ones exceeding configurable thresholds.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::limits::{check, measure, Limit, Limits};
+//!
+//! let source = r#"
+//!     int table[] = { 1, 2, 3, 4, 5 };
+//!     int sum(int n) { { { return n + table[0]; } } }
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//!
+//! let nesting = measure(&parse.unit)
+//!     .into_iter()
+//!     .find(|m| m.limit == Limit::BlockNesting)
+//!     .unwrap();
+//! assert_eq!(nesting.value, 3);
+//!
+//! let mut limits = Limits::c11();
+//! limits.set(Limit::Initializers, 4);
+//! let excess: Vec<_> = check(&parse.unit, &limits).iter().map(|m| m.limit).collect();
+//! assert_eq!(excess, [Limit::Initializers]);
+//! ```
+//!
+//! Measurements follow the syntax tree. Typedef names are not expanded when counting declarator
+//! derivations, and identifiers are only counted where they are declared.
+
+use std::collections::{BTreeMap, HashMap};
+use std::mem;
+
+use ast::*;
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// Construct that a translation limit applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Limit {
+    /// Nesting levels of compound statements, counting the function body as the first
+    BlockNesting,
+    /// Nesting levels of struct and union definitions
+    StructNesting,
+    /// Pointer, array and function declarators in one declarator
+    DeclaratorDerivations,
+    /// Characters in a declared identifier
+    IdentifierLength,
+    /// Characters in an identifier declared at file scope without `static`
+    ExternalIdentifierLength,
+    /// Identifiers declared by the declarations of one block
+    BlockIdentifiers,
+    /// Parameters of a function declarator
+    Parameters,
+    /// Arguments of a function call
+    Arguments,
+    /// Characters in a string literal after concatenation
+    StringLength,
+    /// Members of a struct or union
+    Members,
+    /// Enumeration constants of an enum
+    Enumerators,
+    /// Case labels of a switch statement, excluding those of nested switch statements
+    CaseLabels,
+    /// Items of an initializer list
+    Initializers,
+}
+
+impl Limit {
+    /// Smallest limit every implementation supports (C11 5.2.4.1)
+    ///
+    /// Returns `None` for `Initializers`, which the standard does not limit.
+    pub fn minimum(self) -> Option<usize> {
+        Some(match self {
+            Limit::BlockNesting => 127,
+            Limit::StructNesting => 63,
+            Limit::DeclaratorDerivations => 12,
+            Limit::IdentifierLength => 63,
+            Limit::ExternalIdentifierLength => 31,
+            Limit::BlockIdentifiers => 511,
+            Limit::Parameters => 127,
+            Limit::Arguments => 127,
+            Limit::StringLength => 4095,
+            Limit::Members => 1023,
+            Limit::Enumerators => 1023,
+            Limit::CaseLabels => 1023,
+            Limit::Initializers => return None,
+        })
+    }
+}
+
+/// Thresholds to check a translation unit against
+///
+/// The default thresholds are the minimum limits of C11.
+#[derive(Debug, Clone)]
+pub struct Limits {
+    thresholds: HashMap<Limit, usize>,
+}
+
+impl Limits {
+    /// Thresholds at the minimum limits of C11
+    pub fn c11() -> Limits {
+        let all = [
+            Limit::BlockNesting,
+            Limit::StructNesting,
+            Limit::DeclaratorDerivations,
+            Limit::IdentifierLength,
+            Limit::ExternalIdentifierLength,
+            Limit::BlockIdentifiers,
+            Limit::Parameters,
+            Limit::Arguments,
+            Limit::StringLength,
+            Limit::Members,
+            Limit::Enumerators,
+            Limit::CaseLabels,
+            Limit::Initializers,
+        ];
+        Limits {
+            thresholds: all
+                .iter()
+                .filter_map(|&l| l.minimum().map(|m| (l, m)))
+                .collect(),
+        }
+    }
+
+    /// Largest value allowed for a limit, `None` if it is not checked
+    pub fn get(&self, limit: Limit) -> Option<usize> {
+        self.thresholds.get(&limit).cloned()
+    }
+
+    pub fn set(&mut self, limit: Limit, threshold: usize) {
+        self.thresholds.insert(limit, threshold);
+    }
+
+    /// Stop checking a limit
+    pub fn remove(&mut self, limit: Limit) {
+        self.thresholds.remove(&limit);
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits::c11()
+    }
+}
+
+/// Value of a limited quantity at one construct
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub limit: Limit,
+    pub value: usize,
+    /// Construct that was measured
+    pub span: Span,
+}
+
+/// Largest value of each limited quantity in a translation unit
+///
+/// Returns one measurement per limit that applies to some construct, ordered by limit. Ties are
+/// resolved in favor of the first construct.
+pub fn measure(unit: &TranslationUnit) -> Vec<Measurement> {
+    let mut largest: BTreeMap<Limit, Measurement> = BTreeMap::new();
+    for m in collect(unit) {
+        let replace = match largest.get(&m.limit) {
+            Some(l) => m.value > l.value,
+            None => true,
+        };
+        if replace {
+            largest.insert(m.limit, m);
+        }
+    }
+    largest.values().cloned().collect()
+}
+
+/// Constructs of a translation unit that exceed a threshold, in source order
+pub fn check(unit: &TranslationUnit, limits: &Limits) -> Vec<Measurement> {
+    let mut excess: Vec<_> = collect(unit)
+        .into_iter()
+        .filter(|m| match limits.get(m.limit) {
+            Some(threshold) => m.value > threshold,
+            None => false,
+        })
+        .collect();
+    excess.sort_by_key(|m| (m.span.start, m.limit));
+    excess
+}
+
+fn collect(unit: &TranslationUnit) -> Vec<Measurement> {
+    let mut metrics = Metrics {
+        measurements: Vec::new(),
+        blocks: 0,
+        structs: 0,
+        switches: Vec::new(),
+        nested: false,
+    };
+    metrics.visit_translation_unit(unit);
+    metrics.measurements
+}
+
+struct Metrics {
+    measurements: Vec<Measurement>,
+    blocks: usize,
+    structs: usize,
+    /// Case labels seen in each enclosing switch statement
+    switches: Vec<usize>,
+    /// Next declarator is parenthesized inside another one and already counted
+    nested: bool,
+}
+
+impl Metrics {
+    fn record(&mut self, limit: Limit, value: usize, span: Span) {
+        self.measurements.push(Measurement { limit, value, span });
+    }
+
+    fn identifier(&mut self, limit: Limit, identifier: &Node<Identifier>) {
+        let length = identifier.node.name.chars().count();
+        self.record(limit, length, identifier.span);
+    }
+}
+
+impl<'ast> Visit<'ast> for Metrics {
+    fn visit_external_declaration(
+        &mut self,
+        external_declaration: &'ast ExternalDeclaration,
+        span: &'ast Span,
+    ) {
+        let (specifiers, declarators) = match *external_declaration {
+            ExternalDeclaration::Declaration(ref d) => (
+                &d.node.specifiers[..],
+                d.node
+                    .declarators
+                    .iter()
+                    .map(|i| &i.node.declarator)
+                    .collect(),
+            ),
+            ExternalDeclaration::FunctionDefinition(ref f) => {
+                (&f.node.specifiers[..], vec![&f.node.declarator])
+            }
+            ExternalDeclaration::StaticAssert(_) | ExternalDeclaration::LinkageSpecification(_) => {
+                (&[][..], Vec::new())
+            }
+        };
+        let internal = specifiers.iter().any(|s| {
+            if let DeclarationSpecifier::StorageClass(ref s) = s.node {
+                s.node == StorageClassSpecifier::Static || s.node == StorageClassSpecifier::Typedef
+            } else {
+                false
+            }
+        });
+        if !internal {
+            for declarator in declarators {
+                if let Some(i) = declarator_identifier(&declarator.node) {
+                    self.identifier(Limit::ExternalIdentifierLength, i);
+                }
+            }
+        }
+        visit::visit_external_declaration(self, external_declaration, span);
+    }
+
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        if let Statement::Compound(ref items) = *statement {
+            self.blocks += 1;
+            let blocks = self.blocks;
+            self.record(Limit::BlockNesting, blocks, *span);
+            let identifiers = items
+                .iter()
+                .map(|item| match item.node {
+                    BlockItem::Declaration(ref d) => d.node.declarators.len(),
+                    _ => 0,
+                })
+                .sum();
+            self.record(Limit::BlockIdentifiers, identifiers, *span);
+            visit::visit_statement(self, statement, span);
+            self.blocks -= 1;
+        } else {
+            visit::visit_statement(self, statement, span);
+        }
+    }
+
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        if let Some(ref i) = struct_type.identifier {
+            self.identifier(Limit::IdentifierLength, i);
+        }
+        let declarations = match struct_type.declarations {
+            Some(ref declarations) => declarations,
+            None => return visit::visit_struct_type(self, struct_type, span),
+        };
+
+        self.structs += 1;
+        let structs = self.structs;
+        self.record(Limit::StructNesting, structs, *span);
+        let members = declarations
+            .iter()
+            .map(|d| match d.node {
+                // Anonymous struct or union members have no declarators
+                StructDeclaration::Field(ref f) => f.node.declarators.len().max(1),
+                StructDeclaration::StaticAssert(_) => 0,
+            })
+            .sum();
+        self.record(Limit::Members, members, *span);
+        visit::visit_struct_type(self, struct_type, span);
+        self.structs -= 1;
+    }
+
+    fn visit_enum_type(&mut self, enum_type: &'ast EnumType, span: &'ast Span) {
+        if let Some(ref i) = enum_type.identifier {
+            self.identifier(Limit::IdentifierLength, i);
+        }
+        if !enum_type.enumerators.is_empty() {
+            self.record(Limit::Enumerators, enum_type.enumerators.len(), *span);
+        }
+        visit::visit_enum_type(self, enum_type, span);
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &'ast Enumerator, span: &'ast Span) {
+        self.identifier(Limit::IdentifierLength, &enumerator.identifier);
+        visit::visit_enumerator(self, enumerator, span);
+    }
+
+    fn visit_declarator(&mut self, declarator: &'ast Declarator, span: &'ast Span) {
+        if !mem::replace(&mut self.nested, false) {
+            self.record(Limit::DeclaratorDerivations, derivations(declarator), *span);
+        }
+        if let DeclaratorKind::Identifier(ref i) = declarator.kind.node {
+            self.identifier(Limit::IdentifierLength, i);
+        }
+        visit::visit_declarator(self, declarator, span);
+    }
+
+    fn visit_declarator_kind(&mut self, declarator_kind: &'ast DeclaratorKind, span: &'ast Span) {
+        if let DeclaratorKind::Declarator(_) = *declarator_kind {
+            self.nested = true;
+        }
+        visit::visit_declarator_kind(self, declarator_kind, span);
+    }
+
+    fn visit_function_declarator(
+        &mut self,
+        function_declarator: &'ast FunctionDeclarator,
+        span: &'ast Span,
+    ) {
+        let parameters = function_declarator.parameters.len();
+        self.record(Limit::Parameters, parameters, *span);
+        visit::visit_function_declarator(self, function_declarator, span);
+    }
+
+    fn visit_call_expression(&mut self, call_expression: &'ast CallExpression, span: &'ast Span) {
+        let arguments = call_expression.arguments.len();
+        self.record(Limit::Arguments, arguments, *span);
+        visit::visit_call_expression(self, call_expression, span);
+    }
+
+    fn visit_string_literal(&mut self, string_literal: &'ast StringLiteral, span: &'ast Span) {
+        let length = string_literal.iter().map(|s| string_length(s)).sum();
+        self.record(Limit::StringLength, length, *span);
+        visit::visit_string_literal(self, string_literal, span);
+    }
+
+    fn visit_switch_statement(
+        &mut self,
+        switch_statement: &'ast SwitchStatement,
+        span: &'ast Span,
+    ) {
+        self.switches.push(0);
+        visit::visit_switch_statement(self, switch_statement, span);
+        let labels = self.switches.pop().unwrap();
+        self.record(Limit::CaseLabels, labels, *span);
+    }
+
+    fn visit_label(&mut self, label: &'ast Label, span: &'ast Span) {
+        match *label {
+            Label::Identifier(ref i) => self.identifier(Limit::IdentifierLength, i),
+            Label::Case(_) => {
+                if let Some(labels) = self.switches.last_mut() {
+                    *labels += 1;
+                }
+            }
+            Label::Default => {}
+        }
+        visit::visit_label(self, label, span);
+    }
+
+    fn visit_initializer(&mut self, initializer: &'ast Initializer, span: &'ast Span) {
+        if let Initializer::List(ref items) = *initializer {
+            self.record(Limit::Initializers, items.len(), *span);
+        }
+        visit::visit_initializer(self, initializer, span);
+    }
+}
+
+/// Number of derived declarators, including those of parenthesized declarators
+fn derivations(declarator: &Declarator) -> usize {
+    let inner = match declarator.kind.node {
+        DeclaratorKind::Declarator(ref d) => derivations(&d.node),
+        _ => 0,
+    };
+    declarator.derived.len() + inner
+}
+
+fn declarator_identifier(declarator: &Declarator) -> Option<&Node<Identifier>> {
+    match declarator.kind.node {
+        DeclaratorKind::Abstract => None,
+        DeclaratorKind::Identifier(ref i) => Some(i),
+        DeclaratorKind::Declarator(ref d) => declarator_identifier(&d.node),
+    }
+}
+
+/// Characters of a string literal token, counting each escape sequence as one
+fn string_length(token: &str) -> usize {
+    let body = token.trim_start_matches(|c| c != '"');
+    let body = body.get(1..body.len().saturating_sub(1)).unwrap_or("");
+    let mut chars = body.chars().peekable();
+    let mut length = 0;
+    while let Some(c) = chars.next() {
+        length += 1;
+        if c != '\\' {
+            continue;
+        }
+        let (digits, radix): (usize, u32) = match chars.next() {
+            Some('x') => (std::usize::MAX, 16),
+            Some('u') => (4, 16),
+            Some('U') => (8, 16),
+            Some(d) if d.is_digit(8) => (2, 8),
+            _ => (0, 8),
+        };
+        for _ in 0..digits {
+            match chars.peek() {
+                Some(d) if d.is_digit(radix) => chars.next(),
+                _ => break,
+            };
+        }
+    }
+    length
+}
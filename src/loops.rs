@@ -0,0 +1,454 @@
+//! Induction variables of `for` loops
+//!
+//! Recognizes `for` loops that step a single variable by a constant from an initial value
+//! towards a bound, and describes them in a canonical form: the variable starts at `initial`
+//! and changes by `step` while it has not passed `bound`, with `inclusive` telling whether the
+//! bound itself is reached. Conditions written with the variable on the right, `!=` and the
+//! different ways of writing the increment all map to the same form.
+//!
+//! ```
+//! use lang_c::ast::ExternalDeclaration;
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::loops::loops;
+//!
+//! let source = r#"
+//!     void f(int n, int *a) {
+//!         for (int i = 10; 0 <= i; i -= 2) a[i] = 0;
+//!         for (unsigned j = 0; j != n; ++j) a[j]++;
+//!         for (int k = 0; k < n; k++) k = a[k];
+//!     }
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//! let body = match parse.unit.0[0].node {
+//!     ExternalDeclaration::FunctionDefinition(ref f) => &f.node.statement,
+//!     _ => unreachable!(),
+//! };
+//!
+//! let found = loops(body, &|_| None);
+//! let i = found[0].induction.as_ref().unwrap();
+//! assert_eq!((i.variable, i.step, i.inclusive), ("i", -2, true));
+//! assert_eq!(i.trip_count, Some(6));
+//!
+//! let j = found[1].induction.as_ref().unwrap();
+//! assert_eq!((j.variable, j.step, j.inclusive), ("j", 1, false));
+//! assert_eq!(j.trip_count, None);
+//!
+//! assert!(found[2].induction.is_none());
+//! ```
+//!
+//! A loop has an induction variable if the variable is initialized or declared by the first
+//! clause, compared with the bound by the condition and changed only by the third clause, and if
+//! no variable in the bound is changed by the loop body. Output operands of `asm` statements
+//! count as changes. The step and, for the trip count, the initial value and the bound must
+//! evaluate to integer constants with `eval`. When the first clause declares the variable with
+//! an integer type, constants outside the range of that type in the LP64 data model, including
+//! the value after the last iteration, mean the loop has no induction variable. Otherwise the
+//! variable is assumed not to overflow. The trip count does not account for `break`, `goto` or
+//! `return` leaving the loop early.
+//!
+//! ```
+//! use lang_c::ast::ExternalDeclaration;
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::loops::loops;
+//!
+//! let source = r#"
+//!     void f(int *a) {
+//!         for (int i = 0; i < 10; i++) __asm__("" : "=r"(i));
+//!         for (unsigned char j = 0; j < 300; j++) a[j] = 0;
+//!         for (unsigned char k = 0; k <= 255; k++) a[k] = 0;
+//!         for (unsigned char m = 0; m < 255; m++) a[m] = 0;
+//!         for (signed char n = 100; n >= -128; n -= 1) a[n + 128] = 0;
+//!         for (signed char p = 100; p > -128; p -= 1) a[p + 128] = 0;
+//!     }
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//! let body = match parse.unit.0[0].node {
+//!     ExternalDeclaration::FunctionDefinition(ref f) => &f.node.statement,
+//!     _ => unreachable!(),
+//! };
+//!
+//! let found = loops(body, &|_| None);
+//! assert!(found[0].induction.is_none());
+//! assert!(found[1].induction.is_none());
+//! assert!(found[2].induction.is_none());
+//! assert_eq!(found[3].induction.as_ref().unwrap().trip_count, Some(255));
+//! assert!(found[4].induction.is_none());
+//! assert_eq!(found[5].induction.as_ref().unwrap().trip_count, Some(228));
+//! ```
+
+use std::collections::HashSet;
+
+use ast::*;
+use eval::{eval, Value};
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// `for` statement found in a function body
+#[derive(Debug, Clone)]
+pub struct Loop<'ast> {
+    pub statement: &'ast ForStatement,
+    /// Location of the `for` statement
+    pub span: Span,
+    /// Induction variable in canonical form, if the loop has one
+    pub induction: Option<Induction<'ast>>,
+}
+
+/// Variable stepped by a constant towards a bound
+#[derive(Debug, Clone)]
+pub struct Induction<'ast> {
+    /// Name of the variable
+    pub variable: &'ast str,
+    /// Value assigned by the first clause of the loop
+    pub initial: &'ast Node<Expression>,
+    /// Expression the variable is compared with
+    pub bound: &'ast Node<Expression>,
+    /// Whether the loop body runs when the variable equals the bound
+    pub inclusive: bool,
+    /// Change of the variable per iteration, negative when counting down
+    pub step: i64,
+    /// Number of iterations, if the initial value and the bound are constants
+    pub trip_count: Option<u64>,
+}
+
+/// All `for` loops in a statement, outer loops before the loops nested in them
+///
+/// `constants` provides the values of identifiers such as enumeration constants for evaluating
+/// steps, initial values and bounds.
+pub fn loops<'ast, F>(statement: &'ast Node<Statement>, constants: &F) -> Vec<Loop<'ast>>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    let mut collector = Collector {
+        loops: Vec::new(),
+        constants,
+    };
+    collector.visit_statement(&statement.node, &statement.span);
+    collector.loops
+}
+
+/// Find the induction variable of a `for` loop
+pub fn induction<'ast, F>(f: &'ast ForStatement, constants: &F) -> Option<Induction<'ast>>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    let (variable, initial, range) = match f.initializer.node {
+        ForInitializer::Expression(ref e) => match e.node {
+            Expression::BinaryOperator(ref b) if b.node.operator.node == BinaryOperator::Assign => {
+                (identifier(&b.node.lhs)?, &*b.node.rhs, None)
+            }
+            _ => return None,
+        },
+        ForInitializer::Declaration(ref d) => match d.node.declarators[..] {
+            [ref init] => {
+                let declarator = &init.node.declarator.node;
+                let name = match declarator.kind.node {
+                    DeclaratorKind::Identifier(ref i) => &*i.node.name,
+                    _ => return None,
+                };
+                let range = if declarator.derived.is_empty() {
+                    range(&d.node.specifiers)
+                } else {
+                    None
+                };
+                match init.node.initializer {
+                    Some(Node {
+                        node: Initializer::Expression(ref e),
+                        ..
+                    }) => (name, &**e, range),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let step = step(variable, f.step.as_ref()?, constants)?;
+
+    let condition = match f.condition {
+        Some(ref c) => match c.node {
+            Expression::BinaryOperator(ref b) => &b.node,
+            _ => return None,
+        },
+        None => return None,
+    };
+    let (operator, bound) = if identifier(&condition.lhs) == Some(variable) {
+        (condition.operator.node.clone(), &*condition.rhs)
+    } else if identifier(&condition.rhs) == Some(variable) {
+        let operator = match condition.operator.node {
+            BinaryOperator::Less => BinaryOperator::Greater,
+            BinaryOperator::LessOrEqual => BinaryOperator::GreaterOrEqual,
+            BinaryOperator::Greater => BinaryOperator::Less,
+            BinaryOperator::GreaterOrEqual => BinaryOperator::LessOrEqual,
+            ref op => op.clone(),
+        };
+        (operator, &*condition.lhs)
+    } else {
+        return None;
+    };
+    let inclusive = match (operator, step > 0) {
+        (BinaryOperator::Less, true) | (BinaryOperator::Greater, false) => false,
+        (BinaryOperator::LessOrEqual, true) | (BinaryOperator::GreaterOrEqual, false) => true,
+        (BinaryOperator::NotEquals, _) if step == 1 || step == -1 => false,
+        _ => return None,
+    };
+
+    let mut names = Names::default();
+    names.visit_expression(&bound.node, &bound.span);
+    if names.0.contains(variable) {
+        return None;
+    }
+    names.0.insert(variable);
+    let mut changes = Changes {
+        names: &names.0,
+        changed: false,
+    };
+    changes.visit_expression(&bound.node, &bound.span);
+    changes.visit_statement(&f.statement.node, &f.statement.span);
+    if changes.changed {
+        return None;
+    }
+
+    let initial_value = integer(initial, constants);
+    let bound_value = integer(bound, constants);
+    if let Some((min, max)) = range {
+        for value in initial_value.iter().chain(bound_value.iter()) {
+            if *value < min || *value > max {
+                return None;
+            }
+        }
+    }
+
+    let trip_count = match (initial_value, bound_value) {
+        (Some(a), Some(b)) => {
+            let (distance, magnitude) = if step > 0 {
+                (b - a, step as i128)
+            } else {
+                (a - b, -(step as i128))
+            };
+            let count = match (distance < 0, inclusive) {
+                (true, _) => 0,
+                (false, true) => distance / magnitude + 1,
+                (false, false) => (distance + magnitude - 1) / magnitude,
+            };
+            if let Some((min, max)) = range {
+                let last = a + count * step as i128;
+                if last < min || last > max {
+                    return None;
+                }
+            }
+            Some(count as u64)
+        }
+        _ => None,
+    };
+
+    Some(Induction {
+        variable,
+        initial,
+        bound,
+        inclusive,
+        step,
+        trip_count,
+    })
+}
+
+/// Constant change of `variable` made by the third clause of a `for` loop
+fn step<F>(variable: &str, e: &Node<Expression>, constants: &F) -> Option<i64>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    let step = match e.node {
+        Expression::UnaryOperator(ref u) if identifier(&u.node.operand) == Some(variable) => {
+            match u.node.operator.node {
+                UnaryOperator::PreIncrement | UnaryOperator::PostIncrement => 1,
+                UnaryOperator::PreDecrement | UnaryOperator::PostDecrement => -1,
+                _ => return None,
+            }
+        }
+        Expression::BinaryOperator(ref b) if identifier(&b.node.lhs) == Some(variable) => {
+            match b.node.operator.node {
+                BinaryOperator::AssignPlus => constant(&b.node.rhs, constants)?,
+                BinaryOperator::AssignMinus => constant(&b.node.rhs, constants)?.checked_neg()?,
+                BinaryOperator::Assign => match b.node.rhs.node {
+                    Expression::BinaryOperator(ref r) => {
+                        let r = &r.node;
+                        let lhs = identifier(&r.lhs) == Some(variable);
+                        let rhs = identifier(&r.rhs) == Some(variable);
+                        match r.operator.node {
+                            BinaryOperator::Plus if lhs => constant(&r.rhs, constants)?,
+                            BinaryOperator::Plus if rhs => constant(&r.lhs, constants)?,
+                            BinaryOperator::Minus if lhs => {
+                                constant(&r.rhs, constants)?.checked_neg()?
+                            }
+                            _ => return None,
+                        }
+                    }
+                    _ => return None,
+                },
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    if step == 0 {
+        None
+    } else {
+        Some(step)
+    }
+}
+
+/// Values of a variable declared with `specifiers`, if it has an integer type
+fn range(specifiers: &[Node<DeclarationSpecifier>]) -> Option<(i128, i128)> {
+    let (mut width, mut unsigned, mut long) = (32, false, false);
+    for specifier in specifiers {
+        let t = match specifier.node {
+            DeclarationSpecifier::TypeSpecifier(ref t) => &t.node,
+            _ => continue,
+        };
+        match *t {
+            TypeSpecifier::Char => width = 8,
+            TypeSpecifier::Short => width = 16,
+            TypeSpecifier::Int | TypeSpecifier::Signed => {}
+            TypeSpecifier::Long => long = true,
+            TypeSpecifier::Unsigned => unsigned = true,
+            TypeSpecifier::Bool => return Some((0, 1)),
+            _ => return None,
+        }
+    }
+    if long {
+        width = 64;
+    }
+    if unsigned {
+        Some((0, (1 << width) - 1))
+    } else {
+        Some((-(1 << (width - 1)), (1 << (width - 1)) - 1))
+    }
+}
+
+fn identifier(e: &Node<Expression>) -> Option<&str> {
+    match e.node {
+        Expression::Identifier(ref i) => Some(&i.node.name),
+        _ => None,
+    }
+}
+
+fn constant<F>(e: &Node<Expression>, constants: &F) -> Option<i64>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    match integer(e, constants) {
+        Some(i) if i >= std::i64::MIN as i128 && i <= std::i64::MAX as i128 => Some(i as i64),
+        _ => None,
+    }
+}
+
+fn integer<F>(e: &Node<Expression>, constants: &F) -> Option<i128>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    match eval(e, constants) {
+        Ok(v) => v.integer(),
+        Err(_) => None,
+    }
+}
+
+struct Collector<'ast, 'a, F: 'a> {
+    loops: Vec<Loop<'ast>>,
+    constants: &'a F,
+}
+
+impl<'ast, 'a, F> Visit<'ast> for Collector<'ast, 'a, F>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        if let Statement::For(ref f) = *statement {
+            self.loops.push(Loop {
+                statement: &f.node,
+                span: f.span,
+                induction: induction(&f.node, self.constants),
+            });
+        }
+        visit::visit_statement(self, statement, span);
+    }
+}
+
+/// Identifiers used in an expression
+#[derive(Default)]
+struct Names<'ast>(HashSet<&'ast str>);
+
+impl<'ast> Visit<'ast> for Names<'ast> {
+    fn visit_expression(&mut self, expression: &'ast Expression, span: &'ast Span) {
+        if let Expression::Identifier(ref i) = *expression {
+            self.0.insert(&i.node.name);
+        }
+        visit::visit_expression(self, expression, span);
+    }
+}
+
+/// Check for assignments to, increments of and addresses taken of any of a set of variables
+struct Changes<'a, 'ast: 'a> {
+    names: &'a HashSet<&'ast str>,
+    changed: bool,
+}
+
+impl<'a, 'ast> Changes<'a, 'ast> {
+    fn check(&mut self, e: &Node<Expression>) {
+        if let Some(name) = identifier(e) {
+            if self.names.contains(name) {
+                self.changed = true;
+            }
+        }
+    }
+}
+
+impl<'a, 'ast> Visit<'ast> for Changes<'a, 'ast> {
+    fn visit_unary_operator_expression(
+        &mut self,
+        u: &'ast UnaryOperatorExpression,
+        span: &'ast Span,
+    ) {
+        match u.operator.node {
+            UnaryOperator::PreIncrement
+            | UnaryOperator::PostIncrement
+            | UnaryOperator::PreDecrement
+            | UnaryOperator::PostDecrement
+            | UnaryOperator::Address => self.check(&u.operand),
+            _ => {}
+        }
+        visit::visit_unary_operator_expression(self, u, span);
+    }
+
+    fn visit_binary_operator_expression(
+        &mut self,
+        b: &'ast BinaryOperatorExpression,
+        span: &'ast Span,
+    ) {
+        match b.operator.node {
+            BinaryOperator::Assign
+            | BinaryOperator::AssignMultiply
+            | BinaryOperator::AssignDivide
+            | BinaryOperator::AssignModulo
+            | BinaryOperator::AssignPlus
+            | BinaryOperator::AssignMinus
+            | BinaryOperator::AssignShiftLeft
+            | BinaryOperator::AssignShiftRight
+            | BinaryOperator::AssignBitwiseAnd
+            | BinaryOperator::AssignBitwiseXor
+            | BinaryOperator::AssignBitwiseOr => self.check(&b.lhs),
+            _ => {}
+        }
+        visit::visit_binary_operator_expression(self, b, span);
+    }
+
+    fn visit_gnu_extended_asm_statement(
+        &mut self,
+        s: &'ast GnuExtendedAsmStatement,
+        span: &'ast Span,
+    ) {
+        for output in &s.outputs {
+            self.check(&output.node.variable_name);
+        }
+        visit::visit_gnu_extended_asm_statement(self, s, span);
+    }
+}
@@ -0,0 +1,212 @@
+//! Source to source transformations
+//!
+//! A `Pipeline` parses a file, runs a sequence of passes over its syntax tree and prints the
+//! result back as C source with `unparse`. Each pass receives the tree and a `Context` with the
+//! parser configuration, the original source and the diagnostics reported so far. Passes that
+//! need semantic information build it from the tree with the analysis modules of this crate,
+//! such as `index`, `typedeps` and `cfg`.
+//!
+//! ```
+//! use lang_c::ast::{ExternalDeclaration, TranslationUnit};
+//! use lang_c::driver::Config;
+//! use lang_c::pipeline::{Context, Pass, Pipeline, Severity};
+//!
+//! struct DropStaticAsserts;
+//!
+//! impl Pass for DropStaticAsserts {
+//!     fn name(&self) -> &str {
+//!         "drop-static-asserts"
+//!     }
+//!
+//!     fn run(&mut self, unit: &mut TranslationUnit, context: &mut Context) {
+//!         for external in &unit.0 {
+//!             if let ExternalDeclaration::StaticAssert(_) = external.node {
+//!                 context.report(Severity::Warning, external.span, "static assertion removed");
+//!             }
+//!         }
+//!         unit.0.retain(|e| match e.node {
+//!             ExternalDeclaration::StaticAssert(_) => false,
+//!             _ => true,
+//!         });
+//!     }
+//! }
+//!
+//! let mut pipeline = Pipeline::new(Config::default());
+//! pipeline.add(DropStaticAsserts);
+//! let source = "_Static_assert(1, \"one\");\nint x;\n";
+//! let output = pipeline.run_preprocessed(source.into()).unwrap();
+//! assert_eq!(output.source, "int x;\n");
+//! assert_eq!(output.diagnostics[0].pass, "drop-static-asserts");
+//! assert_eq!(output.diagnostics[0].span.start, 0);
+//! ```
+//!
+//! A pass that reports an error stops the pipeline, which returns the diagnostics instead of the
+//! printed source.
+
+use std::error;
+use std::fmt;
+use std::path::Path;
+
+use ast::TranslationUnit;
+use driver::{self, parse, parse_preprocessed, Config, Parse};
+use span::Span;
+use unparse::unparse;
+
+/// Transformation of a translation unit
+pub trait Pass {
+    /// Name of the pass, recorded in its diagnostics
+    fn name(&self) -> &str;
+
+    /// Transform the translation unit in place
+    fn run(&mut self, unit: &mut TranslationUnit, context: &mut Context);
+}
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Note,
+    Warning,
+    /// Stops the pipeline after the pass that reported it
+    Error,
+}
+
+/// Message reported by a pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Name of the pass that reported the message
+    pub pass: String,
+    pub severity: Severity,
+    pub message: String,
+    /// Location in the original source, `Span::none()` for nodes created by passes
+    pub span: Span,
+}
+
+/// State shared by the passes of a pipeline
+pub struct Context<'a> {
+    /// Configuration the source was parsed with, and is printed with
+    pub config: &'a Config,
+    /// Preprocessed source the translation unit was parsed from
+    pub source: &'a str,
+    /// Diagnostics reported by all passes so far
+    pub diagnostics: Vec<Diagnostic>,
+    pass: String,
+}
+
+impl<'a> Context<'a> {
+    /// Report a diagnostic for the running pass
+    pub fn report<S: Into<String>>(&mut self, severity: Severity, span: Span, message: S) {
+        self.diagnostics.push(Diagnostic {
+            pass: self.pass.clone(),
+            severity,
+            message: message.into(),
+            span,
+        });
+    }
+}
+
+/// Result of running a pipeline
+#[derive(Debug, Clone)]
+pub struct Output {
+    /// Transformed translation unit
+    pub unit: TranslationUnit,
+    /// Transformed translation unit printed as C source
+    pub source: String,
+    /// Diagnostics reported by all passes
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Error type returned from `Pipeline::run`
+#[derive(Debug)]
+pub enum PipelineError {
+    /// The input could not be preprocessed or parsed
+    Parse(driver::Error),
+    /// A pass reported an error, with all diagnostics reported until then
+    Pass(Vec<Diagnostic>),
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PipelineError::Parse(e) => write!(fmt, "{}", e),
+            PipelineError::Pass(diagnostics) => {
+                match diagnostics.iter().find(|d| d.severity == Severity::Error) {
+                    Some(d) => write!(fmt, "{}: {}", d.pass, d.message),
+                    None => write!(fmt, "pass failed"),
+                }
+            }
+        }
+    }
+}
+
+impl error::Error for PipelineError {
+    fn description(&self) -> &str {
+        match *self {
+            PipelineError::Parse(_) => "parse error",
+            PipelineError::Pass(_) => "pass error",
+        }
+    }
+}
+
+/// Sequence of passes run between parsing and printing
+pub struct Pipeline {
+    config: Config,
+    passes: Vec<Box<dyn Pass>>,
+}
+
+impl Pipeline {
+    /// Create a pipeline without passes, which parses and prints with `config`
+    pub fn new(config: Config) -> Pipeline {
+        Pipeline {
+            config,
+            passes: Vec::new(),
+        }
+    }
+
+    /// Append a pass, passes run in the order they were added
+    pub fn add<P: Pass + 'static>(&mut self, pass: P) -> &mut Pipeline {
+        self.passes.push(Box::new(pass));
+        self
+    }
+
+    /// Parse a C file, preprocessing it as `Config::input` says, and run the passes
+    pub fn run<P: AsRef<Path>>(&mut self, path: P) -> Result<Output, PipelineError> {
+        match parse(&self.config, path) {
+            Ok(parse) => self.transform(parse),
+            Err(e) => Err(PipelineError::Parse(e)),
+        }
+    }
+
+    /// Parse preprocessed source and run the passes
+    pub fn run_preprocessed(&mut self, source: String) -> Result<Output, PipelineError> {
+        match parse_preprocessed(&self.config, source) {
+            Ok(parse) => self.transform(parse),
+            Err(e) => Err(PipelineError::Parse(e.into())),
+        }
+    }
+
+    fn transform(&mut self, parse: Parse) -> Result<Output, PipelineError> {
+        let mut unit = parse.unit;
+        let mut context = Context {
+            config: &self.config,
+            source: &parse.source,
+            diagnostics: Vec::new(),
+            pass: String::new(),
+        };
+        for pass in &mut self.passes {
+            context.pass = pass.name().to_string();
+            pass.run(&mut unit, &mut context);
+            if context
+                .diagnostics
+                .iter()
+                .any(|d| d.severity == Severity::Error)
+            {
+                return Err(PipelineError::Pass(context.diagnostics));
+            }
+        }
+        Ok(Output {
+            source: unparse(&self.config, &unit),
+            unit,
+            diagnostics: context.diagnostics,
+        })
+    }
+}
@@ -44,7 +44,19 @@ impl<'a> Printer<'a> {
     }
 
     fn write_field(&mut self, f: &fmt::Debug) {
-        write!(&mut self.w, " {:?}", f).unwrap();
+        // Rust before 1.53 escapes single quotes in strings, print them the same way everywhere
+        let text = format!(" {:?}", f);
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            match (c, if c == '\\' { chars.next() } else { None }) {
+                ('\\', Some('\'')) => self.w.write_char('\'').unwrap(),
+                (c, Some(escaped)) => {
+                    self.w.write_char(c).unwrap();
+                    self.w.write_char(escaped).unwrap();
+                }
+                (c, None) => self.w.write_char(c).unwrap(),
+            }
+        }
     }
 }
 
@@ -56,6 +68,11 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
     }
     fn visit_constant(&mut self, n: &'ast Constant, span: &'ast Span) {
         self.name("Constant");
+        match *n {
+            Constant::Nullptr => self.w.write_str(" Nullptr").unwrap(),
+            Constant::Bool(b) => write!(&mut self.w, " Bool {}", b).unwrap(),
+            _ => {}
+        }
         visit_constant(&mut self.block(), n, span);
     }
     fn visit_integer(&mut self, n: &'ast Integer, span: &'ast Span) {
@@ -99,6 +116,16 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         print_float_format(self, n);
         visit_float_format(&mut self.block(), n, span);
     }
+    fn visit_character(&mut self, n: &'ast Character, span: &'ast Span) {
+        self.name("Character");
+        self.write_field(&n.body);
+        visit_character(&mut self.block(), n, span);
+    }
+    fn visit_character_encoding(&mut self, n: &'ast CharacterEncoding, span: &'ast Span) {
+        self.name("CharacterEncoding");
+        self.write_field(&n);
+        visit_character_encoding(&mut self.block(), n, span);
+    }
     fn visit_string_literal(&mut self, n: &'ast StringLiteral, span: &'ast Span) {
         self.name("StringLiteral");
         self.write_field(&n);
@@ -106,6 +133,7 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
     }
     fn visit_expression(&mut self, n: &'ast Expression, span: &'ast Span) {
         self.name("Expression");
+        print_expression(self, n);
         visit_expression(&mut self.block(), n, span);
     }
     fn visit_member_operator(&mut self, n: &'ast MemberOperator, span: &'ast Span) {
@@ -179,6 +207,18 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         self.name("VaArgExpression");
         visit_va_arg_expression(&mut self.block(), n, span);
     }
+    fn visit_types_compatible_expression(
+        &mut self,
+        n: &'ast TypesCompatibleExpression,
+        span: &'ast Span,
+    ) {
+        self.name("TypesCompatibleExpression");
+        visit_types_compatible_expression(&mut self.block(), n, span);
+    }
+    fn visit_choose_expression(&mut self, n: &'ast ChooseExpression, span: &'ast Span) {
+        self.name("ChooseExpression");
+        visit_choose_expression(&mut self.block(), n, span);
+    }
     fn visit_offset_of_expression(&mut self, n: &'ast OffsetOfExpression, span: &'ast Span) {
         self.name("OffsetOfExpression");
         visit_offset_of_expression(&mut self.block(), n, span);
@@ -343,6 +383,10 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         self.name("LabeledStatement");
         visit_labeled_statement(&mut self.block(), n, span);
     }
+    fn visit_attributed_statement(&mut self, n: &'ast AttributedStatement, span: &'ast Span) {
+        self.name("AttributedStatement");
+        visit_attributed_statement(&mut self.block(), n, span);
+    }
     fn visit_if_statement(&mut self, n: &'ast IfStatement, span: &'ast Span) {
         self.name("IfStatement");
         visit_if_statement(&mut self.block(), n, span);
@@ -375,6 +419,7 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
     }
     fn visit_block_item(&mut self, n: &'ast BlockItem, span: &'ast Span) {
         self.name("BlockItem");
+        print_block_item(self, n);
         visit_block_item(&mut self.block(), n, span);
     }
     fn visit_external_declaration(&mut self, n: &'ast ExternalDeclaration, span: &'ast Span) {
@@ -385,8 +430,18 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         self.name("FunctionDefinition");
         visit_function_definition(&mut self.block(), n, span);
     }
+    fn visit_linkage_specification(&mut self, n: &'ast LinkageSpecification, span: &'ast Span) {
+        self.name("LinkageSpecification");
+        self.write_field(&n.braced);
+        visit_linkage_specification(&mut self.block(), n, span);
+    }
     fn visit_extension(&mut self, n: &'ast Extension, span: &'ast Span) {
         self.name("Extension");
+        match *n {
+            Extension::Marker => self.w.write_str(" Marker").unwrap(),
+            Extension::AsmRegister(_) => self.w.write_str(" AsmRegister").unwrap(),
+            _ => {}
+        }
         visit_extension(&mut self.block(), n, span);
     }
     fn visit_attribute(&mut self, n: &'ast Attribute, span: &'ast Span) {
@@ -394,6 +449,14 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         self.write_field(&n.name.node);
         visit_attribute(&mut self.block(), n, span);
     }
+    fn visit_standard_attribute(&mut self, n: &'ast StandardAttribute, span: &'ast Span) {
+        self.name("StandardAttribute");
+        match n.prefix {
+            Some(ref prefix) => self.write_field(&format!("{}::{}", prefix.node, n.name.node)),
+            None => self.write_field(&n.name.node),
+        }
+        visit_standard_attribute(&mut self.block(), n, span);
+    }
     fn visit_asm_statement(&mut self, n: &'ast AsmStatement, span: &'ast Span) {
         self.name("AsmStatement");
         visit_asm_statement(&mut self.block(), n, span);
@@ -408,6 +471,7 @@ impl<'ast, 'a> Visit<'ast> for Printer<'a> {
         span: &'ast Span,
     ) {
         self.name("GnuExtendedAsmStatement");
+        self.write_field(&n.goto);
         visit_gnu_extended_asm_statement(&mut self.block(), n, span);
     }
     fn visit_gnu_asm_operand(&mut self, n: &'ast GnuAsmOperand, span: &'ast Span) {
@@ -446,10 +510,21 @@ fn print_array_size<'ast>(p: &mut Printer, n: &'ast ArraySize) {
         ArraySize::StaticExpression(_) => p.w.write_str(" StaticExpression").unwrap(),
     }
 }
+fn print_block_item(p: &mut Printer, n: &BlockItem) {
+    if let BlockItem::LocalLabels(_) = *n {
+        p.w.write_str(" LocalLabels").unwrap();
+    }
+}
+fn print_expression(p: &mut Printer, n: &Expression) {
+    if let Expression::LabelAddress(_) = *n {
+        p.w.write_str(" LabelAddress").unwrap();
+    }
+}
 fn print_statement<'ast>(p: &mut Printer, n: &'ast Statement) {
     match *n {
         Statement::Compound(_) => p.w.write_str(" Compound").unwrap(),
         Statement::Goto(_) => p.w.write_str(" Goto").unwrap(),
+        Statement::GotoIndirect(_) => p.w.write_str(" GotoIndirect").unwrap(),
         Statement::Continue => p.w.write_str(" Continue").unwrap(),
         Statement::Break => p.w.write_str(" Break").unwrap(),
         Statement::Return(_) => p.w.write_str(" Return").unwrap(),
@@ -486,9 +561,15 @@ fn print_type_specifier<'ast>(p: &mut Printer, n: &'ast TypeSpecifier) {
         TypeSpecifier::Double => p.w.write_str(" Double").unwrap(),
         TypeSpecifier::Signed => p.w.write_str(" Signed").unwrap(),
         TypeSpecifier::Unsigned => p.w.write_str(" Unsigned").unwrap(),
+        TypeSpecifier::Bool => p.w.write_str(" Bool").unwrap(),
         TypeSpecifier::Complex => p.w.write_str(" Complex").unwrap(),
         TypeSpecifier::Atomic(_) => p.w.write_str(" Atomic").unwrap(),
         TypeSpecifier::TypedefName(_) => p.w.write_str(" TypedefName").unwrap(),
+        TypeSpecifier::BitInt(_) => p.w.write_str(" BitInt").unwrap(),
+        TypeSpecifier::Int128 => p.w.write_str(" Int128").unwrap(),
+        TypeSpecifier::Float128 => p.w.write_str(" Float128").unwrap(),
+        TypeSpecifier::AutoType => p.w.write_str(" AutoType").unwrap(),
+        TypeSpecifier::TypeOfUnqual(_) => p.w.write_str(" TypeOfUnqual").unwrap(),
         _ => {}
     }
 }
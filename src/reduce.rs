@@ -0,0 +1,166 @@
+//! Test case reduction
+//!
+//! `reduce` shrinks a translation unit while it stays interesting to the caller, for example
+//! while a tool still crashes on it. Candidates are made by removing external declarations and
+//! block items from the syntax tree and are printed with the `unparse` module, so every candidate
+//! the predicate sees is well-formed C syntax.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::reduce::reduce;
+//!
+//! let config = Config::default();
+//! let source = r#"
+//!     int unused;
+//!     int helper(int x) { return x + 1; }
+//!     void f(void) { int a = helper(1); if (a) { a++; crash(); } a--; }
+//! "#;
+//! let parse = parse_preprocessed(&config, source.into()).unwrap();
+//!
+//! let reduction = reduce(&config, &parse.unit, |source| source.contains("crash"));
+//! assert_eq!(
+//!     reduction.source,
+//!     "void f(void) {\n    if (a) {\n        crash();\n    }\n}\n"
+//! );
+//! ```
+//!
+//! Items are removed in chunks that halve in size down to single items, as in delta debugging,
+//! and the passes repeat until no single item can be removed. The predicate is the only judge of
+//! a candidate: removing a declaration often leaves uses of an undeclared name behind, and a
+//! predicate that needs valid programs should check that itself.
+
+use std::mem;
+
+use ast::*;
+use driver::Config;
+use span::Node;
+use unparse::unparse;
+
+/// Result of a reduction
+#[derive(Debug, Clone)]
+pub struct Reduction {
+    /// Smallest interesting translation unit found
+    pub unit: TranslationUnit,
+    /// `unit` printed as C source
+    pub source: String,
+    /// Number of times the predicate was called
+    pub tests: usize,
+}
+
+/// Remove declarations and statements from `unit` while `interesting` holds for the printed
+/// source
+///
+/// `unit` itself is not tested and is returned unchanged if no smaller candidate is interesting.
+pub fn reduce<F>(config: &Config, unit: &TranslationUnit, mut interesting: F) -> Reduction
+where
+    F: FnMut(&str) -> bool,
+{
+    let mut current = unit.clone();
+    let mut tests = 0;
+    loop {
+        let mut changed = false;
+        let mut items = count(&current);
+        let mut chunk = items;
+        while chunk > 0 {
+            let mut start = 0;
+            while start < items {
+                let mut candidate = current.clone();
+                retain(&mut candidate, &mut |i| i < start || i >= start + chunk);
+                tests += 1;
+                if interesting(&unparse(config, &candidate)) {
+                    current = candidate;
+                    items = count(&current);
+                    changed = true;
+                } else {
+                    start += chunk;
+                }
+            }
+            chunk /= 2;
+        }
+        if !changed {
+            break;
+        }
+    }
+    Reduction {
+        source: unparse(config, &current),
+        unit: current,
+        tests,
+    }
+}
+
+/// Number of removable items
+fn count(unit: &TranslationUnit) -> usize {
+    let mut unit = unit.clone();
+    let mut n = 0;
+    retain(&mut unit, &mut |_| {
+        n += 1;
+        true
+    });
+    n
+}
+
+/// Keep the removable items for which `keep` returns true
+///
+/// Items are numbered in source order, each before the items nested in it. Items nested in a
+/// removed item are not numbered.
+fn retain<F: FnMut(usize) -> bool>(unit: &mut TranslationUnit, keep: &mut F) {
+    externals(&mut unit.0, keep, &mut 0);
+}
+
+fn externals<F: FnMut(usize) -> bool>(
+    externals: &mut Vec<Node<ExternalDeclaration>>,
+    keep: &mut F,
+    index: &mut usize,
+) {
+    for mut item in mem::replace(externals, Vec::new()) {
+        *index += 1;
+        if !keep(*index - 1) {
+            continue;
+        }
+        match item.node {
+            ExternalDeclaration::FunctionDefinition(ref mut f) => {
+                statement(&mut f.node.statement, keep, index)
+            }
+            ExternalDeclaration::LinkageSpecification(ref mut l) if l.node.braced => {
+                self::externals(&mut l.node.declarations, keep, index)
+            }
+            _ => {}
+        }
+        externals.push(item);
+    }
+}
+
+fn statement<F: FnMut(usize) -> bool>(
+    statement: &mut Node<Statement>,
+    keep: &mut F,
+    index: &mut usize,
+) {
+    match statement.node {
+        Statement::Compound(ref mut block) => {
+            let items = mem::replace(block, Vec::new());
+            for mut item in items {
+                *index += 1;
+                if !keep(*index - 1) {
+                    continue;
+                }
+                if let BlockItem::Statement(ref mut s) = item.node {
+                    self::statement(s, keep, index);
+                }
+                block.push(item);
+            }
+        }
+        Statement::Labeled(ref mut s) => self::statement(&mut s.node.statement, keep, index),
+        Statement::Attributed(ref mut s) => self::statement(&mut s.node.statement, keep, index),
+        Statement::If(ref mut s) => {
+            self::statement(&mut s.node.then_statement, keep, index);
+            if let Some(ref mut e) = s.node.else_statement {
+                self::statement(e, keep, index);
+            }
+        }
+        Statement::Switch(ref mut s) => self::statement(&mut s.node.statement, keep, index),
+        Statement::While(ref mut s) => self::statement(&mut s.node.statement, keep, index),
+        Statement::DoWhile(ref mut s) => self::statement(&mut s.node.statement, keep, index),
+        Statement::For(ref mut s) => self::statement(&mut s.node.statement, keep, index),
+        _ => {}
+    }
+}
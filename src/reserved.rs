@@ -0,0 +1,358 @@
+//! Reserved identifiers
+//!
+//! C11 7.1.3 reserves some identifiers for the implementation: names starting with an
+//! underscore and an uppercase letter or with two underscores for any use, other names starting
+//! with an underscore at file scope, and the names declared by the standard headers a program
+//! includes. Programs that declare them can break with another compiler or C library. This module
+//! finds such declarations in user code and reports them with configurable severities, the same
+//! ones `pipeline` diagnostics use.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::pipeline::Severity;
+//! use lang_c::reserved::{check, Reservation, Severities};
+//!
+//! let source = concat!(
+//!     "# 1 \"main.c\"\n",
+//!     "# 1 \"/usr/include/stdio.h\" 1 3 4\n",
+//!     "struct _IO_FILE;\n",
+//!     "int puts(const char *__s);\n",
+//!     "# 2 \"main.c\" 2\n",
+//!     "int puts(const char *s);\n",
+//!     "static int _count;\n",
+//!     "struct __list { struct __list *_Next; };\n",
+//!     "int puts(const char *s) { return _count++; }\n",
+//! );
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//!
+//! let mut severities = Severities::new();
+//! severities.set(Reservation::DoubleUnderscore, Severity::Error);
+//! let found: Vec<_> = check(&parse.unit, &parse.includes(), &severities)
+//!     .into_iter()
+//!     .map(|f| (f.name, f.reservation, f.severity))
+//!     .collect();
+//! assert_eq!(
+//!     found,
+//!     [
+//!         ("_count".to_string(), Reservation::FileScopeUnderscore, Severity::Warning),
+//!         ("__list".to_string(), Reservation::DoubleUnderscore, Severity::Error),
+//!         ("_Next".to_string(), Reservation::UnderscoreUppercase, Severity::Warning),
+//!         ("puts".to_string(), Reservation::Library, Severity::Warning),
+//!     ]
+//! );
+//! ```
+//!
+//! Declarations are checked where they start, so only text that comes from user files according
+//! to the include graph is reported. Library names are the identifiers and tags that system
+//! headers declare at file scope; redeclaring a library function or an `extern` object is
+//! allowed, defining one is not. Tags are checked where the struct, union or enum is defined.
+//! Macro names are not checked, since macros do not survive preprocessing.
+
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use ast::*;
+use includes::{IncludeGraph, Origin};
+use pipeline::Severity;
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// Rule reserving an identifier (C11 7.1.3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Reservation {
+    /// Starts with an underscore and an uppercase letter, reserved for any use
+    UnderscoreUppercase,
+    /// Starts with two underscores, reserved for any use
+    DoubleUnderscore,
+    /// Starts with an underscore, reserved for ordinary identifiers and tags at file scope
+    FileScopeUnderscore,
+    /// Declared at file scope by an included system header
+    Library,
+}
+
+/// Severity of each reservation to check
+///
+/// All reservations are checked as warnings by default.
+#[derive(Debug, Clone)]
+pub struct Severities {
+    severities: HashMap<Reservation, Severity>,
+}
+
+impl Severities {
+    /// Check all reservations as warnings
+    pub fn new() -> Severities {
+        let all = [
+            Reservation::UnderscoreUppercase,
+            Reservation::DoubleUnderscore,
+            Reservation::FileScopeUnderscore,
+            Reservation::Library,
+        ];
+        Severities {
+            severities: all.iter().map(|&r| (r, Severity::Warning)).collect(),
+        }
+    }
+
+    /// Severity of a reservation, `None` if it is not checked
+    pub fn get(&self, reservation: Reservation) -> Option<Severity> {
+        self.severities.get(&reservation).cloned()
+    }
+
+    pub fn set(&mut self, reservation: Reservation, severity: Severity) {
+        self.severities.insert(reservation, severity);
+    }
+
+    /// Stop checking a reservation
+    pub fn remove(&mut self, reservation: Reservation) {
+        self.severities.remove(&reservation);
+    }
+}
+
+impl Default for Severities {
+    fn default() -> Severities {
+        Severities::new()
+    }
+}
+
+/// Declaration of a reserved identifier
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub name: String,
+    pub reservation: Reservation,
+    pub severity: Severity,
+    /// Declared identifier
+    pub span: Span,
+}
+
+/// Declarations of reserved identifiers in user code, in source order
+///
+/// An identifier reserved by several rules is reported once, for the first checked one in the
+/// order of `Reservation`.
+pub fn check(
+    unit: &TranslationUnit,
+    graph: &IncludeGraph,
+    severities: &Severities,
+) -> Vec<Finding> {
+    let mut declarations = Declarations {
+        declarations: Vec::new(),
+        blocks: 0,
+        external: false,
+    };
+    declarations.visit_translation_unit(unit);
+    let declarations = declarations.declarations;
+
+    let library: HashSet<&str> = declarations
+        .iter()
+        .filter(|d| d.file_scope && d.kind != Kind::Member && d.kind != Kind::Label)
+        .filter(|d| graph.origin(d.identifier.span.start) == Origin::System)
+        .map(|d| &*d.identifier.node.name)
+        .collect();
+
+    let mut findings = Vec::new();
+    for d in &declarations {
+        if graph.origin(d.identifier.span.start) != Origin::User {
+            continue;
+        }
+        let name = &*d.identifier.node.name;
+        let mut chars = name.chars();
+        let (first, second) = (chars.next(), chars.next());
+        let uppercase = match second {
+            Some(c) => c.is_ascii_uppercase(),
+            None => false,
+        };
+        let ordinary = d.file_scope && d.kind != Kind::Member && d.kind != Kind::Label;
+        let reservations = [
+            (
+                Reservation::UnderscoreUppercase,
+                first == Some('_') && uppercase,
+            ),
+            (Reservation::DoubleUnderscore, name.starts_with("__")),
+            (
+                Reservation::FileScopeUnderscore,
+                ordinary && first == Some('_'),
+            ),
+            (
+                Reservation::Library,
+                ordinary && !d.redeclaration && library.contains(name),
+            ),
+        ];
+        let found = reservations
+            .iter()
+            .filter(|&&(_, applies)| applies)
+            .filter_map(|&(r, _)| severities.get(r).map(|s| (r, s)))
+            .next();
+        if let Some((reservation, severity)) = found {
+            findings.push(Finding {
+                name: name.to_owned(),
+                reservation,
+                severity,
+                span: d.identifier.span,
+            });
+        }
+    }
+    findings.sort_by_key(|f| f.span.start);
+    findings
+}
+
+/// Name space of a declared identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// Object, function, typedef name or enumeration constant
+    Ordinary,
+    Tag,
+    Member,
+    Label,
+}
+
+struct Declared<'ast> {
+    identifier: &'ast Node<Identifier>,
+    kind: Kind,
+    file_scope: bool,
+    /// Declaration of a function without a body or of an `extern` object
+    redeclaration: bool,
+}
+
+struct Declarations<'ast> {
+    declarations: Vec<Declared<'ast>>,
+    /// Function bodies and parameter lists being visited
+    blocks: usize,
+    /// Declaration being visited has the `extern` storage class
+    external: bool,
+}
+
+impl<'ast> Declarations<'ast> {
+    fn add(&mut self, identifier: &'ast Node<Identifier>, kind: Kind, redeclaration: bool) {
+        self.declarations.push(Declared {
+            identifier,
+            kind,
+            file_scope: self.blocks == 0,
+            redeclaration,
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for Declarations<'ast> {
+    fn visit_function_definition(&mut self, f: &'ast FunctionDefinition, _: &'ast Span) {
+        if let Some(i) = identifier(&f.declarator.node) {
+            self.add(i, Kind::Ordinary, false);
+        }
+        for specifier in &f.specifiers {
+            self.visit_declaration_specifier(&specifier.node, &specifier.span);
+        }
+        self.visit_declarator(&f.declarator.node, &f.declarator.span);
+        self.blocks += 1;
+        for declaration in &f.declarations {
+            self.visit_declaration(&declaration.node, &declaration.span);
+        }
+        self.visit_statement(&f.statement.node, &f.statement.span);
+        self.blocks -= 1;
+    }
+
+    fn visit_declaration(&mut self, declaration: &'ast Declaration, span: &'ast Span) {
+        let external = declaration.specifiers.iter().any(|s| match s.node {
+            DeclarationSpecifier::StorageClass(ref c) => c.node == StorageClassSpecifier::Extern,
+            _ => false,
+        });
+        let outer = mem::replace(&mut self.external, external);
+        visit::visit_declaration(self, declaration, span);
+        self.external = outer;
+    }
+
+    fn visit_init_declarator(&mut self, init_declarator: &'ast InitDeclarator, span: &'ast Span) {
+        let declarator = &init_declarator.declarator.node;
+        if let Some(i) = identifier(declarator) {
+            let redeclaration = self.external || declares_function(declarator);
+            self.add(i, Kind::Ordinary, redeclaration);
+        }
+        visit::visit_init_declarator(self, init_declarator, span);
+    }
+
+    fn visit_parameter_declaration(
+        &mut self,
+        parameter_declaration: &'ast ParameterDeclaration,
+        span: &'ast Span,
+    ) {
+        if let Some(ref d) = parameter_declaration.declarator {
+            if let Some(i) = identifier(&d.node) {
+                self.add(i, Kind::Ordinary, false);
+            }
+        }
+        visit::visit_parameter_declaration(self, parameter_declaration, span);
+    }
+
+    fn visit_function_declarator(
+        &mut self,
+        function_declarator: &'ast FunctionDeclarator,
+        span: &'ast Span,
+    ) {
+        self.blocks += 1;
+        visit::visit_function_declarator(self, function_declarator, span);
+        self.blocks -= 1;
+    }
+
+    fn visit_struct_declarator(
+        &mut self,
+        struct_declarator: &'ast StructDeclarator,
+        span: &'ast Span,
+    ) {
+        if let Some(ref d) = struct_declarator.declarator {
+            if let Some(i) = identifier(&d.node) {
+                self.add(i, Kind::Member, false);
+            }
+        }
+        visit::visit_struct_declarator(self, struct_declarator, span);
+    }
+
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        if let (Some(i), &Some(_)) = (&struct_type.identifier, &struct_type.declarations) {
+            self.add(i, Kind::Tag, false);
+        }
+        visit::visit_struct_type(self, struct_type, span);
+    }
+
+    fn visit_enum_type(&mut self, enum_type: &'ast EnumType, span: &'ast Span) {
+        if let Some(ref i) = enum_type.identifier {
+            if !enum_type.enumerators.is_empty() {
+                self.add(i, Kind::Tag, false);
+            }
+        }
+        visit::visit_enum_type(self, enum_type, span);
+    }
+
+    fn visit_enumerator(&mut self, enumerator: &'ast Enumerator, span: &'ast Span) {
+        self.add(&enumerator.identifier, Kind::Ordinary, false);
+        visit::visit_enumerator(self, enumerator, span);
+    }
+
+    fn visit_label(&mut self, label: &'ast Label, span: &'ast Span) {
+        if let Label::Identifier(ref i) = *label {
+            self.add(i, Kind::Label, false);
+        }
+        visit::visit_label(self, label, span);
+    }
+}
+
+fn identifier(declarator: &Declarator) -> Option<&Node<Identifier>> {
+    match declarator.kind.node {
+        DeclaratorKind::Abstract => None,
+        DeclaratorKind::Identifier(ref i) => Some(i),
+        DeclaratorKind::Declarator(ref d) => identifier(&d.node),
+    }
+}
+
+/// Whether a declarator declares a function rather than an object
+fn declares_function(declarator: &Declarator) -> bool {
+    if let DeclaratorKind::Declarator(ref inner) = declarator.kind.node {
+        if !inner.node.derived.is_empty() {
+            return declares_function(&inner.node);
+        }
+    }
+    // Suffixes apply before the pointers written in front of the identifier
+    for derived in &declarator.derived {
+        match derived.node {
+            DerivedDeclarator::Function(_) | DerivedDeclarator::KRFunction(_) => return true,
+            DerivedDeclarator::Pointer(_) => continue,
+            DerivedDeclarator::Array(_) => return false,
+        }
+    }
+    false
+}
@@ -0,0 +1,492 @@
+//! Size of syntax tree nodes
+//!
+//! Tools that minimize test cases or report the largest functions of a program need the size of
+//! many subtrees. `Sizes` walks a translation unit once, numbering every node in visiting order,
+//! and answers size queries for any of them in logarithmic time.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::size::{largest_functions, Sizes};
+//!
+//! let source = r#"
+//!     int one(void) { return 1; }
+//!     int twice(int x) { return x * 2 + 1; }
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//! let sizes = Sizes::new(&parse.source, &parse.unit);
+//!
+//! let functions = largest_functions(&sizes, &parse.unit);
+//! assert_eq!(functions.len(), 2);
+//! assert_eq!(functions[0].1.tokens, 15);
+//! assert_eq!(functions[1].1.tokens, 10);
+//!
+//! let body = &functions[0].0.node.statement.node;
+//! let size = sizes.size_of(body).unwrap();
+//! assert_eq!(size.tokens, 9);
+//! assert_eq!(sizes.total().tokens, 25);
+//! ```
+//!
+//! A node is any value passed to a `Visit` hook, so a subtree has one node per line of its `print`
+//! dump. Nodes are identified by address and type, which makes a
+//! table valid only for the tree it was built from; the borrow it holds keeps that tree alive and
+//! unchanged. Tokens are counted in the source text covered by a node's span, after skipping line
+//! directives and comments.
+//!
+//! The parser accepts chains of binary operators of any length, so the walk does not recurse
+//! into subexpressions. `total` counts the nodes of a tree without building the table.
+
+use std::any::{Any, TypeId};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use ast::*;
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// Index of a node in visiting order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(pub usize);
+
+/// Size of a subtree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Size {
+    /// Nodes below the root of the subtree
+    pub nodes: usize,
+    /// Tokens in the source text of the subtree
+    pub tokens: usize,
+}
+
+/// Sizes of all nodes in a translation unit
+#[derive(Debug)]
+pub struct Sizes<'ast> {
+    ids: HashMap<(TypeId, usize), NodeId>,
+    nodes: Vec<Entry>,
+    tokens: Vec<usize>,
+    unit: PhantomData<&'ast TranslationUnit>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    span: Span,
+    /// Identifier of the first node after the subtree
+    end: usize,
+}
+
+impl<'ast> Sizes<'ast> {
+    /// Number the nodes of a translation unit parsed from `source`
+    pub fn new(source: &str, unit: &'ast TranslationUnit) -> Sizes<'ast> {
+        let mut ids = HashMap::new();
+        let mut nodes = Vec::new();
+        let mut open = Vec::new();
+        walk(unit, |step| match step {
+            Step::Enter(key, span) => {
+                let id = nodes.len();
+                ids.insert(key, NodeId(id));
+                nodes.push(Entry { span, end: id + 1 });
+                open.push(id);
+            }
+            Step::Leave => {
+                let id = open.pop().expect("unbalanced walk");
+                nodes[id].end = nodes.len();
+            }
+            Step::Expression(..) => unreachable!(),
+        });
+        Sizes {
+            ids,
+            nodes,
+            tokens: tokens(source, Span::span(0, source.len()))
+                .iter()
+                .map(|t| t.start)
+                .collect(),
+            unit: PhantomData,
+        }
+    }
+
+    /// Number of nodes in the translation unit
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Identifier of a node, given as the value a `Visit` hook receives (`&n.node` for a
+    /// `Node<T>`)
+    pub fn id<T: Any>(&self, node: &T) -> Option<NodeId> {
+        let key = (TypeId::of::<T>(), node as *const T as usize);
+        self.ids.get(&key).cloned()
+    }
+
+    pub fn span(&self, id: NodeId) -> Span {
+        self.nodes[id.0].span
+    }
+
+    /// Nodes of the subtree rooted at `id`, in visiting order and including `id` itself
+    pub fn subtree(&self, id: NodeId) -> Vec<NodeId> {
+        (id.0..self.nodes[id.0].end).map(NodeId).collect()
+    }
+
+    pub fn size(&self, id: NodeId) -> Size {
+        let entry = &self.nodes[id.0];
+        Size {
+            nodes: entry.end - id.0 - 1,
+            tokens: self.count_tokens(entry.span),
+        }
+    }
+
+    /// Size of a node, or `None` if the node is not part of the translation unit
+    pub fn size_of<T: Any>(&self, node: &T) -> Option<Size> {
+        self.id(node).map(|id| self.size(id))
+    }
+
+    /// Size of the whole translation unit
+    pub fn total(&self) -> Size {
+        Size {
+            nodes: self.nodes.len(),
+            tokens: self.tokens.len(),
+        }
+    }
+
+    fn count_tokens(&self, span: Span) -> usize {
+        if span.is_none() {
+            return 0;
+        }
+        let first = first_at(&self.tokens, span.start);
+        let last = first_at(&self.tokens, span.end);
+        last - first
+    }
+}
+
+/// Size of a translation unit parsed from `source`, the same as `Sizes::total`
+///
+/// ```
+/// use lang_c::driver::{parse_preprocessed, Config};
+/// use lang_c::size;
+///
+/// let terms = vec!["1"; 5000].join(" + ");
+/// let source = format!("int x = {};", terms);
+/// let parse = parse_preprocessed(&Config::default(), source).unwrap();
+/// let total = size::total(&parse.source, &parse.unit);
+/// assert_eq!(total.tokens, 10003);
+/// ```
+pub fn total(source: &str, unit: &TranslationUnit) -> Size {
+    let mut nodes = 0;
+    walk(unit, |step| {
+        if let Step::Enter(..) = step {
+            nodes += 1;
+        }
+    });
+    Size {
+        nodes,
+        tokens: tokens(source, Span::span(0, source.len())).len(),
+    }
+}
+
+/// Function definitions with their sizes, the ones with most tokens first
+///
+/// Functions of the same size keep their source order.
+pub fn largest_functions<'ast>(
+    sizes: &Sizes<'ast>,
+    unit: &'ast TranslationUnit,
+) -> Vec<(&'ast Node<FunctionDefinition>, Size)> {
+    let mut functions = Vec::new();
+    function_sizes(sizes, &unit.0, &mut functions);
+    functions.sort_by_key(|f| Reverse(f.1.tokens));
+    functions
+}
+
+fn function_sizes<'ast>(
+    sizes: &Sizes<'ast>,
+    externals: &'ast [Node<ExternalDeclaration>],
+    functions: &mut Vec<(&'ast Node<FunctionDefinition>, Size)>,
+) {
+    for external in externals {
+        match external.node {
+            ExternalDeclaration::FunctionDefinition(ref f) => {
+                if let Some(size) = sizes.size_of(&f.node) {
+                    functions.push((f, size));
+                }
+            }
+            ExternalDeclaration::LinkageSpecification(ref l) => {
+                function_sizes(sizes, &l.node.declarations, functions)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Visit hook call, or an expression whose hooks are yet to be called
+enum Step<'ast> {
+    Enter((TypeId, usize), Span),
+    Leave,
+    Expression(&'ast Expression, &'ast Span),
+}
+
+/// Call `f` with the hooks a visitor of `unit` would run, in the same order
+///
+/// Expressions are visited one at a time and record their subexpressions as `Step::Expression`,
+/// which are expanded in place using a stack instead of recursion.
+fn walk<'ast, F: FnMut(Step<'ast>)>(unit: &'ast TranslationUnit, mut f: F) {
+    let mut recorder = Recorder {
+        steps: Vec::new(),
+        expand: false,
+    };
+    recorder.visit_translation_unit(unit);
+    let mut stack = vec![recorder.steps.split_off(0).into_iter()];
+    while let Some(step) = stack.last_mut().map(|steps| steps.next()) {
+        match step {
+            Some(Step::Expression(expression, span)) => {
+                recorder.expand = true;
+                recorder.visit_expression(expression, span);
+                stack.push(recorder.steps.split_off(0).into_iter());
+            }
+            Some(step) => f(step),
+            None => {
+                stack.pop();
+            }
+        }
+    }
+}
+
+struct Recorder<'ast> {
+    steps: Vec<Step<'ast>>,
+    /// Record the hooks of the next expression instead of deferring it
+    expand: bool,
+}
+
+impl<'ast> Recorder<'ast> {
+    fn enter<T: Any>(&mut self, node: &T, span: &Span) {
+        let key = (TypeId::of::<T>(), node as *const T as usize);
+        self.steps.push(Step::Enter(key, *span));
+    }
+}
+
+macro_rules! recorder {
+    ($($hook:ident: $ty:ty,)* ; $($leaf:ident: $leaf_ty:ty,)*) => {
+        impl<'ast> Visit<'ast> for Recorder<'ast> {
+            fn visit_expression(&mut self, node: &'ast Expression, span: &'ast Span) {
+                if self.expand {
+                    self.expand = false;
+                    self.enter(node, span);
+                    visit::visit_expression(self, node, span);
+                    self.steps.push(Step::Leave);
+                } else {
+                    self.steps.push(Step::Expression(node, span));
+                }
+            }
+            $(
+                fn $hook(&mut self, node: &'ast $ty, span: &'ast Span) {
+                    self.enter(node, span);
+                    visit::$hook(self, node, span);
+                    self.steps.push(Step::Leave);
+                }
+            )*
+            $(
+                fn $leaf(&mut self, node: &'ast $leaf_ty, span: &'ast Span) {
+                    self.enter(node, span);
+                    self.steps.push(Step::Leave);
+                }
+            )*
+        }
+    };
+}
+
+recorder! {
+    visit_identifier: Identifier,
+    visit_constant: Constant,
+    visit_integer: Integer,
+    visit_integer_base: IntegerBase,
+    visit_integer_suffix: IntegerSuffix,
+    visit_integer_size: IntegerSize,
+    visit_float: Float,
+    visit_float_base: FloatBase,
+    visit_float_suffix: FloatSuffix,
+    visit_float_format: FloatFormat,
+    visit_character: Character,
+    visit_character_encoding: CharacterEncoding,
+    visit_string_literal: StringLiteral,
+    visit_member_operator: MemberOperator,
+    visit_generic_selection: GenericSelection,
+    visit_generic_association: GenericAssociation,
+    visit_generic_association_type: GenericAssociationType,
+    visit_member_expression: MemberExpression,
+    visit_call_expression: CallExpression,
+    visit_compound_literal: CompoundLiteral,
+    visit_unary_operator: UnaryOperator,
+    visit_unary_operator_expression: UnaryOperatorExpression,
+    visit_cast_expression: CastExpression,
+    visit_binary_operator: BinaryOperator,
+    visit_binary_operator_expression: BinaryOperatorExpression,
+    visit_conditional_expression: ConditionalExpression,
+    visit_va_arg_expression: VaArgExpression,
+    visit_types_compatible_expression: TypesCompatibleExpression,
+    visit_choose_expression: ChooseExpression,
+    visit_offset_of_expression: OffsetOfExpression,
+    visit_offset_designator: OffsetDesignator,
+    visit_offset_member: OffsetMember,
+    visit_declaration: Declaration,
+    visit_declaration_specifier: DeclarationSpecifier,
+    visit_init_declarator: InitDeclarator,
+    visit_storage_class_specifier: StorageClassSpecifier,
+    visit_type_specifier: TypeSpecifier,
+    visit_ts18661_float_type: TS18661FloatType,
+    visit_ts18661_float_format: TS18661FloatFormat,
+    visit_struct_type: StructType,
+    visit_struct_kind: StructKind,
+    visit_struct_declaration: StructDeclaration,
+    visit_struct_field: StructField,
+    visit_specifier_qualifier: SpecifierQualifier,
+    visit_struct_declarator: StructDeclarator,
+    visit_enum_type: EnumType,
+    visit_enumerator: Enumerator,
+    visit_type_qualifier: TypeQualifier,
+    visit_function_specifier: FunctionSpecifier,
+    visit_alignment_specifier: AlignmentSpecifier,
+    visit_declarator: Declarator,
+    visit_declarator_kind: DeclaratorKind,
+    visit_derived_declarator: DerivedDeclarator,
+    visit_array_declarator: ArrayDeclarator,
+    visit_function_declarator: FunctionDeclarator,
+    visit_pointer_qualifier: PointerQualifier,
+    visit_array_size: ArraySize,
+    visit_parameter_declaration: ParameterDeclaration,
+    visit_ellipsis: Ellipsis,
+    visit_type_name: TypeName,
+    visit_initializer: Initializer,
+    visit_initializer_list_item: InitializerListItem,
+    visit_designator: Designator,
+    visit_range_designator: RangeDesignator,
+    visit_static_assert: StaticAssert,
+    visit_statement: Statement,
+    visit_labeled_statement: LabeledStatement,
+    visit_if_statement: IfStatement,
+    visit_switch_statement: SwitchStatement,
+    visit_while_statement: WhileStatement,
+    visit_do_while_statement: DoWhileStatement,
+    visit_for_statement: ForStatement,
+    visit_label: Label,
+    visit_for_initializer: ForInitializer,
+    visit_block_item: BlockItem,
+    visit_external_declaration: ExternalDeclaration,
+    visit_function_definition: FunctionDefinition,
+    visit_linkage_specification: LinkageSpecification,
+    visit_extension: Extension,
+    visit_attribute: Attribute,
+    visit_standard_attribute: StandardAttribute,
+    visit_asm_statement: AsmStatement,
+    visit_availability_attribute: AvailabilityAttribute,
+    visit_gnu_extended_asm_statement: GnuExtendedAsmStatement,
+    visit_gnu_asm_operand: GnuAsmOperand,
+    visit_type_of: TypeOf,
+    ;
+    visit_availability_clause: AvailabilityClause,
+}
+
+/// Tokens of preprocessed source text that start within `span`, in order
+///
+/// Line directives and comments are skipped. Tokens are found without classifying them, so
+/// keywords are identifiers and numbers are C preprocessing numbers.
+pub fn tokens(source: &str, span: Span) -> Vec<Span> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = span.start;
+    let mut line_start = i == 0 || bytes[i - 1] == b'\n';
+    while i < span.end {
+        match bytes[i] {
+            b'\n' => {
+                line_start = true;
+                i += 1;
+            }
+            b' ' | b'\t' | b'\r' | b'\x0b' | b'\x0c' => i += 1,
+            b'#' if line_start => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes[i..].starts_with(b"//") => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes[i..].starts_with(b"/*") => {
+                i = match source[i + 2..].find("*/") {
+                    Some(end) => i + 2 + end + 2,
+                    None => bytes.len(),
+                };
+            }
+            _ => {
+                line_start = false;
+                let end = token_end(bytes, i);
+                tokens.push(Span::span(i, end));
+                i = end;
+            }
+        }
+    }
+    tokens
+}
+
+/// Index of the first token that starts at or after `offset`
+fn first_at(tokens: &[usize], offset: usize) -> usize {
+    match tokens.binary_search(&offset) {
+        Ok(index) | Err(index) => index,
+    }
+}
+
+const PUNCTUATORS: &[&str] = &[
+    "%:%:", "...", "<<=", ">>=", "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||",
+    "*=", "/=", "%=", "+=", "-=", "&=", "^=", "|=", "##", "<:", ":>", "<%", "%>", "%:", "::",
+];
+
+fn token_end(bytes: &[u8], start: usize) -> usize {
+    let at = |i: usize| bytes.get(i).cloned().unwrap_or(0);
+    let word = |c: u8| c == b'_' || c.is_ascii_alphanumeric() || c >= 0x80;
+    let c = bytes[start];
+    if c.is_ascii_digit() || (c == b'.' && at(start + 1).is_ascii_digit()) {
+        // Preprocessing number, C11 6.4.8
+        let mut i = start + 1;
+        loop {
+            let d = at(i);
+            let sign = (d == b'+' || d == b'-') && b"eEpP".contains(&at(i - 1));
+            if !(sign || word(d) || d == b'.') {
+                return i;
+            }
+            i += 1;
+        }
+    }
+    if word(c) {
+        let mut i = start + 1;
+        while word(at(i)) {
+            i += 1;
+        }
+        let quote = at(i) == b'\'' || at(i) == b'"';
+        return match &bytes[start..i] {
+            b"L" | b"u" | b"U" | b"u8" if quote => quoted_end(bytes, i),
+            _ => i,
+        };
+    }
+    if c == b'\'' || c == b'"' {
+        return quoted_end(bytes, start);
+    }
+    for p in PUNCTUATORS {
+        if bytes[start..].starts_with(p.as_bytes()) {
+            return start + p.len();
+        }
+    }
+    start + 1
+}
+
+fn quoted_end(bytes: &[u8], open: usize) -> usize {
+    let quote = bytes[open];
+    let mut i = open + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\n' => return i,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
@@ -0,0 +1,93 @@
+//! Source text of function definitions
+//!
+//! Tools that hand single functions to other programs, such as review bots or external
+//! analyzers, need the text of a function without the rest of the file. `function_source` cuts
+//! a definition out of the text it was parsed from and lexes only that part.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::snippet::function_sources;
+//!
+//! let source = "int x;\nstatic int twice(int y) {\n    return 2 * y; /* double */\n}\n";
+//! let config = Config {
+//!     comments: true,
+//!     ..Config::default()
+//! };
+//! let parse = parse_preprocessed(&config, source.into()).unwrap();
+//!
+//! let functions = function_sources(&parse);
+//! assert_eq!(functions.len(), 1);
+//! let twice = &functions[0].1;
+//! assert!(twice.text.starts_with("static int twice"));
+//! assert_eq!(&source[twice.body.start..twice.body.end], "{\n    return 2 * y; /* double */\n}");
+//!
+//! let tokens: Vec<_> = twice.tokens.iter().map(|t| &source[t.start..t.end]).collect();
+//! assert_eq!(
+//!     tokens,
+//!     ["static", "int", "twice", "(", "int", "y", ")", "{", "return", "2", "*", "y", ";", "}"]
+//! );
+//! ```
+
+use ast::*;
+use driver::Parse;
+use size::tokens;
+use span::{Node, Span};
+
+/// Original source of a function definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionSource<'a> {
+    /// Source text of the whole definition, from its first specifier to the closing brace
+    pub text: &'a str,
+    /// Location of `text`
+    pub span: Span,
+    /// Location of the body, including the braces
+    pub body: Span,
+    /// Tokens of the definition, without line directives and comments
+    pub tokens: Vec<Span>,
+}
+
+/// Source of a function definition parsed from `source`
+///
+/// `source` must be the text the function was parsed from, usually `Parse::source`. Spans are
+/// offsets into `source`.
+pub fn function_source<'a>(
+    source: &'a str,
+    function: &Node<FunctionDefinition>,
+) -> FunctionSource<'a> {
+    let span = function.span;
+    FunctionSource {
+        text: &source[span.start..span.end],
+        span,
+        body: function.node.statement.span,
+        tokens: tokens(source, span),
+    }
+}
+
+/// Sources of all function definitions of a translation unit, in source order
+///
+/// Definitions inside `extern "C"` blocks are included, nested functions are not.
+pub fn function_sources<'a>(
+    parse: &'a Parse,
+) -> Vec<(&'a Node<FunctionDefinition>, FunctionSource<'a>)> {
+    let mut functions = Vec::new();
+    collect(&parse.source, &parse.unit.0, &mut functions);
+    functions
+}
+
+fn collect<'a>(
+    source: &'a str,
+    externals: &'a [Node<ExternalDeclaration>],
+    functions: &mut Vec<(&'a Node<FunctionDefinition>, FunctionSource<'a>)>,
+) {
+    for external in externals {
+        match external.node {
+            ExternalDeclaration::FunctionDefinition(ref f) => {
+                functions.push((f, function_source(source, f)))
+            }
+            ExternalDeclaration::LinkageSpecification(ref l) => {
+                collect(source, &l.node.declarations, functions)
+            }
+            _ => {}
+        }
+    }
+}
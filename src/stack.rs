@@ -0,0 +1,260 @@
+//! Stack usage of functions
+//!
+//! Estimates how many bytes the local variables of each function take on the stack, for example
+//! to size the stacks of threads on a microcontroller. Locals are laid out for a target data
+//! model in declaration order, each aligned to its type, and blocks that are not nested in each
+//! other share the space after the locals of their enclosing block. The estimate is the end of
+//! the deepest block.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::layout::{Endianness, Target};
+//! use lang_c::stack::stack_usage;
+//!
+//! let source = r#"
+//!     struct packet { char header[6]; int length; };
+//!     int checksum(const char *data, int n) {
+//!         struct packet p;
+//!         short crc = 0;
+//!         static char table[256];
+//!         if (n > 8) {
+//!             double scale[4];
+//!         } else {
+//!             char line[n];
+//!         }
+//!         for (int i = 0; i < n; i++)
+//!             crc += data[i];
+//!         return crc;
+//!     }
+//!     void *buffer(int n) { return __builtin_alloca(n); }
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//!
+//! let usage = stack_usage(&parse.unit, &Target::lp64(Endianness::Little));
+//! assert_eq!(usage[0].name, "checksum");
+//! assert_eq!(usage[0].bytes, 48);
+//! assert_eq!(usage[0].unbounded.len(), 1);
+//! assert_eq!(usage[1].bytes, 0);
+//! assert_eq!(usage[1].unbounded.len(), 1);
+//! ```
+//!
+//! Only named local variables are counted: parameters, saved registers, return addresses,
+//! temporaries and compound literals are not, and neither is the effect of optimizations such as
+//! keeping variables in registers. Variable length arrays and calls to `alloca` and
+//! `__builtin_alloca` make the usage unbounded. Calls to other functions are not followed.
+
+use std::mem;
+
+use ast::*;
+use initializers::resolve;
+use layout::{Layouts, Target, Type};
+use span::Span;
+use visit::{self, Visit};
+
+/// Estimated stack usage of a function
+#[derive(Debug, Clone)]
+pub struct StackUsage<'ast> {
+    pub function: &'ast FunctionDefinition,
+    pub name: &'ast str,
+    /// Location of the function definition
+    pub span: Span,
+    /// Bytes taken by the local variables of the deepest nested block
+    pub bytes: usize,
+    /// Variable length arrays and `alloca` calls, which can take any amount of stack
+    pub unbounded: Vec<Span>,
+    /// Local variables whose type has no known layout, left out of `bytes`
+    pub unknown: Vec<Span>,
+}
+
+/// Stack usage of the functions defined in a translation unit, in source order
+///
+/// Nested functions are reported separately from the function they are defined in.
+pub fn stack_usage<'ast>(unit: &'ast TranslationUnit, target: &Target) -> Vec<StackUsage<'ast>> {
+    let layouts = Layouts::new(unit, *target);
+    let mut frames = Frames {
+        layouts: &layouts,
+        functions: Vec::new(),
+        current: None,
+        scopes: Vec::new(),
+    };
+    frames.visit_translation_unit(unit);
+    frames.functions
+}
+
+/// Offsets in a block being visited
+struct Scope {
+    /// End of the locals declared so far
+    end: usize,
+    /// End of the locals of the block and the blocks nested in it
+    deepest: usize,
+}
+
+struct Frames<'l, 'ast: 'l> {
+    layouts: &'l Layouts<'ast>,
+    functions: Vec<StackUsage<'ast>>,
+    /// Index of the function being visited
+    current: Option<usize>,
+    /// Blocks being visited in the current function, innermost last
+    scopes: Vec<Scope>,
+}
+
+impl<'l, 'ast> Frames<'l, 'ast> {
+    fn local(&mut self, base: &Type<'ast>, init: &'ast InitDeclarator, function: usize) {
+        let declarator = &init.declarator;
+        if declares_function(&declarator.node) {
+            return;
+        }
+        let mut ty = self.layouts.declarator(base.clone(), &declarator.node);
+        if let (Type::Array(element, None), Some(i)) = (ty.clone(), init.initializer.as_ref()) {
+            if let Ok(Some(length)) = resolve(self.layouts, &ty, i).map(|i| i.length) {
+                ty = Type::Array(element, Some(length));
+            }
+        }
+        let layout = match self.layouts.layout(&ty) {
+            Ok(layout) => layout,
+            Err(_) if self.variable_length(&declarator.node) => {
+                self.functions[function].unbounded.push(declarator.span);
+                return;
+            }
+            Err(_) => {
+                self.functions[function].unknown.push(declarator.span);
+                return;
+            }
+        };
+        if let Some(scope) = self.scopes.last_mut() {
+            let start = round_up(scope.end, layout.alignment);
+            scope.end = start + layout.size;
+            scope.deepest = scope.deepest.max(scope.end);
+        }
+    }
+
+    /// Whether a declarator has an array derivation whose length is not a constant
+    fn variable_length(&self, d: &Declarator) -> bool {
+        let variable = d.derived.iter().any(|derived| match derived.node {
+            DerivedDeclarator::Array(ref a) => match a.node.size {
+                ArraySize::Unknown => false,
+                ArraySize::VariableUnknown => true,
+                ArraySize::VariableExpression(ref e) | ArraySize::StaticExpression(ref e) => {
+                    self.layouts.eval(e).is_err()
+                }
+            },
+            _ => false,
+        });
+        match d.kind.node {
+            DeclaratorKind::Declarator(ref inner) => variable || self.variable_length(&inner.node),
+            _ => variable,
+        }
+    }
+
+    fn block<F>(&mut self, visit: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let end = self.scopes.last().map_or(0, |s| s.end);
+        self.scopes.push(Scope { end, deepest: end });
+        visit(self);
+        let deepest = self.scopes.pop().map_or(0, |s| s.deepest);
+        match (self.scopes.last_mut(), self.current) {
+            (Some(outer), _) => outer.deepest = outer.deepest.max(deepest),
+            (None, Some(f)) => self.functions[f].bytes = self.functions[f].bytes.max(deepest),
+            (None, None) => {}
+        }
+    }
+}
+
+impl<'l, 'ast> Visit<'ast> for Frames<'l, 'ast> {
+    fn visit_function_definition(&mut self, f: &'ast FunctionDefinition, span: &'ast Span) {
+        self.functions.push(StackUsage {
+            function: f,
+            name: declarator_name(&f.declarator.node).unwrap_or(""),
+            span: *span,
+            bytes: 0,
+            unbounded: Vec::new(),
+            unknown: Vec::new(),
+        });
+        let current = self.current.replace(self.functions.len() - 1);
+        let scopes = mem::replace(&mut self.scopes, Vec::new());
+        self.visit_statement(&f.statement.node, &f.statement.span);
+        self.current = current;
+        self.scopes = scopes;
+    }
+
+    fn visit_declaration(&mut self, declaration: &'ast Declaration, span: &'ast Span) {
+        let function = match self.current {
+            Some(f) => f,
+            None => return,
+        };
+        let automatic = declaration.specifiers.iter().all(|s| match s.node {
+            DeclarationSpecifier::StorageClass(ref s) => match s.node {
+                StorageClassSpecifier::Typedef
+                | StorageClassSpecifier::Extern
+                | StorageClassSpecifier::Static
+                | StorageClassSpecifier::ThreadLocal => false,
+                _ => true,
+            },
+            _ => true,
+        });
+        if automatic {
+            let specifiers = declaration.specifiers.iter().filter_map(|s| match s.node {
+                DeclarationSpecifier::TypeSpecifier(ref t) => Some(t),
+                _ => None,
+            });
+            let base = self.layouts.base(specifiers);
+            for init in &declaration.declarators {
+                self.local(&base, &init.node, function);
+            }
+        }
+        visit::visit_declaration(self, declaration, span);
+    }
+
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        match *statement {
+            Statement::Compound(_) | Statement::For(_) => {
+                self.block(|frames| visit::visit_statement(frames, statement, span))
+            }
+            _ => visit::visit_statement(self, statement, span),
+        }
+    }
+
+    fn visit_call_expression(&mut self, call: &'ast CallExpression, span: &'ast Span) {
+        if let (Expression::Identifier(ref i), Some(f)) = (&call.callee.node, self.current) {
+            match &*i.node.name {
+                "alloca" | "__builtin_alloca" | "__builtin_alloca_with_align" => {
+                    self.functions[f].unbounded.push(*span)
+                }
+                _ => {}
+            }
+        }
+        visit::visit_call_expression(self, call, span);
+    }
+}
+
+fn round_up(n: usize, multiple: usize) -> usize {
+    (n + multiple - 1) / multiple * multiple
+}
+
+fn declarator_name(d: &Declarator) -> Option<&str> {
+    match d.kind.node {
+        DeclaratorKind::Abstract => None,
+        DeclaratorKind::Identifier(ref i) => Some(&i.node.name),
+        DeclaratorKind::Declarator(ref d) => declarator_name(&d.node),
+    }
+}
+
+/// Whether a declarator declares a function rather than an object
+fn declares_function(declarator: &Declarator) -> bool {
+    if let DeclaratorKind::Declarator(ref inner) = declarator.kind.node {
+        if !inner.node.derived.is_empty() {
+            return declares_function(&inner.node);
+        }
+    }
+    // Suffixes apply before the pointers written in front of the identifier
+    for derived in &declarator.derived {
+        match derived.node {
+            DerivedDeclarator::Function(_) | DerivedDeclarator::KRFunction(_) => return true,
+            DerivedDeclarator::Pointer(_) => continue,
+            DerivedDeclarator::Array(_) => return false,
+        }
+    }
+    false
+}
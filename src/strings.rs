@@ -36,6 +36,7 @@ pub const RESERVED_C11: &'static [&'static str] = &[
     "_Alignas",
     "_Alignof",
     "_Atomic",
+    "_BitInt",
     "_Bool",
     "_Complex",
     "_Generic",
@@ -59,6 +60,20 @@ pub const RESERVED_C11: &'static [&'static str] = &[
     "_Decimal128x",
 ];
 
+pub const RESERVED_C23: &'static [&'static str] = &[
+    "alignas",
+    "alignof",
+    "bool",
+    "constexpr",
+    "false",
+    "nullptr",
+    "static_assert",
+    "thread_local",
+    "true",
+    "typeof",
+    "typeof_unqual",
+];
+
 pub const RESERVED_GNU: &'static [&'static str] = &[
     "__FUNCTION__",
     "__PRETTY_FUNCTION__",
@@ -68,17 +83,22 @@ pub const RESERVED_GNU: &'static [&'static str] = &[
     "__asm__",
     "__attribute",
     "__attribute__",
+    "__auto_type",
+    "__builtin_choose_expr",
     "__builtin_offsetof",
+    "__builtin_types_compatible_p",
     "__builtin_va_arg",
     "__complex",
     "__complex__",
     "__const",
     "__extension__",
+    "__float128",
     "__func__",
     "__imag",
     "__imag__",
     "__inline",
     "__inline__",
+    "__int128",
     "__label__",
     "__null",
     "__real",
@@ -89,6 +109,7 @@ pub const RESERVED_GNU: &'static [&'static str] = &[
     "__signed__",
     "__thread",
     "__typeof",
+    "__typeof_unqual",
     "__volatile",
     "__volatile__",
 ];
@@ -111,3 +132,96 @@ pub const RESERVED_CLANG: &'static [&'static str] = &[
     "_Null_unspecified",
     "_Nullable",
 ];
+
+// Keywords that can be given alternative spellings through `Config::keyword_aliases`
+pub const ALIASABLE: &'static [&'static str] = &[
+    "auto",
+    "extern",
+    "register",
+    "static",
+    "typedef",
+    "_Thread_local",
+    "void",
+    "char",
+    "short",
+    "int",
+    "long",
+    "float",
+    "double",
+    "signed",
+    "unsigned",
+    "_Bool",
+    "_Complex",
+    "const",
+    "restrict",
+    "volatile",
+    "inline",
+    "_Noreturn",
+    "asm",
+];
+
+// Ref: https://learn.microsoft.com/en-us/cpp/code-quality/understanding-sal
+// Prefixes of SAL 2 annotations, whose names also end with an underscore
+pub const SAL_PREFIXES: &'static [&'static str] = &[
+    "_Acquires_",
+    "_Always_",
+    "_Analysis_",
+    "_Callback_",
+    "_Check_return_",
+    "_COM_Outptr_",
+    "_Const_",
+    "_Deref_",
+    "_Field_",
+    "_Frees_ptr_",
+    "_Guarded_by_",
+    "_In_",
+    "_Inout_",
+    "_Interlocked_",
+    "_IRQL_",
+    "_Literal_",
+    "_Maybenull_",
+    "_Must_inspect_result_",
+    "_Notnull_",
+    "_Null_",
+    "_NullNull_terminated_",
+    "_On_failure_",
+    "_Out_",
+    "_Outptr_",
+    "_Outref_",
+    "_Points_to_data_",
+    "_Post_",
+    "_Pre_",
+    "_Printf_format_string_",
+    "_Readable_",
+    "_Releases_",
+    "_Requires_",
+    "_Reserved_",
+    "_Result_",
+    "_Ret_",
+    "_Return_type_success_",
+    "_Scanf_format_string_",
+    "_Strict_type_match_",
+    "_Struct_size_bytes_",
+    "_Success_",
+    "_Use_decl_annotations_",
+    "_When_",
+    "_Writable_",
+];
+
+// SAL 1 annotations, alone or followed by an underscore and modifiers such as `__in_opt`
+pub const SAL1_ANNOTATIONS: &'static [&'static str] = &[
+    "__bcount",
+    "__callback",
+    "__checkReturn",
+    "__deref",
+    "__ecount",
+    "__format_string",
+    "__in",
+    "__inout",
+    "__nullnullterminated",
+    "__nullterminated",
+    "__out",
+    "__reserved",
+    "__success",
+    "__typefix",
+];
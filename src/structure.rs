@@ -0,0 +1,663 @@
+//! Goto elimination
+//!
+//! `structure` rewrites a function so that `goto` statements are replaced by the `if` and
+//! `do`-`while` statements they imitate, for tools that only understand structured control
+//! flow, such as decompilers and verifiers. The result is a syntax tree that can be printed with
+//! the `unparse` module.
+//!
+//! ```
+//! use lang_c::ast::{ExternalDeclaration, TranslationUnit};
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::span::Node;
+//! use lang_c::structure::structure;
+//! use lang_c::unparse::unparse;
+//!
+//! let config = Config::default();
+//! let source = r#"
+//!     int f(int n) {
+//!         int s = 0;
+//!     again:
+//!         s += n--;
+//!         if (n > 0) goto again;
+//!         if (s > 100) goto done;
+//!         s *= 2;
+//!     done:
+//!         return s;
+//!     }
+//! "#;
+//! let parse = parse_preprocessed(&config, source.into()).unwrap();
+//! let function = match parse.unit.0[0].node {
+//!     ExternalDeclaration::FunctionDefinition(ref f) => f,
+//!     _ => unreachable!(),
+//! };
+//!
+//! let structuring = structure(&function.node);
+//! assert_eq!(structuring.eliminated, 2);
+//! assert!(structuring.remaining.is_empty());
+//! assert!(!structuring.irreducible);
+//! assert_eq!(
+//!     unparse(&config, &TranslationUnit(vec![Node::new(
+//!         ExternalDeclaration::FunctionDefinition(Node::new(structuring.function, function.span)),
+//!         function.span,
+//!     )])),
+//!     "int f(int n) {\n    int s = 0;\n    do {\n        s += n--;\n    } while (n > 0);\n    \
+//!      if (!(s > 100)) {\n        s *= 2;\n    }\n    return s;\n}\n"
+//! );
+//! ```
+//!
+//! A `goto`, unconditional or the only statement of an `if` without `else`, is replaced when its
+//! label is on another statement of the same block. A jump forward becomes an `if` around the
+//! statements it skips, and a jump backward a `do`-`while` loop around the statements from the
+//! label to the jump. A jump is kept if the wrapped statements contain a label that other jumps
+//! lead to, if a loop would capture a `break` or `continue` of the enclosing statement, or if a
+//! name declared by the wrapped statements is used after them.
+//! Labels that are no longer the target of a jump are removed.
+//!
+//! Jumps between different blocks are kept and reported. Some of them can not be replaced by
+//! any structured statements without copying code or adding variables: `irreducible` tells
+//! whether the function has a loop that can be entered at more than one point.
+
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use ast::*;
+use cfg::{Cfg, ENTRY};
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// Result of goto elimination
+#[derive(Debug, Clone)]
+pub struct Structuring {
+    /// Function with `goto` statements replaced
+    pub function: FunctionDefinition,
+    /// Number of `goto` statements replaced
+    pub eliminated: usize,
+    /// Locations of the `goto` statements left in `function`
+    pub remaining: Vec<Span>,
+    /// Whether the control flow of `function` has a loop with more than one entry
+    pub irreducible: bool,
+}
+
+/// Replace the `goto` statements of a function by structured statements where possible
+///
+/// ```
+/// use lang_c::ast::ExternalDeclaration;
+/// use lang_c::driver::{parse_preprocessed, Config};
+/// use lang_c::structure::structure;
+///
+/// let source = r#"
+///     int declared(int n) {
+///         if (n) goto skip;
+///         int k = 2;
+///     skip:
+///         return k;
+///     }
+///     int scoped(int n) {
+///         if (n) goto skip;
+///         { int k = 2; n += k; }
+///     skip:
+///         return n;
+///     }
+///     void captured(int n) {
+///         while (n) {
+///         again:
+///             if (--n == 5) break;
+///             if (n & 1) goto again;
+///         }
+///     }
+///     int entered(int n) {
+///         if (n) goto b;
+///     a:
+///         n++;
+///     b:
+///         if (--n > 10) goto a;
+///         return n;
+///     }
+/// "#;
+/// let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+/// let result: Vec<_> = parse.unit.0.iter().map(|e| match e.node {
+///     ExternalDeclaration::FunctionDefinition(ref f) => {
+///         let s = structure(&f.node);
+///         (s.eliminated, s.remaining.len(), s.irreducible)
+///     }
+///     _ => unreachable!(),
+/// }).collect();
+///
+/// // `k` is used after the statements the jump skips
+/// assert_eq!(result[0], (0, 1, false));
+/// // A declaration in a nested block does not prevent the replacement
+/// assert_eq!(result[1], (1, 0, false));
+/// // A loop around the statements would capture the `break` of the `while`
+/// assert_eq!(result[2], (0, 1, false));
+/// // The loop from `a` to the last jump can also be entered at `b`
+/// assert_eq!(result[3], (0, 2, true));
+/// ```
+pub fn structure(function: &FunctionDefinition) -> Structuring {
+    let mut function = function.clone();
+    let mut eliminated = 0;
+    loop {
+        let targets = Targets::of(&function.statement);
+        if !statement(&mut function.statement, &targets) {
+            break;
+        }
+        eliminated += 1;
+    }
+
+    let targets = Targets::of(&function.statement);
+    remove_labels(&mut function.statement, &targets);
+
+    let mut gotos = Gotos(Vec::new());
+    gotos.visit_statement(&function.statement.node, &function.statement.span);
+    let irreducible = irreducible(&Cfg::new(&function));
+    Structuring {
+        eliminated,
+        remaining: gotos.0,
+        irreducible,
+        function,
+    }
+}
+
+/// Replace one `goto` in a statement, preferring the innermost ones
+fn statement(statement: &mut Node<Statement>, targets: &Targets) -> bool {
+    match statement.node {
+        Statement::Compound(ref mut items) => {
+            for item in items.iter_mut() {
+                if let BlockItem::Statement(ref mut s) = item.node {
+                    if self::statement(s, targets) {
+                        return true;
+                    }
+                }
+            }
+            eliminate(items, targets)
+        }
+        Statement::Labeled(ref mut l) => self::statement(&mut l.node.statement, targets),
+        Statement::Attributed(ref mut a) => self::statement(&mut a.node.statement, targets),
+        Statement::If(ref mut i) => {
+            if self::statement(&mut i.node.then_statement, targets) {
+                return true;
+            }
+            match i.node.else_statement {
+                Some(ref mut e) => self::statement(e, targets),
+                None => false,
+            }
+        }
+        Statement::Switch(ref mut s) => self::statement(&mut s.node.statement, targets),
+        Statement::While(ref mut w) => self::statement(&mut w.node.statement, targets),
+        Statement::DoWhile(ref mut d) => self::statement(&mut d.node.statement, targets),
+        Statement::For(ref mut f) => self::statement(&mut f.node.statement, targets),
+        _ => false,
+    }
+}
+
+/// Replace one `goto` to a label of the same block
+fn eliminate(items: &mut Vec<Node<BlockItem>>, targets: &Targets) -> bool {
+    for from in 0..items.len() {
+        let (name, condition) = match jump(&items[from]) {
+            Some(j) => j,
+            None => continue,
+        };
+        let to = match items.iter().position(|i| is_labeled(i, &name)) {
+            Some(to) => to,
+            None => continue,
+        };
+        let replaced = if from < to {
+            forward(items, from, to, condition, targets)
+        } else if to < from {
+            backward(items, to, from, condition, targets, &name)
+        } else {
+            false
+        };
+        if replaced {
+            return true;
+        }
+    }
+    false
+}
+
+/// `goto` at `from` skipping to the label at `to`
+fn forward(
+    items: &mut Vec<Node<BlockItem>>,
+    from: usize,
+    to: usize,
+    condition: Option<Node<Expression>>,
+    targets: &Targets,
+) -> bool {
+    let region = &items[from + 1..to];
+    if entered(region, targets, None) || used_after(region, &items[to..], None) {
+        return false;
+    }
+    let span = Span::span(items[from].span.start, items[to - 1].span.end);
+    let skipped: Vec<_> = items.drain(from + 1..to).collect();
+    let replacement = match (condition, skipped.is_empty()) {
+        (Some(c), true) => Statement::Expression(Some(Box::new(c))),
+        (None, true) => {
+            items.remove(from);
+            return true;
+        }
+        (condition, false) => {
+            let condition = match condition {
+                Some(c) => negate(c),
+                None => constant("0"),
+            };
+            Statement::If(Node::new(
+                IfStatement {
+                    condition: Box::new(condition),
+                    then_statement: Box::new(compound(skipped)),
+                    else_statement: None,
+                },
+                span,
+            ))
+        }
+    };
+    items[from] = Node::new(BlockItem::Statement(Node::new(replacement, span)), span);
+    true
+}
+
+/// `goto` at `from` jumping back to the label at `to`
+fn backward(
+    items: &mut Vec<Node<BlockItem>>,
+    to: usize,
+    from: usize,
+    condition: Option<Node<Expression>>,
+    targets: &Targets,
+    name: &str,
+) -> bool {
+    let region = &items[to..from];
+    if region.iter().any(escapes) || entered(region, targets, Some(name)) {
+        return false;
+    }
+    let condition = condition.unwrap_or_else(|| constant("1"));
+    if used_after(region, &items[from + 1..], Some(&condition)) {
+        return false;
+    }
+    let span = Span::span(items[to].span.start, items[from].span.end);
+    let body: Vec<_> = items.drain(to..from).collect();
+    let replacement = Statement::DoWhile(Node::new(
+        DoWhileStatement {
+            statement: Box::new(compound(body)),
+            expression: Box::new(condition),
+        },
+        span,
+    ));
+    items[to] = Node::new(BlockItem::Statement(Node::new(replacement, span)), span);
+    true
+}
+
+/// Label and condition of `goto label;` or `if (condition) goto label;`
+fn jump(item: &Node<BlockItem>) -> Option<(String, Option<Node<Expression>>)> {
+    let statement = match item.node {
+        BlockItem::Statement(ref s) => s,
+        _ => return None,
+    };
+    match statement.node {
+        Statement::Goto(ref l) => Some((l.node.name.clone(), None)),
+        Statement::If(ref i) if i.node.else_statement.is_none() => {
+            let then = &i.node.then_statement;
+            let goto = match then.node {
+                Statement::Compound(ref items) if items.len() == 1 => match items[0].node {
+                    BlockItem::Statement(ref s) => s,
+                    _ => return None,
+                },
+                _ => then,
+            };
+            match goto.node {
+                Statement::Goto(ref l) => {
+                    Some((l.node.name.clone(), Some((*i.node.condition).clone())))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_labeled(item: &Node<BlockItem>, name: &str) -> bool {
+    let mut statement = match item.node {
+        BlockItem::Statement(ref s) => s,
+        _ => return false,
+    };
+    while let Statement::Labeled(ref l) = statement.node {
+        if let Label::Identifier(ref i) = l.node.label.node {
+            if i.node.name == name {
+                return true;
+            }
+        }
+        statement = &l.node.statement;
+    }
+    false
+}
+
+/// Whether a label in `region` is the target of a jump from outside of it, other than the
+/// `goto` to `except` being replaced
+fn entered(region: &[Node<BlockItem>], targets: &Targets, except: Option<&str>) -> bool {
+    let mut inside = Targets::default();
+    let mut labels = Labels(Vec::new());
+    for item in region {
+        inside.visit_block_item(&item.node, &item.span);
+        labels.visit_block_item(&item.node, &item.span);
+    }
+    labels.0.iter().any(|&label| {
+        let mut jumps = targets.jumps.get(label).cloned().unwrap_or(0);
+        if except == Some(label) {
+            jumps -= 1;
+        }
+        targets.addressed.contains(label) || jumps > inside.jumps.get(label).cloned().unwrap_or(0)
+    })
+}
+
+/// Whether a `break` or `continue` in an item leaves the item
+fn escapes(item: &Node<BlockItem>) -> bool {
+    let mut jumps = Escapes {
+        loops: 0,
+        switches: 0,
+        escapes: false,
+    };
+    if let BlockItem::Statement(ref s) = item.node {
+        jumps.visit_statement(&s.node, &s.span);
+    }
+    jumps.escapes
+}
+
+/// Whether a name declared by `moved` is used in `after` or in `condition`
+fn used_after(
+    moved: &[Node<BlockItem>],
+    after: &[Node<BlockItem>],
+    condition: Option<&Node<Expression>>,
+) -> bool {
+    let mut declared = Declared(HashSet::new());
+    for item in moved {
+        if let BlockItem::Declaration(ref d) = item.node {
+            declared.visit_declaration(&d.node, &d.span);
+        }
+    }
+    if declared.0.is_empty() {
+        return false;
+    }
+    let mut names = Names(HashSet::new());
+    for item in after {
+        names.visit_block_item(&item.node, &item.span);
+    }
+    if let Some(c) = condition {
+        names.visit_expression(&c.node, &c.span);
+    }
+    !declared.0.is_disjoint(&names.0)
+}
+
+fn negate(condition: Node<Expression>) -> Node<Expression> {
+    if let Expression::UnaryOperator(ref u) = condition.node {
+        if u.node.operator.node == UnaryOperator::Negate {
+            return (*u.node.operand).clone();
+        }
+    }
+    let negation = UnaryOperatorExpression {
+        operator: Node::new(UnaryOperator::Negate, Span::none()),
+        operand: Box::new(condition),
+    };
+    Node::new(
+        Expression::UnaryOperator(Box::new(Node::new(negation, Span::none()))),
+        Span::none(),
+    )
+}
+
+fn constant(number: &str) -> Node<Expression> {
+    let integer = Integer {
+        base: IntegerBase::Decimal,
+        number: number.into(),
+        suffix: IntegerSuffix {
+            size: IntegerSize::Int,
+            unsigned: false,
+            imaginary: false,
+        },
+    };
+    let constant = Node::new(Constant::Integer(integer), Span::none());
+    Node::new(Expression::Constant(Box::new(constant)), Span::none())
+}
+
+fn compound(items: Vec<Node<BlockItem>>) -> Node<Statement> {
+    let span = match (items.first(), items.last()) {
+        (Some(first), Some(last)) => Span::span(first.span.start, last.span.end),
+        _ => Span::none(),
+    };
+    Node::new(Statement::Compound(items), span)
+}
+
+/// Replace labeled statements whose label is not in `targets` by the statement they label
+fn remove_labels(statement: &mut Node<Statement>, targets: &Targets) {
+    let unused = match statement.node {
+        Statement::Labeled(ref l) => match l.node.label.node {
+            Label::Identifier(ref i) => !targets.contains(&i.node.name),
+            _ => false,
+        },
+        _ => false,
+    };
+    if unused {
+        let inner = match mem::replace(&mut statement.node, Statement::Expression(None)) {
+            Statement::Labeled(l) => *l.node.statement,
+            _ => unreachable!(),
+        };
+        *statement = inner;
+        return remove_labels(statement, targets);
+    }
+    match statement.node {
+        Statement::Compound(ref mut items) => {
+            for item in items {
+                if let BlockItem::Statement(ref mut s) = item.node {
+                    remove_labels(s, targets);
+                }
+            }
+        }
+        Statement::Labeled(ref mut l) => remove_labels(&mut l.node.statement, targets),
+        Statement::Attributed(ref mut a) => remove_labels(&mut a.node.statement, targets),
+        Statement::If(ref mut i) => {
+            remove_labels(&mut i.node.then_statement, targets);
+            if let Some(ref mut e) = i.node.else_statement {
+                remove_labels(e, targets);
+            }
+        }
+        Statement::Switch(ref mut s) => remove_labels(&mut s.node.statement, targets),
+        Statement::While(ref mut w) => remove_labels(&mut w.node.statement, targets),
+        Statement::DoWhile(ref mut d) => remove_labels(&mut d.node.statement, targets),
+        Statement::For(ref mut f) => remove_labels(&mut f.node.statement, targets),
+        _ => {}
+    }
+}
+
+/// Whether a control flow graph has a loop entered other than through its header
+///
+/// Such a loop shows up as an edge back to a block on the current depth-first search path
+/// that does not dominate the source of the edge.
+fn irreducible(cfg: &Cfg) -> bool {
+    let count = cfg.blocks.len();
+    let predecessors: Vec<_> = (0..count).map(|b| cfg.predecessors(b)).collect();
+
+    // Blocks dominating each block, by iteration to a fixed point
+    let mut dominators = vec![vec![true; count]; count];
+    dominators[ENTRY] = vec![false; count];
+    dominators[ENTRY][ENTRY] = true;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for block in 0..count {
+            if block == ENTRY {
+                continue;
+            }
+            let mut set = vec![true; count];
+            for &p in &predecessors[block] {
+                for (d, s) in set.iter_mut().enumerate() {
+                    *s = *s && dominators[p][d];
+                }
+            }
+            set[block] = true;
+            if set != dominators[block] {
+                dominators[block] = set;
+                changed = true;
+            }
+        }
+    }
+
+    let mut on_path = vec![false; count];
+    let mut visited = vec![false; count];
+    let mut stack = vec![(ENTRY, 0)];
+    visited[ENTRY] = true;
+    on_path[ENTRY] = true;
+    while let Some(&mut (block, ref mut next)) = stack.last_mut() {
+        let successors = &cfg.blocks[block].successors;
+        if *next == successors.len() {
+            on_path[block] = false;
+            stack.pop();
+            continue;
+        }
+        let to = successors[*next].to;
+        *next += 1;
+        if on_path[to] && !dominators[block][to] {
+            return true;
+        }
+        if !visited[to] {
+            visited[to] = true;
+            on_path[to] = true;
+            stack.push((to, 0));
+        }
+    }
+    false
+}
+
+/// Labels that are the target of a `goto` or an `asm goto`, and labels whose address is taken
+#[derive(Default)]
+struct Targets {
+    /// Number of jumps to each label
+    jumps: HashMap<String, usize>,
+    addressed: HashSet<String>,
+}
+
+impl Targets {
+    fn of(statement: &Node<Statement>) -> Targets {
+        let mut targets = Targets::default();
+        targets.visit_statement(&statement.node, &statement.span);
+        targets
+    }
+
+    fn jump(&mut self, label: &str) {
+        *self.jumps.entry(label.to_owned()).or_insert(0) += 1;
+    }
+
+    fn contains(&self, label: &str) -> bool {
+        self.jumps.contains_key(label) || self.addressed.contains(label)
+    }
+}
+
+impl<'ast> Visit<'ast> for Targets {
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        match *statement {
+            Statement::Goto(ref l) => self.jump(&l.node.name),
+            Statement::Asm(ref a) => {
+                if let AsmStatement::GnuExtended(ref e) = a.node {
+                    for label in &e.labels {
+                        self.jump(&label.node.name);
+                    }
+                }
+            }
+            _ => {}
+        }
+        visit::visit_statement(self, statement, span);
+    }
+
+    fn visit_expression(&mut self, expression: &'ast Expression, span: &'ast Span) {
+        if let Expression::LabelAddress(ref l) = *expression {
+            self.addressed.insert(l.node.name.clone());
+        }
+        visit::visit_expression(self, expression, span);
+    }
+}
+
+/// Names of the labels in a piece of code
+struct Labels<'ast>(Vec<&'ast str>);
+
+impl<'ast> Visit<'ast> for Labels<'ast> {
+    fn visit_label(&mut self, label: &'ast Label, span: &'ast Span) {
+        if let Label::Identifier(ref i) = *label {
+            self.0.push(&i.node.name);
+        }
+        visit::visit_label(self, label, span);
+    }
+}
+
+/// Locations of `goto` statements
+struct Gotos(Vec<Span>);
+
+impl<'ast> Visit<'ast> for Gotos {
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        if let Statement::Goto(_) = *statement {
+            self.0.push(*span);
+        }
+        visit::visit_statement(self, statement, span);
+    }
+}
+
+/// Check for `break` and `continue` statements outside of the loops and switches they end
+struct Escapes {
+    loops: usize,
+    switches: usize,
+    escapes: bool,
+}
+
+impl<'ast> Visit<'ast> for Escapes {
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        match *statement {
+            Statement::Break if self.loops + self.switches == 0 => self.escapes = true,
+            Statement::Continue if self.loops == 0 => self.escapes = true,
+            Statement::While(_) | Statement::DoWhile(_) | Statement::For(_) => {
+                self.loops += 1;
+                visit::visit_statement(self, statement, span);
+                self.loops -= 1;
+                return;
+            }
+            Statement::Switch(_) => {
+                self.switches += 1;
+                visit::visit_statement(self, statement, span);
+                self.switches -= 1;
+                return;
+            }
+            _ => {}
+        }
+        visit::visit_statement(self, statement, span);
+    }
+}
+
+/// Names of variables, functions, typedefs, tags and enumerators declared by a declaration
+struct Declared<'ast>(HashSet<&'ast str>);
+
+impl<'ast> Visit<'ast> for Declared<'ast> {
+    fn visit_declarator(&mut self, declarator: &'ast Declarator, span: &'ast Span) {
+        if let DeclaratorKind::Identifier(ref i) = declarator.kind.node {
+            self.0.insert(&i.node.name);
+        }
+        visit::visit_declarator(self, declarator, span);
+    }
+
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        if let (Some(_), Some(ref i)) = (&struct_type.declarations, &struct_type.identifier) {
+            self.0.insert(&i.node.name);
+        }
+        visit::visit_struct_type(self, struct_type, span);
+    }
+
+    fn visit_enum_type(&mut self, enum_type: &'ast EnumType, span: &'ast Span) {
+        if let Some(ref i) = enum_type.identifier {
+            self.0.insert(&i.node.name);
+        }
+        for enumerator in &enum_type.enumerators {
+            self.0.insert(&enumerator.node.identifier.node.name);
+        }
+        visit::visit_enum_type(self, enum_type, span);
+    }
+}
+
+/// All identifiers in a piece of code
+struct Names<'ast>(HashSet<&'ast str>);
+
+impl<'ast> Visit<'ast> for Names<'ast> {
+    fn visit_identifier(&mut self, identifier: &'ast Identifier, span: &'ast Span) {
+        self.0.insert(&identifier.name);
+        visit::visit_identifier(self, identifier, span);
+    }
+}
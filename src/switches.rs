@@ -0,0 +1,171 @@
+//! Case tables of `switch` statements
+//!
+//! The body of a `switch` is a single statement with `case` and `default` labels anywhere inside
+//! it. `switches` collects the labels that belong to each `switch`, leaving out the labels of
+//! nested `switch` statements, and evaluates the `case` expressions.
+//!
+//! ```
+//! use lang_c::ast::ExternalDeclaration;
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::eval::Value;
+//! use lang_c::switches::switches;
+//!
+//! let source = r#"
+//!     enum { A = 4, B };
+//!     int f(int x, int y) {
+//!         switch (x) {
+//!         case A:
+//!             switch (y) { case 1: return 1; }
+//!             break;
+//!         default:
+//!             if (y) { case B + 1: return 2; }
+//!         }
+//!         return 0;
+//!     }
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.into()).unwrap();
+//! let body = match parse.unit.0[1].node {
+//!     ExternalDeclaration::FunctionDefinition(ref f) => &f.node.statement,
+//!     _ => unreachable!(),
+//! };
+//!
+//! let constants = |name: &str| match name {
+//!     "A" => Some(Value::Int(4)),
+//!     "B" => Some(Value::Int(5)),
+//!     _ => None,
+//! };
+//! let found = switches(body, &constants);
+//! assert_eq!(found.len(), 2);
+//! let values: Vec<_> = found[0].cases.iter().map(|c| c.value.clone()).collect();
+//! assert_eq!(values, [Ok(Value::Int(4)), Ok(Value::Int(6))]);
+//! assert!(found[0].default.is_some());
+//! assert_eq!(found[0].target(Value::Int(6)), Some(found[0].cases[1].span));
+//! assert_eq!(found[0].target(Value::Int(1)), found[0].default);
+//!
+//! assert_eq!(found[1].cases.len(), 1);
+//! assert_eq!(found[1].target(Value::Int(2)), None);
+//! ```
+
+use ast::*;
+use eval::{eval, EvalError, Value};
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// `switch` statement found in a function body
+#[derive(Debug, Clone)]
+pub struct Switch<'ast> {
+    pub statement: &'ast SwitchStatement,
+    /// Location of the `switch` statement
+    pub span: Span,
+    /// `case` labels in source order
+    pub cases: Vec<Case<'ast>>,
+    /// Location of the statement labeled `default`, if there is one
+    pub default: Option<Span>,
+}
+
+/// `case` label of a `switch` statement
+#[derive(Debug, Clone)]
+pub struct Case<'ast> {
+    pub expression: &'ast Node<Expression>,
+    /// Value of the expression
+    pub value: Result<Value, EvalError>,
+    /// Location of the labeled statement, starting at the `case` keyword
+    pub span: Span,
+}
+
+impl<'ast> Switch<'ast> {
+    /// Location of the statement control is passed to when the controlling expression has
+    /// the value `value`
+    ///
+    /// Values are compared as mathematical integers and `case` labels that could not be
+    /// evaluated never match. Returns `None` if no label matches and there is no `default`.
+    pub fn target(&self, value: Value) -> Option<Span> {
+        let value = value.integer()?;
+        for case in &self.cases {
+            if let Ok(v) = case.value {
+                if v.integer() == Some(value) {
+                    return Some(case.span);
+                }
+            }
+        }
+        self.default
+    }
+}
+
+/// All `switch` statements in a statement, outer statements before the ones nested in them
+///
+/// `constants` provides the values of identifiers such as enumeration constants for evaluating
+/// the `case` expressions.
+pub fn switches<'ast, F>(statement: &'ast Node<Statement>, constants: &F) -> Vec<Switch<'ast>>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    let mut collector = Collector {
+        switches: Vec::new(),
+        constants,
+    };
+    collector.visit_statement(&statement.node, &statement.span);
+    collector.switches
+}
+
+struct Collector<'ast, 'a, F: 'a> {
+    switches: Vec<Switch<'ast>>,
+    constants: &'a F,
+}
+
+impl<'ast, 'a, F> Visit<'ast> for Collector<'ast, 'a, F>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        if let Statement::Switch(ref s) = *statement {
+            let mut labels = Labels {
+                cases: Vec::new(),
+                default: None,
+                constants: self.constants,
+            };
+            let body = &s.node.statement;
+            labels.visit_statement(&body.node, &body.span);
+            self.switches.push(Switch {
+                statement: &s.node,
+                span: s.span,
+                cases: labels.cases,
+                default: labels.default,
+            });
+        }
+        visit::visit_statement(self, statement, span);
+    }
+}
+
+/// Labels of a `switch` body, outside of nested `switch` statements
+struct Labels<'ast, 'a, F: 'a> {
+    cases: Vec<Case<'ast>>,
+    default: Option<Span>,
+    constants: &'a F,
+}
+
+impl<'ast, 'a, F> Visit<'ast> for Labels<'ast, 'a, F>
+where
+    F: Fn(&str) -> Option<Value>,
+{
+    fn visit_statement(&mut self, statement: &'ast Statement, span: &'ast Span) {
+        match *statement {
+            Statement::Switch(_) => return,
+            Statement::Labeled(ref l) => match l.node.label.node {
+                Label::Case(ref e) => self.cases.push(Case {
+                    expression: e,
+                    value: eval(e, self.constants),
+                    span: l.span,
+                }),
+                Label::Default => {
+                    if self.default.is_none() {
+                        self.default = Some(l.span);
+                    }
+                }
+                Label::Identifier(_) => {}
+            },
+            _ => {}
+        }
+        visit::visit_statement(self, statement, span);
+    }
+}
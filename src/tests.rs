@@ -8,10 +8,12 @@ use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::mem;
 use std::path::PathBuf;
 
+use driver::{Config, Flavor};
 use env::Env;
 use parser;
 use print::Printer;
 use span::Span;
+use unparse::{unparse, unparse_expression, unparse_statement};
 use visit::Visit;
 
 struct Case {
@@ -69,7 +71,8 @@ impl Case {
         })
     }
 
-    fn run(&self) -> bool {
+    /// Build the environment requested by the pragmas of the case
+    fn env(&self) -> Env {
         let mut env = None;
 
         for pragma in &self.pragma {
@@ -84,11 +87,29 @@ impl Case {
 
         for pragma in &self.pragma {
             match *pragma {
+                Pragma::C23 => env.enable_c23(),
+                Pragma::Linkage => env.linkage_specifications = true,
+                Pragma::Comments => env.comments = true,
+                Pragma::Sal => env.sal_annotations = true,
+                Pragma::BuiltinMacros => env.builtin_macros = true,
+                Pragma::NoDecimalFloats => env.decimal_floats = false,
                 Pragma::Typedef(ref name) => env.add_typename(&name),
+                Pragma::KeywordAlias(ref alias, ref keyword) => {
+                    assert!(
+                        env.add_keyword_alias(alias, keyword),
+                        "keyword can not be aliased"
+                    );
+                }
                 _ => {}
             }
         }
 
+        env
+    }
+
+    fn run(&self) -> bool {
+        let mut env = self.env();
+
         let (actual, error) = match self.kind.parse_and_print(&self.source, &mut env) {
             Ok(s) => (s, None),
             Err(e) => ("~ERROR\n".to_string(), Some(e)),
@@ -103,8 +124,33 @@ impl Case {
             })
             .collect::<Vec<_>>();
 
+        let round_trip = self.pragma.iter().any(|p| match *p {
+            Pragma::RoundTrip => true,
+            _ => false,
+        });
+        let mut round_trip_fail = None;
+        if round_trip && error.is_none() {
+            let config = config_for(&env);
+            let printed = self
+                .kind
+                .unparse(&self.source, &mut env, &config)
+                .expect("case parsed before");
+            match self.kind.parse_and_print(&printed, &mut env) {
+                Ok(ref reparsed) if *reparsed == actual => {}
+                _ => round_trip_fail = Some(printed),
+            }
+        }
+
+        // Parsing the same input again in a fresh environment must give the same tree
+        let repeated = match self.kind.parse_and_print(&self.source, &mut self.env()) {
+            Ok(s) => s,
+            Err(_) => "~ERROR\n".to_string(),
+        };
+        let repeat_matches = repeated == actual;
+
         let output_matches = actual == self.expect;
-        let success = output_matches && pragma_fail.is_empty();
+        let success =
+            output_matches && repeat_matches && pragma_fail.is_empty() && round_trip_fail.is_none();
 
         if !success {
             writeln!(stdout(), "\n{}:", self.name).unwrap();
@@ -121,6 +167,19 @@ impl Case {
             }
         }
 
+        if !repeat_matches {
+            writeln!(
+                stdout(),
+                "Second parse gives a different tree:\n{}",
+                repeated
+            )
+            .unwrap();
+        }
+
+        if let Some(ref printed) = round_trip_fail {
+            writeln!(stdout(), "Printed source parses differently:\n{}", printed).unwrap();
+        }
+
         if !output_matches {
             let width = self.expect.lines().map(|s| s.len()).max().unwrap_or(25);
             let mut alines = Some(self.expect.lines());
@@ -246,6 +305,48 @@ impl Kind {
 
         Ok(s)
     }
+
+    /// Parse the source and print it back as C source
+    fn unparse(
+        &self,
+        source: &str,
+        env: &mut Env,
+        config: &Config,
+    ) -> Result<String, parser::ParseError> {
+        let source = source.trim_right();
+
+        Ok(match *self {
+            Kind::Statement => {
+                let n = try!(parser::statement(source, env));
+                unparse_statement(config, &n)
+            }
+            Kind::Expression => {
+                let n = try!(parser::expression(source, env));
+                unparse_expression(config, &n)
+            }
+            Kind::TranslationUnit => {
+                let n = try!(parser::translation_unit(source, env));
+                unparse(config, &n)
+            }
+            Kind::Constant | Kind::Declaration => panic!("round_trip pragma not supported"),
+        })
+    }
+}
+
+/// Configuration for printing source parsed with `env`
+fn config_for(env: &Env) -> Config {
+    let flavor = if env.extensions_clang {
+        Flavor::ClangC11
+    } else if env.extensions_gnu {
+        Flavor::GnuC11
+    } else {
+        Flavor::StdC11
+    };
+    Config {
+        flavor,
+        c23: env.c23,
+        ..Config::default()
+    }
 }
 
 #[derive(Debug)]
@@ -254,10 +355,26 @@ enum Pragma {
     Gnu,
     // Enable clang extensions
     Clang,
+    /// Enable C23 syntax
+    C23,
+    /// Accept C++ linkage specifications
+    Linkage,
+    /// Accept comments
+    Comments,
+    /// Skip Microsoft source code annotations
+    Sal,
+    /// Parse `offsetof` and `va_arg` as built-ins
+    BuiltinMacros,
+    /// Reject decimal floating types
+    NoDecimalFloats,
     /// Define typename
     Typedef(String),
     /// Assert argument is a typename
     IsTypename(String),
+    /// Define an alternative spelling of a keyword
+    KeywordAlias(String, String),
+    /// Check that the case printed as C source parses back into the same tree
+    RoundTrip,
 }
 
 impl Pragma {
@@ -270,6 +387,13 @@ impl Pragma {
         Some(match line[0].trim() {
             "gnu" => Pragma::Gnu,
             "clang" => Pragma::Clang,
+            "c23" => Pragma::C23,
+            "linkage" => Pragma::Linkage,
+            "comments" => Pragma::Comments,
+            "sal" => Pragma::Sal,
+            "builtin_macros" => Pragma::BuiltinMacros,
+            "no_decimal_floats" => Pragma::NoDecimalFloats,
+            "round_trip" => Pragma::RoundTrip,
             "typedef" => Pragma::Typedef(match line.pop() {
                 Some(v) => v,
                 None => return None,
@@ -278,6 +402,10 @@ impl Pragma {
                 Some(v) => v,
                 None => return None,
             }),
+            "keyword_alias" => match (line.pop(), line.pop()) {
+                (Some(keyword), Some(alias)) => Pragma::KeywordAlias(alias, keyword),
+                _ => return None,
+            },
             _ => return None,
         })
     }
@@ -0,0 +1,363 @@
+//! Dependencies between type definitions
+//!
+//! Collects struct, union, enum and typedef definitions of a translation unit together with the
+//! named types each of them refers to, and orders the definitions so that every type is defined
+//! before it is needed.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::typedeps::{DependencyKind, NamedType, TypeGraph};
+//!
+//! let source = r#"
+//!     typedef struct node node;
+//!     struct list { node *head; struct stats stats; };
+//!     struct stats { unsigned count; };
+//!     struct node { node *next; int value; };
+//! "#;
+//! let parse = parse_preprocessed(&Config::default(), source.to_string()).unwrap();
+//! let graph = TypeGraph::new(&parse.unit);
+//!
+//! let list = NamedType::Struct("list".into());
+//! let uses: Vec<_> = graph.dependencies_of(&list).map(|d| (&d.to, d.kind)).collect();
+//! assert_eq!(uses, [
+//!     (&NamedType::Typedef("node".into()), DependencyKind::Pointer),
+//!     (&NamedType::Struct("stats".into()), DependencyKind::Value),
+//! ]);
+//!
+//! let order = graph.topological_order().unwrap();
+//! assert_eq!(order, [
+//!     NamedType::Typedef("node".into()),
+//!     NamedType::Struct("stats".into()),
+//!     NamedType::Struct("list".into()),
+//!     NamedType::Struct("node".into()),
+//! ]);
+//! ```
+//!
+//! Tags and typedef names are collected from the whole translation unit without regard to
+//! scope. Members of anonymous structs and unions count as dependencies of the enclosing
+//! definition.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::mem;
+
+use ast::*;
+use span::{Node, Span};
+use visit::{self, Visit};
+
+/// Type that can be referred to by name
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NamedType {
+    /// `struct tag`
+    Struct(String),
+    /// `union tag`
+    Union(String),
+    /// `enum tag`
+    Enum(String),
+    /// Typedef name
+    Typedef(String),
+}
+
+/// How a definition uses another type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    /// Type is used directly and must be complete
+    Value,
+    /// Type is only used behind a pointer
+    Pointer,
+}
+
+/// Reference from one type definition to another named type
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    /// Type whose definition contains the reference
+    pub from: NamedType,
+    /// Referenced type
+    pub to: NamedType,
+    pub kind: DependencyKind,
+    /// Location of the first such reference
+    pub span: Span,
+}
+
+/// Type definitions of a translation unit and dependencies between them
+#[derive(Debug, Clone, Default)]
+pub struct TypeGraph {
+    /// Defined types in the order of their first definition
+    pub definitions: Vec<Node<NamedType>>,
+    /// References from definitions to other named types, in source order
+    ///
+    /// Referenced types do not have to be defined in the translation unit.
+    pub dependencies: Vec<Dependency>,
+}
+
+impl TypeGraph {
+    /// Collect type definitions from a translation unit
+    pub fn new(unit: &TranslationUnit) -> TypeGraph {
+        let mut collector = Collector {
+            graph: TypeGraph::default(),
+            defined: HashSet::new(),
+            seen: HashSet::new(),
+            owner: None,
+            pointer: false,
+        };
+        collector.visit_translation_unit(unit);
+        collector.graph
+    }
+
+    /// Dependencies of a single definition
+    pub fn dependencies_of<'a>(
+        &'a self,
+        ty: &'a NamedType,
+    ) -> impl Iterator<Item = &'a Dependency> {
+        self.dependencies.iter().filter(move |d| d.from == *ty)
+    }
+
+    /// Order definitions so that each type is defined before it is needed
+    ///
+    /// A definition must follow any typedef or enum it refers to and any struct or union it uses
+    /// by value, including through typedef names. Structs and unions used only behind pointers
+    /// can be forward-declared and do not constrain the order. Otherwise definitions keep their
+    /// source order.
+    ///
+    /// Returns the definitions that form a cycle if no such order exists.
+    pub fn topological_order(&self) -> Result<Vec<NamedType>, Vec<NamedType>> {
+        let index: HashMap<&NamedType, usize> = self
+            .definitions
+            .iter()
+            .enumerate()
+            .map(|(i, d)| (&d.node, i))
+            .collect();
+
+        let mut before: Vec<HashSet<usize>> = vec![HashSet::new(); self.definitions.len()];
+        for d in &self.dependencies {
+            let (from, to) = match (index.get(&d.from), index.get(&d.to)) {
+                (Some(&from), Some(&to)) => (from, to),
+                _ => continue,
+            };
+            match (&d.from, &d.to, d.kind) {
+                (&NamedType::Typedef(_), &NamedType::Typedef(_), _) => {
+                    before[from].insert(to);
+                }
+                (_, &NamedType::Typedef(_), DependencyKind::Value) => {
+                    for complete in self.aliased(&d.to) {
+                        if let Some(&i) = index.get(complete) {
+                            before[from].insert(i);
+                        }
+                    }
+                    before[from].insert(to);
+                }
+                (_, &NamedType::Typedef(_), _) | (_, &NamedType::Enum(_), _) => {
+                    before[from].insert(to);
+                }
+                // A typedef of a struct or union does not need it to be complete
+                (&NamedType::Typedef(_), _, _) => {}
+                (_, _, DependencyKind::Value) => {
+                    before[from].insert(to);
+                }
+                (_, _, DependencyKind::Pointer) => {}
+            }
+        }
+        for (i, b) in before.iter_mut().enumerate() {
+            b.remove(&i);
+        }
+
+        let mut done = vec![false; self.definitions.len()];
+        let mut ready: BTreeSet<usize> = (0..before.len())
+            .filter(|&i| before[i].is_empty())
+            .collect();
+        let mut order = Vec::with_capacity(self.definitions.len());
+        while let Some(&i) = ready.iter().next() {
+            ready.remove(&i);
+            done[i] = true;
+            order.push(self.definitions[i].node.clone());
+            for (j, b) in before.iter_mut().enumerate() {
+                if b.remove(&i) && b.is_empty() && !done[j] {
+                    ready.insert(j);
+                }
+            }
+        }
+
+        if order.len() == self.definitions.len() {
+            Ok(order)
+        } else {
+            Err((0..done.len())
+                .filter(|&i| !done[i])
+                .map(|i| self.definitions[i].node.clone())
+                .collect())
+        }
+    }
+
+    /// Structs and unions a typedef uses by value, following other typedef names
+    fn aliased<'a>(&'a self, ty: &'a NamedType) -> Vec<&'a NamedType> {
+        let mut result = Vec::new();
+        let mut pending = vec![ty];
+        let mut visited = HashSet::new();
+        while let Some(t) = pending.pop() {
+            if !visited.insert(t) {
+                continue;
+            }
+            for d in self.dependencies_of(t) {
+                if d.kind != DependencyKind::Value {
+                    continue;
+                }
+                match d.to {
+                    NamedType::Typedef(_) => pending.push(&d.to),
+                    _ => result.push(&d.to),
+                }
+            }
+        }
+        result
+    }
+}
+
+struct Collector {
+    graph: TypeGraph,
+    defined: HashSet<NamedType>,
+    seen: HashSet<(NamedType, NamedType, DependencyKind)>,
+    owner: Option<NamedType>,
+    pointer: bool,
+}
+
+impl Collector {
+    fn define(&mut self, ty: NamedType, span: Span) {
+        if self.defined.insert(ty.clone()) {
+            self.graph.definitions.push(Node::new(ty, span));
+        }
+    }
+
+    fn depend(&mut self, to: NamedType, span: Span) {
+        let from = match self.owner {
+            Some(ref owner) => owner.clone(),
+            None => return,
+        };
+        let kind = if self.pointer {
+            DependencyKind::Pointer
+        } else {
+            DependencyKind::Value
+        };
+        if self.seen.insert((from.clone(), to.clone(), kind)) {
+            self.graph.dependencies.push(Dependency {
+                from,
+                to,
+                kind,
+                span,
+            });
+        }
+    }
+
+    /// Visit part of a definition with a different owner and pointer context
+    fn within<F: FnOnce(&mut Self)>(&mut self, owner: Option<NamedType>, pointer: bool, f: F) {
+        let owner = mem::replace(&mut self.owner, owner);
+        let pointer = mem::replace(&mut self.pointer, pointer);
+        f(self);
+        self.owner = owner;
+        self.pointer = pointer;
+    }
+}
+
+impl<'ast> Visit<'ast> for Collector {
+    fn visit_declaration(&mut self, declaration: &'ast Declaration, span: &'ast Span) {
+        let is_typedef = declaration.specifiers.iter().any(|s| match s.node {
+            DeclarationSpecifier::StorageClass(ref s) => s.node == StorageClassSpecifier::Typedef,
+            _ => false,
+        });
+        if !is_typedef {
+            return visit::visit_declaration(self, declaration, span);
+        }
+
+        for init in &declaration.declarators {
+            let declarator = &init.node.declarator;
+            let name = match declarator_name(&declarator.node) {
+                Some(name) => name,
+                None => continue,
+            };
+            let ty = NamedType::Typedef(name.to_string());
+            self.define(ty.clone(), init.span);
+            self.within(Some(ty), has_pointer(&declarator.node), |c| {
+                for specifier in &declaration.specifiers {
+                    c.visit_declaration_specifier(&specifier.node, &specifier.span);
+                }
+                c.visit_declarator(&declarator.node, &declarator.span);
+            });
+        }
+    }
+
+    fn visit_struct_field(&mut self, field: &'ast StructField, span: &'ast Span) {
+        if field.declarators.is_empty() {
+            return visit::visit_struct_field(self, field, span);
+        }
+        for declarator in &field.declarators {
+            let pointer = match declarator.node.declarator {
+                Some(ref d) => self.pointer || has_pointer(&d.node),
+                None => self.pointer,
+            };
+            let owner = self.owner.clone();
+            self.within(owner, pointer, |c| {
+                for specifier in &field.specifiers {
+                    c.visit_specifier_qualifier(&specifier.node, &specifier.span);
+                }
+                c.visit_struct_declarator(&declarator.node, &declarator.span);
+            });
+        }
+    }
+
+    fn visit_struct_type(&mut self, struct_type: &'ast StructType, span: &'ast Span) {
+        let ty = match struct_type.identifier {
+            Some(ref i) => {
+                let name = i.node.name.clone();
+                match struct_type.kind.node {
+                    StructKind::Struct => NamedType::Struct(name),
+                    StructKind::Union => NamedType::Union(name),
+                }
+            }
+            None => return visit::visit_struct_type(self, struct_type, span),
+        };
+        self.depend(ty.clone(), *span);
+        if struct_type.declarations.is_some() {
+            self.define(ty.clone(), *span);
+            self.within(Some(ty), false, |c| {
+                visit::visit_struct_type(c, struct_type, span)
+            });
+        }
+    }
+
+    fn visit_enum_type(&mut self, enum_type: &'ast EnumType, span: &'ast Span) {
+        let ty = match enum_type.identifier {
+            Some(ref i) => NamedType::Enum(i.node.name.clone()),
+            None => return visit::visit_enum_type(self, enum_type, span),
+        };
+        self.depend(ty.clone(), *span);
+        if !enum_type.enumerators.is_empty() {
+            self.define(ty.clone(), *span);
+            self.within(Some(ty), false, |c| {
+                visit::visit_enum_type(c, enum_type, span)
+            });
+        }
+    }
+
+    fn visit_type_specifier(&mut self, type_specifier: &'ast TypeSpecifier, span: &'ast Span) {
+        if let TypeSpecifier::TypedefName(ref i) = *type_specifier {
+            self.depend(NamedType::Typedef(i.node.name.clone()), *span);
+        }
+        visit::visit_type_specifier(self, type_specifier, span);
+    }
+}
+
+fn declarator_name(d: &Declarator) -> Option<&str> {
+    match d.kind.node {
+        DeclaratorKind::Abstract => None,
+        DeclaratorKind::Identifier(ref i) => Some(&i.node.name),
+        DeclaratorKind::Declarator(ref d) => declarator_name(&d.node),
+    }
+}
+
+fn has_pointer(d: &Declarator) -> bool {
+    for derived in &d.derived {
+        if let DerivedDeclarator::Pointer(_) = derived.node {
+            return true;
+        }
+    }
+    match d.kind.node {
+        DeclaratorKind::Declarator(ref d) => has_pointer(&d.node),
+        _ => false,
+    }
+}
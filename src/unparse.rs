@@ -0,0 +1,1377 @@
+//! Print an abstract syntax tree back as C source
+//!
+//! `unparse` writes a translation unit as preprocessed C source that parses back into the same
+//! tree, up to spans. Comments, layout, redundant parentheses and alternative keyword spellings
+//! of the original source are not kept.
+//!
+//! `round_trip` checks that promise on a parsed file, which makes a quick smoke test of the
+//! parser on a code base: any divergence points at a construct the parser or the printer gets
+//! wrong.
+//!
+//! ```
+//! use lang_c::driver::{parse_preprocessed, Config};
+//! use lang_c::unparse::{round_trip, unparse};
+//!
+//! let config = Config::default();
+//! let source = "int  max(int a,int b){return (a>b)?a:(b);}";
+//! let parse = parse_preprocessed(&config, source.into()).unwrap();
+//! assert_eq!(
+//!     unparse(&config, &parse.unit),
+//!     "int max(int a, int b) {\n    return a > b ? a : b;\n}\n"
+//! );
+//! round_trip(&config, &parse).unwrap();
+//! ```
+
+use std::error;
+use std::fmt;
+use std::slice;
+
+use ast::*;
+use driver::{parse_preprocessed, Config, Flavor, Parse, SyntaxError};
+use span::{Node, Span};
+
+/// Print a translation unit as C source
+///
+/// Keywords are written in the spelling accepted by the language flavor of `config`. Without GNU
+/// extensions, `offsetof` and `va_arg` are written as the standard macros, which parse back with
+/// `Config::builtin_macros`.
+pub fn unparse(config: &Config, unit: &TranslationUnit) -> String {
+    let mut u = Unparser {
+        out: String::new(),
+        indent: 0,
+        c23: config.c23,
+        gnu: config.flavor != Flavor::StdC11,
+    };
+    for external in &unit.0 {
+        u.external_declaration(&external.node);
+        u.out.push('\n');
+    }
+    u.out
+}
+
+/// Print an expression as C source
+pub fn unparse_expression(config: &Config, expression: &Node<Expression>) -> String {
+    let mut u = Unparser {
+        out: String::new(),
+        indent: 0,
+        c23: config.c23,
+        gnu: config.flavor != Flavor::StdC11,
+    };
+    u.expression(expression, COMMA);
+    u.out
+}
+
+/// Print a statement as C source
+pub fn unparse_statement(config: &Config, statement: &Node<Statement>) -> String {
+    let mut u = Unparser {
+        out: String::new(),
+        indent: 0,
+        c23: config.c23,
+        gnu: config.flavor != Flavor::StdC11,
+    };
+    u.statement(&statement.node);
+    u.out
+}
+
+/// Print a parsed file, parse the output again and compare the two trees ignoring spans
+///
+/// The printed source is parsed with `config`, which should be the configuration `parse` was
+/// produced with.
+pub fn round_trip(config: &Config, parse: &Parse) -> Result<(), RoundTripError> {
+    let printed = unparse(config, &parse.unit);
+    let reparsed = match parse_preprocessed(config, printed) {
+        Ok(reparsed) => reparsed,
+        Err(e) => return Err(RoundTripError::SyntaxError(e)),
+    };
+
+    let expected = format!("{:#?}", parse.unit);
+    let found = format!("{:#?}", reparsed.unit);
+    match divergence(&expected, &found) {
+        None => Ok(()),
+        Some((line, original, printed)) => Err(RoundTripError::Divergence(Divergence {
+            printed: reparsed.source,
+            original,
+            reprinted: printed,
+            expected: expected.lines().nth(line).unwrap_or("").trim().into(),
+            found: found.lines().nth(line).unwrap_or("").trim().into(),
+        })),
+    }
+}
+
+/// Error type returned from `round_trip`
+#[derive(Debug)]
+pub enum RoundTripError {
+    /// Printed source could not be parsed
+    SyntaxError(SyntaxError),
+    /// Printed source parsed into a different tree
+    Divergence(Divergence),
+}
+
+impl fmt::Display for RoundTripError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RoundTripError::SyntaxError(e) => write!(fmt, "printed source: {}", e),
+            RoundTripError::Divergence(d) => write!(
+                fmt,
+                "printed source diverges at {}…{}: expected `{}`, found `{}`",
+                d.original.start, d.original.end, d.expected, d.found
+            ),
+        }
+    }
+}
+
+impl error::Error for RoundTripError {
+    fn description(&self) -> &str {
+        match *self {
+            RoundTripError::SyntaxError(_) => "printed source syntax error",
+            RoundTripError::Divergence(_) => "printed source divergence",
+        }
+    }
+}
+
+/// First difference between the original tree and the tree parsed from the printed source
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    /// Source text printed from the original tree
+    pub printed: String,
+    /// Innermost node of the original source containing the difference
+    pub original: Span,
+    /// Innermost node of the printed source containing the difference
+    pub reprinted: Span,
+    /// Differing line of the original tree's debug dump
+    pub expected: String,
+    /// Differing line of the reparsed tree's debug dump
+    pub found: String,
+}
+
+/// Compare two pretty-printed debug dumps ignoring span values
+///
+/// Returns the first differing line and the spans of the innermost nodes containing it.
+fn divergence(expected: &str, found: &str) -> Option<(usize, Span, Span)> {
+    let a: Vec<&str> = expected.lines().collect();
+    let b: Vec<&str> = found.lines().collect();
+    let same = |x: &str, y: &str| x == y || (is_span(x) && is_span(y));
+    (0..a.len().max(b.len()))
+        .find(|&i| match (a.get(i), b.get(i)) {
+            (Some(x), Some(y)) => !same(x, y),
+            _ => true,
+        })
+        .map(|line| (line, enclosing_span(&a, line), enclosing_span(&b, line)))
+}
+
+fn is_span(line: &str) -> bool {
+    line.trim_left().starts_with("span: ")
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_left().len()
+}
+
+/// Span of the innermost `Node` whose debug dump contains the given line
+///
+/// `span` is the last field of a node, so it is the first span field after the line that is not
+/// nested deeper than the line itself.
+fn enclosing_span(lines: &[&str], line: usize) -> Span {
+    let indent = match lines.get(line) {
+        Some(l) => indentation(l),
+        None => return Span::none(),
+    };
+    for l in &lines[line..] {
+        if is_span(l) && indentation(l) <= indent {
+            return parse_span(l.trim_left()["span: ".len()..].trim_right_matches(','));
+        }
+    }
+    Span::none()
+}
+
+fn parse_span(s: &str) -> Span {
+    let mut parts = s.split('…');
+    match (parts.next(), parts.next()) {
+        (Some(start), Some(end)) => match (start.parse(), end.parse()) {
+            (Ok(start), Ok(end)) => Span::span(start, end),
+            _ => Span::none(),
+        },
+        _ => Span::none(),
+    }
+}
+
+// Expression precedence levels, from loosest to tightest binding
+const COMMA: u8 = 0;
+const ASSIGNMENT: u8 = 1;
+const CONDITIONAL: u8 = 2;
+const CAST: u8 = 13;
+const UNARY: u8 = 14;
+const POSTFIX: u8 = 15;
+const PRIMARY: u8 = 16;
+
+fn binary_precedence(op: &BinaryOperator) -> u8 {
+    match *op {
+        BinaryOperator::Index => POSTFIX,
+        BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => 12,
+        BinaryOperator::Plus | BinaryOperator::Minus => 11,
+        BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => 10,
+        BinaryOperator::Less
+        | BinaryOperator::Greater
+        | BinaryOperator::LessOrEqual
+        | BinaryOperator::GreaterOrEqual => 9,
+        BinaryOperator::Equals | BinaryOperator::NotEquals => 8,
+        BinaryOperator::BitwiseAnd => 7,
+        BinaryOperator::BitwiseXor => 6,
+        BinaryOperator::BitwiseOr => 5,
+        BinaryOperator::LogicalAnd => 4,
+        BinaryOperator::LogicalOr => 3,
+        _ => ASSIGNMENT,
+    }
+}
+
+fn precedence(e: &Expression) -> u8 {
+    match *e {
+        Expression::Comma(_) => COMMA,
+        Expression::Conditional(_) => CONDITIONAL,
+        Expression::BinaryOperator(ref b) => binary_precedence(&b.node.operator.node),
+        Expression::Cast(_) => CAST,
+        Expression::SizeOf(_) | Expression::AlignOf(_) => UNARY,
+        Expression::UnaryOperator(ref u) => match u.node.operator.node {
+            UnaryOperator::PostIncrement | UnaryOperator::PostDecrement => POSTFIX,
+            _ => UNARY,
+        },
+        Expression::Member(_) | Expression::Call(_) | Expression::CompoundLiteral(_) => POSTFIX,
+        _ => PRIMARY,
+    }
+}
+
+fn binary_operator(op: &BinaryOperator) -> &'static str {
+    match *op {
+        BinaryOperator::Index => "[]",
+        BinaryOperator::Multiply => "*",
+        BinaryOperator::Divide => "/",
+        BinaryOperator::Modulo => "%",
+        BinaryOperator::Plus => "+",
+        BinaryOperator::Minus => "-",
+        BinaryOperator::ShiftLeft => "<<",
+        BinaryOperator::ShiftRight => ">>",
+        BinaryOperator::Less => "<",
+        BinaryOperator::Greater => ">",
+        BinaryOperator::LessOrEqual => "<=",
+        BinaryOperator::GreaterOrEqual => ">=",
+        BinaryOperator::Equals => "==",
+        BinaryOperator::NotEquals => "!=",
+        BinaryOperator::BitwiseAnd => "&",
+        BinaryOperator::BitwiseXor => "^",
+        BinaryOperator::BitwiseOr => "|",
+        BinaryOperator::LogicalAnd => "&&",
+        BinaryOperator::LogicalOr => "||",
+        BinaryOperator::Assign => "=",
+        BinaryOperator::AssignMultiply => "*=",
+        BinaryOperator::AssignDivide => "/=",
+        BinaryOperator::AssignModulo => "%=",
+        BinaryOperator::AssignPlus => "+=",
+        BinaryOperator::AssignMinus => "-=",
+        BinaryOperator::AssignShiftLeft => "<<=",
+        BinaryOperator::AssignShiftRight => ">>=",
+        BinaryOperator::AssignBitwiseAnd => "&=",
+        BinaryOperator::AssignBitwiseXor => "^=",
+        BinaryOperator::AssignBitwiseOr => "|=",
+    }
+}
+
+/// Attribute specifier syntax an extension is written in
+#[derive(PartialEq)]
+enum Syntax {
+    Gnu,
+    Standard,
+    AsmLabel,
+    Marker,
+}
+
+fn syntax(e: &Extension) -> Syntax {
+    match *e {
+        Extension::Attribute(_) | Extension::AvailabilityAttribute(_) => Syntax::Gnu,
+        Extension::StandardAttribute(_) => Syntax::Standard,
+        Extension::AsmLabel(_) | Extension::AsmRegister(_) => Syntax::AsmLabel,
+        Extension::Marker => Syntax::Marker,
+    }
+}
+
+/// Split declarator extensions into GNU attributes before the pointers, standard attributes
+/// after the identifier and the asm label and attributes following the declarator
+///
+/// Returns the end of the first two groups. The parser concatenates the three groups, so only
+/// GNU attributes in front of a standard attribute or an asm label are known to lead. A lone
+/// group of GNU attributes is put at the end where the grammar allows it, as attributes in front
+/// of the first declarator would be taken for declaration specifiers.
+fn split_extensions(extensions: &[Node<Extension>], trailing: bool) -> (usize, usize) {
+    let leading = match extensions
+        .iter()
+        .position(|e| syntax(&e.node) != Syntax::Gnu)
+    {
+        Some(leading) => leading,
+        None if trailing => return (0, 0),
+        None => return (extensions.len(), extensions.len()),
+    };
+    let standard = extensions[leading..]
+        .iter()
+        .position(|e| syntax(&e.node) != Syntax::Standard)
+        .map_or(extensions.len(), |n| leading + n);
+    (leading, standard)
+}
+
+struct Unparser {
+    out: String,
+    indent: usize,
+    c23: bool,
+    gnu: bool,
+}
+
+impl Unparser {
+    /// Append a token, separated from the previous one where they would run together
+    fn token(&mut self, s: &str) {
+        let mut s = s;
+        if self.out.is_empty() || self.out.ends_with(' ') || self.out.ends_with('\n') {
+            s = s.trim_left_matches(' ');
+        }
+        if s.starts_with(|c| ";,)]".contains(c)) {
+            let end = self.out.trim_right_matches(' ').len();
+            if !self.out[..end].ends_with('\n') {
+                self.out.truncate(end);
+            }
+        }
+        if let (Some(a), Some(b)) = (self.out.chars().last(), s.chars().next()) {
+            let word = |c: char| c.is_alphanumeric() || c == '_';
+            let punct = |c: char| "+-*/%&|^<>=!.:?#".contains(c);
+            if (word(a) && word(b)) || (punct(a) && punct(b) && (a, b) != ('*', '*')) {
+                self.out.push(' ');
+            }
+        }
+        self.out.push_str(s);
+    }
+
+    fn line(&mut self) {
+        self.out.push('\n');
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+    }
+
+    fn external_declaration(&mut self, e: &ExternalDeclaration) {
+        match *e {
+            ExternalDeclaration::Declaration(ref d) => self.declaration(&d.node),
+            ExternalDeclaration::StaticAssert(ref s) => self.static_assert(&s.node),
+            ExternalDeclaration::FunctionDefinition(ref f) => self.function_definition(&f.node),
+            ExternalDeclaration::LinkageSpecification(ref l) => self.linkage_specification(&l.node),
+        }
+    }
+
+    /// Print a linkage specification, leaving the enclosed declarations unindented
+    fn linkage_specification(&mut self, l: &LinkageSpecification) {
+        self.token("extern ");
+        self.string_literal(&l.language.node);
+        if !l.braced {
+            self.token(" ");
+            for d in &l.declarations {
+                self.external_declaration(&d.node);
+            }
+            return;
+        }
+        self.token(" {");
+        for d in &l.declarations {
+            self.line();
+            self.external_declaration(&d.node);
+        }
+        self.line();
+        self.token("}");
+    }
+
+    fn function_definition(&mut self, f: &FunctionDefinition) {
+        self.declaration_specifiers(&f.specifiers);
+        self.declarator(&f.declarator.node, false);
+        for d in &f.declarations {
+            self.line();
+            self.declaration(&d.node);
+        }
+        if f.declarations.is_empty() {
+            self.token(" ");
+        } else {
+            self.line();
+        }
+        self.statement(&f.statement.node);
+    }
+
+    // Declarations
+
+    fn declaration(&mut self, d: &Declaration) {
+        self.declaration_specifiers(&d.specifiers);
+        for (i, init) in d.declarators.iter().enumerate() {
+            if i > 0 {
+                self.token(", ");
+            }
+            let trailing = self.declarator(&init.node.declarator.node, true);
+            self.extensions(trailing);
+            if let Some(ref i) = init.node.initializer {
+                self.token(" = ");
+                self.initializer(&i.node);
+            }
+        }
+        self.token(";");
+    }
+
+    fn declaration_specifiers(&mut self, specifiers: &[Node<DeclarationSpecifier>]) {
+        for s in specifiers {
+            match s.node {
+                DeclarationSpecifier::StorageClass(ref s) => {
+                    self.token(storage_class(&s.node));
+                }
+                DeclarationSpecifier::TypeSpecifier(ref t) => self.type_specifier(&t.node),
+                DeclarationSpecifier::TypeQualifier(ref q) => self.token(type_qualifier(&q.node)),
+                DeclarationSpecifier::Function(ref f) => self.token(match f.node {
+                    FunctionSpecifier::Inline => "inline",
+                    FunctionSpecifier::Noreturn => "_Noreturn",
+                }),
+                DeclarationSpecifier::Alignment(ref a) => {
+                    self.token("_Alignas(");
+                    match a.node {
+                        AlignmentSpecifier::Type(ref t) => self.type_name(&t.node),
+                        AlignmentSpecifier::Constant(ref e) => self.expression(e, CONDITIONAL),
+                    }
+                    self.token(")");
+                }
+                DeclarationSpecifier::Extension(ref e) => self.extensions(slice::from_ref(e)),
+            }
+            self.token(" ");
+        }
+    }
+
+    fn specifier_qualifiers(&mut self, specifiers: &[Node<SpecifierQualifier>]) {
+        for (i, s) in specifiers.iter().enumerate() {
+            if i > 0 {
+                self.token(" ");
+            }
+            match s.node {
+                SpecifierQualifier::TypeSpecifier(ref t) => self.type_specifier(&t.node),
+                SpecifierQualifier::TypeQualifier(ref q) => self.token(type_qualifier(&q.node)),
+                SpecifierQualifier::Extension(ref e) => self.extensions(slice::from_ref(e)),
+            }
+        }
+    }
+
+    fn type_specifier(&mut self, t: &TypeSpecifier) {
+        match *t {
+            TypeSpecifier::Void => self.token("void"),
+            TypeSpecifier::Char => self.token("char"),
+            TypeSpecifier::Short => self.token("short"),
+            TypeSpecifier::Int => self.token("int"),
+            TypeSpecifier::Long => self.token("long"),
+            TypeSpecifier::Int128 => self.token("__int128"),
+            TypeSpecifier::Float128 => self.token("__float128"),
+            TypeSpecifier::AutoType => self.token("__auto_type"),
+            TypeSpecifier::Float => self.token("float"),
+            TypeSpecifier::Double => self.token("double"),
+            TypeSpecifier::Signed => self.token("signed"),
+            TypeSpecifier::Unsigned => self.token("unsigned"),
+            TypeSpecifier::Bool => self.token("_Bool"),
+            TypeSpecifier::Complex => self.token("_Complex"),
+            TypeSpecifier::Atomic(ref t) => {
+                self.token("_Atomic(");
+                self.type_name(&t.node);
+                self.token(")");
+            }
+            TypeSpecifier::Struct(ref s) => self.struct_type(&s.node),
+            TypeSpecifier::Enum(ref e) => self.enum_type(&e.node),
+            TypeSpecifier::TypedefName(ref i) => self.token(&i.node.name),
+            TypeSpecifier::TypeOf(ref t) => {
+                self.token("typeof(");
+                self.type_of(&t.node);
+                self.token(")");
+            }
+            TypeSpecifier::TypeOfUnqual(ref t) => {
+                self.token(if self.c23 {
+                    "typeof_unqual("
+                } else {
+                    "__typeof_unqual__("
+                });
+                self.type_of(&t.node);
+                self.token(")");
+            }
+            TypeSpecifier::TS18661Float(ref f) => {
+                let name = match f.format {
+                    TS18661FloatFormat::BinaryInterchange => format!("_Float{}", f.width),
+                    TS18661FloatFormat::BinaryExtended => format!("_Float{}x", f.width),
+                    TS18661FloatFormat::DecimalInterchange => format!("_Decimal{}", f.width),
+                    TS18661FloatFormat::DecimalExtended => format!("_Decimal{}x", f.width),
+                };
+                self.token(&name);
+            }
+            TypeSpecifier::BitInt(ref e) => {
+                self.token("_BitInt(");
+                self.expression(e, CONDITIONAL);
+                self.token(")");
+            }
+        }
+    }
+
+    fn type_of(&mut self, t: &TypeOf) {
+        match *t {
+            TypeOf::Expression(ref e) => self.expression(e, COMMA),
+            TypeOf::Type(ref t) => self.type_name(&t.node),
+        }
+    }
+
+    fn struct_type(&mut self, s: &StructType) {
+        self.token(match s.kind.node {
+            StructKind::Struct => "struct",
+            StructKind::Union => "union",
+        });
+        self.extensions(&s.extensions);
+        if let Some(ref i) = s.identifier {
+            self.token(" ");
+            self.token(&i.node.name);
+        }
+        let declarations = match s.declarations {
+            Some(ref declarations) => declarations,
+            None => return,
+        };
+        self.token(" {");
+        self.indent += 1;
+        for d in declarations {
+            self.line();
+            match d.node {
+                StructDeclaration::Field(ref f) => self.struct_field(&f.node),
+                StructDeclaration::StaticAssert(ref s) => self.static_assert(&s.node),
+            }
+        }
+        self.indent -= 1;
+        if !declarations.is_empty() {
+            self.line();
+        }
+        self.token("}");
+    }
+
+    fn struct_field(&mut self, f: &StructField) {
+        self.specifier_qualifiers(&f.specifiers);
+        for (i, d) in f.declarators.iter().enumerate() {
+            self.token(if i > 0 { ", " } else { " " });
+            let trailing = match d.node.declarator {
+                Some(ref d) => self.declarator(&d.node, true),
+                None => &[],
+            };
+            if let Some(ref e) = d.node.bit_width {
+                self.token(" : ");
+                self.expression(e, CONDITIONAL);
+            }
+            self.extensions(trailing);
+        }
+        self.token(";");
+    }
+
+    fn enum_type(&mut self, e: &EnumType) {
+        self.token("enum");
+        self.extensions(&e.extensions);
+        if let Some(ref i) = e.identifier {
+            self.token(" ");
+            self.token(&i.node.name);
+        }
+        if let Some(ref underlying) = e.underlying {
+            self.token(" : ");
+            self.specifier_qualifiers(underlying);
+        }
+        if e.enumerators.is_empty() {
+            return;
+        }
+        self.token(" { ");
+        for (i, n) in e.enumerators.iter().enumerate() {
+            if i > 0 {
+                self.token(", ");
+            }
+            self.token(&n.node.identifier.node.name);
+            self.extensions(&n.node.extensions);
+            if let Some(ref e) = n.node.expression {
+                self.token(" = ");
+                self.expression(e, CONDITIONAL);
+            }
+        }
+        self.token(" }");
+    }
+
+    /// Print a declarator, returning the extensions left for the caller to print after it
+    fn declarator<'a>(&mut self, d: &'a Declarator, trailing: bool) -> &'a [Node<Extension>] {
+        let (leading, standard) = split_extensions(&d.extensions, trailing);
+        self.extensions(&d.extensions[..leading]);
+
+        let mut pointers = 0;
+        for p in &d.derived {
+            if let DerivedDeclarator::Pointer(_) = p.node {
+                pointers += 1;
+            } else {
+                break;
+            }
+        }
+        for p in &d.derived[..pointers] {
+            self.derived_declarator(&p.node);
+        }
+        match d.kind.node {
+            DeclaratorKind::Abstract => {}
+            DeclaratorKind::Identifier(ref i) => self.token(&i.node.name),
+            DeclaratorKind::Declarator(ref d) => {
+                self.token("(");
+                self.declarator(&d.node, false);
+                self.token(")");
+            }
+        }
+        self.extensions(&d.extensions[leading..standard]);
+        for s in &d.derived[pointers..] {
+            self.derived_declarator(&s.node);
+        }
+        &d.extensions[standard..]
+    }
+
+    fn derived_declarator(&mut self, d: &DerivedDeclarator) {
+        match *d {
+            DerivedDeclarator::Pointer(ref qualifiers) => {
+                self.token("*");
+                for q in qualifiers {
+                    match q.node {
+                        PointerQualifier::TypeQualifier(ref q) => {
+                            self.token(type_qualifier(&q.node))
+                        }
+                        PointerQualifier::Extension(ref e) => self.extension_group(e),
+                    }
+                    self.token(" ");
+                }
+            }
+            DerivedDeclarator::Array(ref a) => {
+                self.token("[");
+                if let ArraySize::StaticExpression(_) = a.node.size {
+                    self.token("static ");
+                }
+                for q in &a.node.qualifiers {
+                    self.token(type_qualifier(&q.node));
+                    self.token(" ");
+                }
+                match a.node.size {
+                    ArraySize::Unknown => {}
+                    ArraySize::VariableUnknown => self.token("*"),
+                    ArraySize::VariableExpression(ref e) | ArraySize::StaticExpression(ref e) => {
+                        self.expression(e, ASSIGNMENT)
+                    }
+                }
+                self.token("]");
+                self.extensions(&a.node.extensions);
+            }
+            DerivedDeclarator::Function(ref f) => {
+                self.token("(");
+                for (i, p) in f.node.parameters.iter().enumerate() {
+                    if i > 0 {
+                        self.token(", ");
+                    }
+                    self.parameter_declaration(&p.node);
+                }
+                match (
+                    f.node.ellipsis == Ellipsis::Some,
+                    f.node.parameters.is_empty(),
+                ) {
+                    (true, true) => self.token("..."),
+                    (true, false) => self.token(", ..."),
+                    (false, _) => {}
+                }
+                self.token(")");
+                self.extensions(&f.node.extensions);
+            }
+            DerivedDeclarator::KRFunction(ref identifiers) => {
+                self.token("(");
+                for (i, p) in identifiers.iter().enumerate() {
+                    if i > 0 {
+                        self.token(", ");
+                    }
+                    self.token(&p.node.name);
+                }
+                self.token(")");
+            }
+        }
+    }
+
+    fn parameter_declaration(&mut self, p: &ParameterDeclaration) {
+        self.declaration_specifiers(&p.specifiers);
+        if let Some(ref d) = p.declarator {
+            self.declarator(&d.node, false);
+        }
+        self.extensions(&p.extensions);
+    }
+
+    fn type_name(&mut self, t: &TypeName) {
+        self.specifier_qualifiers(&t.specifiers);
+        if let Some(ref d) = t.declarator {
+            match (&d.node.kind.node, d.node.derived.first().map(|d| &d.node)) {
+                (&DeclaratorKind::Declarator(_), _) | (_, Some(&DerivedDeclarator::Pointer(_))) => {
+                    self.token(" ")
+                }
+                _ => {}
+            }
+            self.declarator(&d.node, false);
+        }
+    }
+
+    fn initializer(&mut self, i: &Initializer) {
+        match *i {
+            Initializer::Expression(ref e) => self.expression(e, ASSIGNMENT),
+            Initializer::List(ref items) => self.initializer_list(items),
+        }
+    }
+
+    fn initializer_list(&mut self, items: &[Node<InitializerListItem>]) {
+        if items.is_empty() {
+            self.token("{}");
+            return;
+        }
+        self.token("{ ");
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.token(", ");
+            }
+            for d in &item.node.designation {
+                match d.node {
+                    Designator::Index(ref e) => {
+                        self.token("[");
+                        self.expression(e, CONDITIONAL);
+                        self.token("]");
+                    }
+                    Designator::Member(ref i) => {
+                        self.token(".");
+                        self.token(&i.node.name);
+                    }
+                    Designator::Range(ref r) => {
+                        self.token("[");
+                        self.expression(&r.node.from, CONDITIONAL);
+                        self.token(" ... ");
+                        self.expression(&r.node.to, CONDITIONAL);
+                        self.token("]");
+                    }
+                }
+            }
+            if !item.node.designation.is_empty() {
+                self.token(" = ");
+            }
+            self.initializer(&item.node.initializer.node);
+        }
+        self.token(" }");
+    }
+
+    fn static_assert(&mut self, s: &StaticAssert) {
+        for _ in &s.extensions {
+            self.token("__extension__ ");
+        }
+        self.token("_Static_assert(");
+        self.expression(&s.expression, CONDITIONAL);
+        if let Some(ref m) = s.message {
+            self.token(", ");
+            self.string_literal(&m.node);
+        }
+        self.token(");");
+    }
+
+    // Extensions
+
+    /// Print extensions one per attribute specifier
+    fn extensions(&mut self, extensions: &[Node<Extension>]) {
+        for e in extensions {
+            self.token(" ");
+            self.extension_group(slice::from_ref(e));
+            self.token(" ");
+        }
+    }
+
+    /// Print extensions of the same kind in a single attribute specifier
+    fn extension_group(&mut self, extensions: &[Node<Extension>]) {
+        let (open, close) = match extensions.first().map(|e| &e.node) {
+            Some(&Extension::AsmLabel(ref s)) | Some(&Extension::AsmRegister(ref s)) => {
+                self.token("__asm__(");
+                self.string_literal(&s.node);
+                self.token(")");
+                return;
+            }
+            Some(&Extension::Marker) => {
+                self.token("__extension__");
+                return;
+            }
+            Some(&Extension::StandardAttribute(_)) => ("[[", "]]"),
+            Some(_) => ("__attribute__((", "))"),
+            None => return,
+        };
+        self.token(open);
+        for (i, e) in extensions.iter().enumerate() {
+            if i > 0 {
+                self.token(", ");
+            }
+            match e.node {
+                Extension::Attribute(ref a) => {
+                    self.token(&a.name.node);
+                    if !a.arguments.is_empty() {
+                        self.arguments(&a.arguments);
+                    }
+                }
+                Extension::StandardAttribute(ref a) => {
+                    if let Some(ref p) = a.prefix {
+                        self.token(&p.node);
+                        self.token("::");
+                    }
+                    self.token(&a.name.node);
+                    if let Some(ref arguments) = a.arguments {
+                        self.arguments(arguments);
+                    }
+                }
+                Extension::AvailabilityAttribute(ref a) => self.availability(&a.node),
+                Extension::AsmLabel(_) | Extension::AsmRegister(_) | Extension::Marker => {}
+            }
+        }
+        self.token(close);
+    }
+
+    fn availability(&mut self, a: &AvailabilityAttribute) {
+        self.token("availability(");
+        self.token(&a.platform.node.name);
+        for c in &a.clauses {
+            self.token(", ");
+            let version = match c.node {
+                AvailabilityClause::Introduced(ref v) => ("introduced=", v),
+                AvailabilityClause::Deprecated(ref v) => ("deprecated=", v),
+                AvailabilityClause::Obsoleted(ref v) => ("obsoleted=", v),
+                AvailabilityClause::Unavailable => {
+                    self.token("unavailable");
+                    continue;
+                }
+                AvailabilityClause::Message(ref s) => {
+                    self.token("message=");
+                    self.string_literal(&s.node);
+                    continue;
+                }
+                AvailabilityClause::Replacement(ref s) => {
+                    self.token("replacement=");
+                    self.string_literal(&s.node);
+                    continue;
+                }
+            };
+            self.token(version.0);
+            let v = &version.1.node;
+            self.token(&v.major);
+            for part in v.minor.iter().chain(v.subminor.iter()) {
+                self.token(".");
+                self.token(part);
+            }
+        }
+        self.token(")");
+    }
+
+    // Statements
+
+    fn statement(&mut self, s: &Statement) {
+        match *s {
+            Statement::Labeled(ref l) => {
+                match l.node.label.node {
+                    Label::Identifier(ref i) => self.token(&i.node.name),
+                    Label::Case(ref e) => {
+                        self.token("case ");
+                        self.expression(e, CONDITIONAL);
+                    }
+                    Label::Default => self.token("default"),
+                }
+                self.token(": ");
+                self.statement(&l.node.statement.node);
+            }
+            Statement::Compound(ref items) => {
+                self.token("{");
+                self.indent += 1;
+                for item in items {
+                    self.line();
+                    match item.node {
+                        BlockItem::Declaration(ref d) => self.declaration(&d.node),
+                        BlockItem::StaticAssert(ref s) => self.static_assert(&s.node),
+                        BlockItem::Statement(ref s) => self.statement(&s.node),
+                        BlockItem::FunctionDefinition(ref f) => self.function_definition(&f.node),
+                        BlockItem::LocalLabels(ref labels) => {
+                            self.token("__label__ ");
+                            for (i, l) in labels.iter().enumerate() {
+                                if i > 0 {
+                                    self.token(", ");
+                                }
+                                self.token(&l.node.name);
+                            }
+                            self.token(";");
+                        }
+                    }
+                }
+                self.indent -= 1;
+                if !items.is_empty() {
+                    self.line();
+                }
+                self.token("}");
+            }
+            Statement::Expression(ref e) => {
+                if let Some(ref e) = *e {
+                    self.expression(e, COMMA);
+                }
+                self.token(";");
+            }
+            Statement::If(ref i) => {
+                self.token("if (");
+                self.expression(&i.node.condition, COMMA);
+                self.token(") ");
+                self.statement(&i.node.then_statement.node);
+                if let Some(ref e) = i.node.else_statement {
+                    self.token(" else ");
+                    self.statement(&e.node);
+                }
+            }
+            Statement::Switch(ref s) => {
+                self.token("switch (");
+                self.expression(&s.node.expression, COMMA);
+                self.token(") ");
+                self.statement(&s.node.statement.node);
+            }
+            Statement::While(ref w) => {
+                self.token("while (");
+                self.expression(&w.node.expression, COMMA);
+                self.token(") ");
+                self.statement(&w.node.statement.node);
+            }
+            Statement::DoWhile(ref d) => {
+                self.token("do ");
+                self.statement(&d.node.statement.node);
+                self.token(" while (");
+                self.expression(&d.node.expression, COMMA);
+                self.token(");");
+            }
+            Statement::For(ref f) => {
+                self.token("for (");
+                match f.node.initializer.node {
+                    ForInitializer::Empty => self.token(";"),
+                    ForInitializer::Expression(ref e) => {
+                        self.expression(e, COMMA);
+                        self.token(";");
+                    }
+                    ForInitializer::Declaration(ref d) => self.declaration(&d.node),
+                    ForInitializer::StaticAssert(ref s) => self.static_assert(&s.node),
+                }
+                if let Some(ref e) = f.node.condition {
+                    self.token(" ");
+                    self.expression(e, COMMA);
+                }
+                self.token(";");
+                if let Some(ref e) = f.node.step {
+                    self.token(" ");
+                    self.expression(e, COMMA);
+                }
+                self.token(") ");
+                self.statement(&f.node.statement.node);
+            }
+            Statement::Goto(ref i) => {
+                self.token("goto ");
+                self.token(&i.node.name);
+                self.token(";");
+            }
+            Statement::GotoIndirect(ref e) => {
+                self.token("goto *");
+                self.expression(e, CAST);
+                self.token(";");
+            }
+            Statement::Continue => self.token("continue;"),
+            Statement::Break => self.token("break;"),
+            Statement::Return(ref e) => {
+                self.token("return");
+                if let Some(ref e) = *e {
+                    self.token(" ");
+                    self.expression(e, COMMA);
+                }
+                self.token(";");
+            }
+            Statement::Asm(ref a) => self.asm_statement(&a.node),
+            Statement::Attributed(ref a) => {
+                self.extensions(&a.node.extensions);
+                self.statement(&a.node.statement.node);
+            }
+        }
+    }
+
+    fn asm_statement(&mut self, a: &AsmStatement) {
+        self.token("__asm__");
+        let e = match *a {
+            AsmStatement::GnuBasic(ref s) => {
+                self.token("(");
+                self.string_literal(&s.node);
+                self.token(");");
+                return;
+            }
+            AsmStatement::GnuExtended(ref e) => e,
+        };
+        if let Some(ref q) = e.qualifier {
+            self.token(" ");
+            self.token(type_qualifier(&q.node));
+        }
+        if e.goto {
+            self.token(" goto");
+        }
+        self.token(" (");
+        self.string_literal(&e.template.node);
+        let sections = if e.goto {
+            4
+        } else if !e.clobbers.is_empty() {
+            3
+        } else if !e.inputs.is_empty() {
+            2
+        } else {
+            1
+        };
+        for operands in [&e.outputs, &e.inputs].iter().take(sections) {
+            self.token(" :");
+            for (i, o) in operands.iter().enumerate() {
+                self.token(if i > 0 { ", " } else { " " });
+                if let Some(ref n) = o.node.symbolic_name {
+                    self.token("[");
+                    self.token(&n.node.name);
+                    self.token("] ");
+                }
+                self.string_literal(&o.node.constraints.node);
+                self.token(" (");
+                self.expression(&o.node.variable_name, COMMA);
+                self.token(")");
+            }
+        }
+        if sections >= 3 {
+            self.token(" :");
+            for (i, c) in e.clobbers.iter().enumerate() {
+                self.token(if i > 0 { ", " } else { " " });
+                self.string_literal(&c.node);
+            }
+        }
+        if sections == 4 {
+            self.token(" :");
+            for (i, l) in e.labels.iter().enumerate() {
+                self.token(if i > 0 { ", " } else { " " });
+                self.token(&l.node.name);
+            }
+        }
+        self.token(");");
+    }
+
+    // Expressions
+
+    /// Print an expression, parenthesized if it binds looser than `min`
+    fn expression(&mut self, e: &Node<Expression>, min: u8) {
+        if precedence(&e.node) < min {
+            self.token("(");
+            self.expression_inner(&e.node);
+            self.token(")");
+        } else {
+            self.expression_inner(&e.node);
+        }
+    }
+
+    fn expression_inner(&mut self, e: &Expression) {
+        match *e {
+            Expression::Identifier(ref i) => self.token(&i.node.name),
+            Expression::Constant(ref c) => self.constant(&c.node),
+            Expression::StringLiteral(ref s) => self.string_literal(&s.node),
+            Expression::GenericSelection(ref g) => {
+                self.token("_Generic(");
+                self.expression(&g.node.expression, ASSIGNMENT);
+                for a in &g.node.associations {
+                    self.token(", ");
+                    match a.node {
+                        GenericAssociation::Type(ref t) => {
+                            self.type_name(&t.node.type_name.node);
+                            self.token(": ");
+                            self.expression(&t.node.expression, ASSIGNMENT);
+                        }
+                        GenericAssociation::Default(ref e) => {
+                            self.token("default: ");
+                            self.expression(e, ASSIGNMENT);
+                        }
+                    }
+                }
+                self.token(")");
+            }
+            Expression::Member(ref m) => {
+                self.expression(&m.node.expression, POSTFIX);
+                self.token(match m.node.operator.node {
+                    MemberOperator::Direct => ".",
+                    MemberOperator::Indirect => "->",
+                });
+                self.token(&m.node.identifier.node.name);
+            }
+            Expression::Call(ref c) => {
+                self.expression(&c.node.callee, POSTFIX);
+                self.arguments(&c.node.arguments);
+            }
+            Expression::CompoundLiteral(ref c) => {
+                self.token("(");
+                self.type_name(&c.node.type_name.node);
+                self.token(")");
+                self.initializer_list(&c.node.initializer_list);
+            }
+            Expression::SizeOf(ref t) => {
+                self.token("sizeof(");
+                self.type_name(&t.node);
+                self.token(")");
+            }
+            Expression::AlignOf(ref t) => {
+                self.token("_Alignof(");
+                self.type_name(&t.node);
+                self.token(")");
+            }
+            Expression::UnaryOperator(ref u) => {
+                let operand = &u.node.operand;
+                match u.node.operator.node {
+                    UnaryOperator::PostIncrement => {
+                        self.expression(operand, POSTFIX);
+                        self.token("++");
+                    }
+                    UnaryOperator::PostDecrement => {
+                        self.expression(operand, POSTFIX);
+                        self.token("--");
+                    }
+                    // A parenthesized operand keeps compound literals from being read as a
+                    // type name operand
+                    UnaryOperator::SizeOf => {
+                        self.token("sizeof(");
+                        self.expression(operand, COMMA);
+                        self.token(")");
+                    }
+                    UnaryOperator::AlignOf => {
+                        self.token("__alignof__(");
+                        self.expression(operand, COMMA);
+                        self.token(")");
+                    }
+                    ref op => {
+                        let (token, min) = match *op {
+                            UnaryOperator::PreIncrement => ("++", UNARY),
+                            UnaryOperator::PreDecrement => ("--", UNARY),
+                            UnaryOperator::Address => ("&", CAST),
+                            UnaryOperator::Indirection => ("*", CAST),
+                            UnaryOperator::Plus => ("+", CAST),
+                            UnaryOperator::Minus => ("-", CAST),
+                            UnaryOperator::Complement => ("~", CAST),
+                            UnaryOperator::Real => ("__real__ ", CAST),
+                            UnaryOperator::Imag => ("__imag__ ", CAST),
+                            UnaryOperator::Extension => ("__extension__ ", CAST),
+                            _ => ("!", CAST),
+                        };
+                        self.token(token);
+                        self.expression(operand, min);
+                    }
+                }
+            }
+            Expression::Cast(ref c) => {
+                self.token("(");
+                self.type_name(&c.node.type_name.node);
+                self.token(")");
+                self.expression(&c.node.expression, CAST);
+            }
+            Expression::BinaryOperator(ref b) => {
+                let op = &b.node.operator.node;
+                let p = binary_precedence(op);
+                if *op == BinaryOperator::Index {
+                    self.expression(&b.node.lhs, POSTFIX);
+                    self.token("[");
+                    self.expression(&b.node.rhs, COMMA);
+                    self.token("]");
+                } else if p == ASSIGNMENT {
+                    self.expression(&b.node.lhs, UNARY);
+                    self.token(&format!(" {} ", binary_operator(op)));
+                    self.expression(&b.node.rhs, ASSIGNMENT);
+                } else {
+                    self.expression(&b.node.lhs, p);
+                    self.token(&format!(" {} ", binary_operator(op)));
+                    self.expression(&b.node.rhs, p + 1);
+                }
+            }
+            Expression::Conditional(ref c) => {
+                self.expression(&c.node.condition, CONDITIONAL + 1);
+                match c.node.then_expression {
+                    Some(ref e) => {
+                        self.token(" ? ");
+                        self.expression(e, COMMA);
+                        self.token(" : ");
+                    }
+                    None => self.token(" ?: "),
+                }
+                self.expression(&c.node.else_expression, CONDITIONAL);
+            }
+            Expression::Comma(ref items) => {
+                for (i, e) in items.iter().enumerate() {
+                    if i > 0 {
+                        self.token(", ");
+                    }
+                    self.expression(e, ASSIGNMENT);
+                }
+            }
+            Expression::OffsetOf(ref o) => {
+                self.token(if self.gnu {
+                    "__builtin_offsetof("
+                } else {
+                    "offsetof("
+                });
+                self.type_name(&o.node.type_name.node);
+                self.token(", ");
+                self.token(&o.node.designator.node.base.node.name);
+                for m in &o.node.designator.node.members {
+                    match m.node {
+                        OffsetMember::Member(ref i) => {
+                            self.token(".");
+                            self.token(&i.node.name);
+                        }
+                        OffsetMember::IndirectMember(ref i) => {
+                            self.token("->");
+                            self.token(&i.node.name);
+                        }
+                        OffsetMember::Index(ref e) => {
+                            self.token("[");
+                            self.expression(e, COMMA);
+                            self.token("]");
+                        }
+                    }
+                }
+                self.token(")");
+            }
+            Expression::VaArg(ref v) => {
+                self.token(if self.gnu {
+                    "__builtin_va_arg("
+                } else {
+                    "va_arg("
+                });
+                self.expression(&v.node.va_list, ASSIGNMENT);
+                self.token(", ");
+                self.type_name(&v.node.type_name.node);
+                self.token(")");
+            }
+            Expression::TypesCompatible(ref t) => {
+                self.token("__builtin_types_compatible_p(");
+                self.type_name(&t.node.lhs.node);
+                self.token(", ");
+                self.type_name(&t.node.rhs.node);
+                self.token(")");
+            }
+            Expression::Choose(ref c) => {
+                self.token("__builtin_choose_expr(");
+                self.expression(&c.node.condition, ASSIGNMENT);
+                self.token(", ");
+                self.expression(&c.node.then_expression, ASSIGNMENT);
+                self.token(", ");
+                self.expression(&c.node.else_expression, ASSIGNMENT);
+                self.token(")");
+            }
+            Expression::Statement(ref s) => {
+                self.token("(");
+                self.statement(&s.node);
+                self.token(")");
+            }
+            Expression::LabelAddress(ref l) => {
+                self.token("&&");
+                self.token(&l.node.name);
+            }
+        }
+    }
+
+    fn arguments(&mut self, arguments: &[Node<Expression>]) {
+        self.token("(");
+        for (i, a) in arguments.iter().enumerate() {
+            if i > 0 {
+                self.token(", ");
+            }
+            self.expression(a, ASSIGNMENT);
+        }
+        self.token(")");
+    }
+
+    fn constant(&mut self, c: &Constant) {
+        let text = match *c {
+            Constant::Integer(ref i) => {
+                let mut text = String::from(match i.base {
+                    IntegerBase::Decimal => "",
+                    IntegerBase::Octal => "0",
+                    IntegerBase::Hexadecimal => "0x",
+                    IntegerBase::Binary => "0b",
+                });
+                text.push_str(&i.number);
+                if i.suffix.unsigned {
+                    text.push('u');
+                }
+                text.push_str(match i.suffix.size {
+                    IntegerSize::Int => "",
+                    IntegerSize::Long => "l",
+                    IntegerSize::LongLong => "ll",
+                    IntegerSize::BitInt => "wb",
+                });
+                if i.suffix.imaginary {
+                    text.push('i');
+                }
+                text
+            }
+            Constant::Float(ref f) => {
+                let mut text = String::from(match f.base {
+                    FloatBase::Decimal => "",
+                    FloatBase::Hexadecimal => "0x",
+                });
+                text.push_str(&f.number);
+                match f.suffix.format {
+                    FloatFormat::Float => text.push('f'),
+                    FloatFormat::Double => {}
+                    FloatFormat::LongDouble => text.push('l'),
+                    FloatFormat::TS18661Format(ref t) => text.push_str(&match t.format {
+                        TS18661FloatFormat::BinaryInterchange => format!("f{}", t.width),
+                        TS18661FloatFormat::BinaryExtended => format!("f{}x", t.width),
+                        TS18661FloatFormat::DecimalInterchange => format!("d{}", t.width),
+                        TS18661FloatFormat::DecimalExtended => format!("d{}x", t.width),
+                    }),
+                }
+                if f.suffix.imaginary {
+                    text.push('i');
+                }
+                text
+            }
+            Constant::Character(ref c) => {
+                let prefix = match c.encoding {
+                    CharacterEncoding::Plain => "",
+                    CharacterEncoding::Wide => "L",
+                    CharacterEncoding::Utf8 => "u8",
+                    CharacterEncoding::Utf16 => "u",
+                    CharacterEncoding::Utf32 => "U",
+                };
+                format!("{}'{}'", prefix, c.body)
+            }
+            Constant::Nullptr => "nullptr".into(),
+            Constant::Bool(b) => String::from(if b { "true" } else { "false" }),
+        };
+        self.token(&text);
+    }
+
+    fn string_literal(&mut self, s: &StringLiteral) {
+        for (i, part) in s.iter().enumerate() {
+            if i > 0 {
+                self.token(" ");
+            }
+            self.token(part);
+        }
+    }
+}
+
+fn storage_class(s: &StorageClassSpecifier) -> &'static str {
+    match *s {
+        StorageClassSpecifier::Typedef => "typedef",
+        StorageClassSpecifier::Extern => "extern",
+        StorageClassSpecifier::Static => "static",
+        StorageClassSpecifier::ThreadLocal => "_Thread_local",
+        StorageClassSpecifier::Auto => "auto",
+        StorageClassSpecifier::Register => "register",
+        StorageClassSpecifier::Constexpr => "constexpr",
+    }
+}
+
+fn type_qualifier(q: &TypeQualifier) -> &'static str {
+    match *q {
+        TypeQualifier::Const => "const",
+        TypeQualifier::Restrict => "restrict",
+        TypeQualifier::Volatile => "volatile",
+        TypeQualifier::Nonnull => "_Nonnull",
+        TypeQualifier::NullUnspecified => "_Null_unspecified",
+        TypeQualifier::Nullable => "_Nullable",
+        TypeQualifier::Atomic => "_Atomic",
+    }
+}
@@ -62,6 +62,18 @@ pub trait Visit<'ast> {
         visit_float_format(self, float_format, span)
     }
 
+    fn visit_character(&mut self, character: &'ast Character, span: &'ast Span) {
+        visit_character(self, character, span)
+    }
+
+    fn visit_character_encoding(
+        &mut self,
+        character_encoding: &'ast CharacterEncoding,
+        span: &'ast Span,
+    ) {
+        visit_character_encoding(self, character_encoding, span)
+    }
+
     fn visit_string_literal(&mut self, string_literal: &'ast StringLiteral, span: &'ast Span) {
         visit_string_literal(self, string_literal, span)
     }
@@ -162,6 +174,22 @@ pub trait Visit<'ast> {
         visit_va_arg_expression(self, va_arg_expression, span)
     }
 
+    fn visit_types_compatible_expression(
+        &mut self,
+        types_compatible_expression: &'ast TypesCompatibleExpression,
+        span: &'ast Span,
+    ) {
+        visit_types_compatible_expression(self, types_compatible_expression, span)
+    }
+
+    fn visit_choose_expression(
+        &mut self,
+        choose_expression: &'ast ChooseExpression,
+        span: &'ast Span,
+    ) {
+        visit_choose_expression(self, choose_expression, span)
+    }
+
     fn visit_offset_of_expression(
         &mut self,
         offset_of_expression: &'ast OffsetOfExpression,
@@ -390,6 +418,14 @@ pub trait Visit<'ast> {
         visit_labeled_statement(self, labeled_statement, span)
     }
 
+    fn visit_attributed_statement(
+        &mut self,
+        attributed_statement: &'ast AttributedStatement,
+        span: &'ast Span,
+    ) {
+        visit_attributed_statement(self, attributed_statement, span)
+    }
+
     fn visit_if_statement(&mut self, if_statement: &'ast IfStatement, span: &'ast Span) {
         visit_if_statement(self, if_statement, span)
     }
@@ -450,6 +486,14 @@ pub trait Visit<'ast> {
         visit_function_definition(self, function_definition, span)
     }
 
+    fn visit_linkage_specification(
+        &mut self,
+        linkage_specification: &'ast LinkageSpecification,
+        span: &'ast Span,
+    ) {
+        visit_linkage_specification(self, linkage_specification, span)
+    }
+
     fn visit_extension(&mut self, extension: &'ast Extension, span: &'ast Span) {
         visit_extension(self, extension, span)
     }
@@ -458,6 +502,10 @@ pub trait Visit<'ast> {
         visit_attribute(self, attribute, span)
     }
 
+    fn visit_standard_attribute(&mut self, attribute: &'ast StandardAttribute, span: &'ast Span) {
+        visit_standard_attribute(self, attribute, span)
+    }
+
     fn visit_asm_statement(&mut self, asm_statement: &'ast AsmStatement, span: &'ast Span) {
         visit_asm_statement(self, asm_statement, span)
     }
@@ -504,7 +552,8 @@ pub fn visit_constant<'ast, V: Visit<'ast> + ?Sized>(
     match *constant {
         Constant::Integer(ref i) => visitor.visit_integer(i, span),
         Constant::Float(ref f) => visitor.visit_float(f, span),
-        Constant::Character(_) => {}
+        Constant::Character(ref c) => visitor.visit_character(c, span),
+        Constant::Nullptr | Constant::Bool(_) => {}
     }
 }
 
@@ -574,6 +623,21 @@ pub fn visit_float_format<'ast, V: Visit<'ast> + ?Sized>(
     }
 }
 
+pub fn visit_character<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    character: &'ast Character,
+    span: &'ast Span,
+) {
+    visitor.visit_character_encoding(&character.encoding, span);
+}
+
+pub fn visit_character_encoding<'ast, V: Visit<'ast> + ?Sized>(
+    _visitor: &mut V,
+    _character_encoding: &'ast CharacterEncoding,
+    _span: &'ast Span,
+) {
+}
+
 pub fn visit_string_literal<'ast, V: Visit<'ast> + ?Sized>(
     _visitor: &mut V,
     _string_literal: &'ast StringLiteral,
@@ -611,7 +675,12 @@ pub fn visit_expression<'ast, V: Visit<'ast> + ?Sized>(
         }
         Expression::OffsetOf(ref o) => visitor.visit_offset_of_expression(&o.node, &o.span),
         Expression::VaArg(ref v) => visitor.visit_va_arg_expression(&v.node, &v.span),
+        Expression::TypesCompatible(ref t) => {
+            visitor.visit_types_compatible_expression(&t.node, &t.span)
+        }
+        Expression::Choose(ref c) => visitor.visit_choose_expression(&c.node, &c.span),
         Expression::Statement(ref s) => visitor.visit_statement(&s.node, &s.span),
+        Expression::LabelAddress(ref l) => visitor.visit_identifier(&l.node, &l.span),
     }
 }
 
@@ -792,10 +861,9 @@ pub fn visit_conditional_expression<'ast, V: Visit<'ast> + ?Sized>(
         &conditional_expression.condition.node,
         &conditional_expression.condition.span,
     );
-    visitor.visit_expression(
-        &conditional_expression.then_expression.node,
-        &conditional_expression.then_expression.span,
-    );
+    if let Some(ref then_expression) = conditional_expression.then_expression {
+        visitor.visit_expression(&then_expression.node, &then_expression.span);
+    }
     visitor.visit_expression(
         &conditional_expression.else_expression.node,
         &conditional_expression.else_expression.span,
@@ -817,6 +885,40 @@ pub fn visit_va_arg_expression<'ast, V: Visit<'ast> + ?Sized>(
     );
 }
 
+pub fn visit_types_compatible_expression<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    types_compatible_expression: &'ast TypesCompatibleExpression,
+    _span: &'ast Span,
+) {
+    visitor.visit_type_name(
+        &types_compatible_expression.lhs.node,
+        &types_compatible_expression.lhs.span,
+    );
+    visitor.visit_type_name(
+        &types_compatible_expression.rhs.node,
+        &types_compatible_expression.rhs.span,
+    );
+}
+
+pub fn visit_choose_expression<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    choose_expression: &'ast ChooseExpression,
+    _span: &'ast Span,
+) {
+    visitor.visit_expression(
+        &choose_expression.condition.node,
+        &choose_expression.condition.span,
+    );
+    visitor.visit_expression(
+        &choose_expression.then_expression.node,
+        &choose_expression.then_expression.span,
+    );
+    visitor.visit_expression(
+        &choose_expression.else_expression.node,
+        &choose_expression.else_expression.span,
+    );
+}
+
 pub fn visit_offset_of_expression<'ast, V: Visit<'ast> + ?Sized>(
     visitor: &mut V,
     offset_of_expression: &'ast OffsetOfExpression,
@@ -888,11 +990,7 @@ pub fn visit_declaration_specifier<'ast, V: Visit<'ast> + ?Sized>(
         DeclarationSpecifier::Alignment(ref a) => {
             visitor.visit_alignment_specifier(&a.node, &a.span)
         }
-        DeclarationSpecifier::Extension(ref e) => {
-            for extension in e {
-                visitor.visit_extension(&extension.node, &extension.span);
-            }
-        }
+        DeclarationSpecifier::Extension(ref e) => visitor.visit_extension(&e.node, &e.span),
     }
 }
 
@@ -927,8 +1025,11 @@ pub fn visit_type_specifier<'ast, V: Visit<'ast> + ?Sized>(
         TypeSpecifier::Struct(ref s) => visitor.visit_struct_type(&s.node, &s.span),
         TypeSpecifier::Enum(ref e) => visitor.visit_enum_type(&e.node, &e.span),
         TypeSpecifier::TypedefName(ref t) => visitor.visit_identifier(&t.node, &t.span),
-        TypeSpecifier::TypeOf(ref t) => visitor.visit_type_of(&t.node, &t.span),
+        TypeSpecifier::TypeOf(ref t) | TypeSpecifier::TypeOfUnqual(ref t) => {
+            visitor.visit_type_of(&t.node, &t.span)
+        }
         TypeSpecifier::TS18661Float(ref t) => visitor.visit_ts18661_float_type(t, span),
+        TypeSpecifier::BitInt(ref e) => visitor.visit_expression(&e.node, &e.span),
         _ => {}
     }
 }
@@ -954,6 +1055,9 @@ pub fn visit_struct_type<'ast, V: Visit<'ast> + ?Sized>(
     _span: &'ast Span,
 ) {
     visitor.visit_struct_kind(&struct_type.kind.node, &struct_type.kind.span);
+    for extension in &struct_type.extensions {
+        visitor.visit_extension(&extension.node, &extension.span);
+    }
     if let Some(ref identifier) = struct_type.identifier {
         visitor.visit_identifier(&identifier.node, &identifier.span);
     }
@@ -1003,6 +1107,7 @@ pub fn visit_specifier_qualifier<'ast, V: Visit<'ast> + ?Sized>(
     match *specifier_qualifier {
         SpecifierQualifier::TypeSpecifier(ref t) => visitor.visit_type_specifier(&t.node, &t.span),
         SpecifierQualifier::TypeQualifier(ref t) => visitor.visit_type_qualifier(&t.node, &t.span),
+        SpecifierQualifier::Extension(ref e) => visitor.visit_extension(&e.node, &e.span),
     }
 }
 
@@ -1024,9 +1129,17 @@ pub fn visit_enum_type<'ast, V: Visit<'ast> + ?Sized>(
     enum_type: &'ast EnumType,
     _span: &'ast Span,
 ) {
+    for extension in &enum_type.extensions {
+        visitor.visit_extension(&extension.node, &extension.span);
+    }
     if let Some(ref identifier) = enum_type.identifier {
         visitor.visit_identifier(&identifier.node, &identifier.span);
     }
+    if let Some(ref underlying) = enum_type.underlying {
+        for specifier in underlying {
+            visitor.visit_specifier_qualifier(&specifier.node, &specifier.span);
+        }
+    }
     for enumerator in &enum_type.enumerators {
         visitor.visit_enumerator(&enumerator.node, &enumerator.span);
     }
@@ -1041,6 +1154,9 @@ pub fn visit_enumerator<'ast, V: Visit<'ast> + ?Sized>(
     if let Some(ref expression) = enumerator.expression {
         visitor.visit_expression(&expression.node, &expression.span);
     }
+    for extension in &enumerator.extensions {
+        visitor.visit_extension(&extension.node, &extension.span);
+    }
 }
 
 pub fn visit_type_qualifier<'ast, V: Visit<'ast> + ?Sized>(
@@ -1123,7 +1239,10 @@ pub fn visit_array_declarator<'ast, V: Visit<'ast> + ?Sized>(
     for qualifier in &array_declarator.qualifiers {
         visitor.visit_type_qualifier(&qualifier.node, &qualifier.span);
     }
-    visitor.visit_array_size(&array_declarator.size, span)
+    visitor.visit_array_size(&array_declarator.size, span);
+    for extension in &array_declarator.extensions {
+        visitor.visit_extension(&extension.node, &extension.span);
+    }
 }
 
 pub fn visit_function_declarator<'ast, V: Visit<'ast> + ?Sized>(
@@ -1135,6 +1254,9 @@ pub fn visit_function_declarator<'ast, V: Visit<'ast> + ?Sized>(
         visitor.visit_parameter_declaration(&parameter.node, &parameter.span);
     }
     visitor.visit_ellipsis(&function_declarator.ellipsis, span);
+    for extension in &function_declarator.extensions {
+        visitor.visit_extension(&extension.node, &extension.span);
+    }
 }
 
 pub fn visit_pointer_qualifier<'ast, V: Visit<'ast> + ?Sized>(
@@ -1255,11 +1377,16 @@ pub fn visit_static_assert<'ast, V: Visit<'ast> + ?Sized>(
     static_assert: &'ast StaticAssert,
     _span: &'ast Span,
 ) {
+    for extension in &static_assert.extensions {
+        visitor.visit_extension(&extension.node, &extension.span);
+    }
     visitor.visit_expression(
         &static_assert.expression.node,
         &static_assert.expression.span,
     );
-    visitor.visit_string_literal(&static_assert.message.node, &static_assert.message.span);
+    if let Some(ref message) = static_assert.message {
+        visitor.visit_string_literal(&message.node, &message.span);
+    }
 }
 
 pub fn visit_statement<'ast, V: Visit<'ast> + ?Sized>(
@@ -1283,10 +1410,12 @@ pub fn visit_statement<'ast, V: Visit<'ast> + ?Sized>(
         Statement::DoWhile(ref d) => visitor.visit_do_while_statement(&d.node, &d.span),
         Statement::For(ref f) => visitor.visit_for_statement(&f.node, &f.span),
         Statement::Goto(ref g) => visitor.visit_identifier(&g.node, &g.span),
+        Statement::GotoIndirect(ref e) => visitor.visit_expression(&e.node, &e.span),
         Statement::Return(Some(ref r)) => {
             visitor.visit_expression(&r.node, &r.span);
         }
         Statement::Asm(ref a) => visitor.visit_asm_statement(&a.node, &a.span),
+        Statement::Attributed(ref a) => visitor.visit_attributed_statement(&a.node, &a.span),
         _ => {}
     }
 }
@@ -1303,6 +1432,20 @@ pub fn visit_labeled_statement<'ast, V: Visit<'ast> + ?Sized>(
     );
 }
 
+pub fn visit_attributed_statement<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    attributed_statement: &'ast AttributedStatement,
+    _span: &'ast Span,
+) {
+    for extension in &attributed_statement.extensions {
+        visitor.visit_extension(&extension.node, &extension.span);
+    }
+    visitor.visit_statement(
+        &attributed_statement.statement.node,
+        &attributed_statement.statement.span,
+    );
+}
+
 pub fn visit_if_statement<'ast, V: Visit<'ast> + ?Sized>(
     visitor: &mut V,
     if_statement: &'ast IfStatement,
@@ -1414,6 +1557,12 @@ pub fn visit_block_item<'ast, V: Visit<'ast> + ?Sized>(
     match *block_item {
         BlockItem::Declaration(ref d) => visitor.visit_declaration(&d.node, &d.span),
         BlockItem::StaticAssert(ref s) => visitor.visit_static_assert(&s.node, &s.span),
+        BlockItem::FunctionDefinition(ref f) => visitor.visit_function_definition(&f.node, &f.span),
+        BlockItem::LocalLabels(ref labels) => {
+            for l in labels {
+                visitor.visit_identifier(&l.node, &l.span);
+            }
+        }
         BlockItem::Statement(ref s) => visitor.visit_statement(&s.node, &s.span),
     }
 }
@@ -1438,6 +1587,9 @@ pub fn visit_external_declaration<'ast, V: Visit<'ast> + ?Sized>(
         ExternalDeclaration::FunctionDefinition(ref f) => {
             visitor.visit_function_definition(&f.node, &f.span)
         }
+        ExternalDeclaration::LinkageSpecification(ref l) => {
+            visitor.visit_linkage_specification(&l.node, &l.span)
+        }
     }
 }
 
@@ -1462,6 +1614,20 @@ pub fn visit_function_definition<'ast, V: Visit<'ast> + ?Sized>(
     );
 }
 
+pub fn visit_linkage_specification<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    linkage_specification: &'ast LinkageSpecification,
+    _span: &'ast Span,
+) {
+    visitor.visit_string_literal(
+        &linkage_specification.language.node,
+        &linkage_specification.language.span,
+    );
+    for declaration in &linkage_specification.declarations {
+        visitor.visit_external_declaration(&declaration.node, &declaration.span);
+    }
+}
+
 pub fn visit_extension<'ast, V: Visit<'ast> + ?Sized>(
     visitor: &mut V,
     extension: &'ast Extension,
@@ -1469,10 +1635,14 @@ pub fn visit_extension<'ast, V: Visit<'ast> + ?Sized>(
 ) {
     match *extension {
         Extension::Attribute(ref a) => visitor.visit_attribute(a, span),
-        Extension::AsmLabel(ref a) => visitor.visit_string_literal(&a.node, &a.span),
+        Extension::AsmLabel(ref a) | Extension::AsmRegister(ref a) => {
+            visitor.visit_string_literal(&a.node, &a.span)
+        }
         Extension::AvailabilityAttribute(ref a) => {
             visitor.visit_availability_attribute(&a.node, &a.span)
         }
+        Extension::StandardAttribute(ref a) => visitor.visit_standard_attribute(a, span),
+        Extension::Marker => {}
     }
 }
 
@@ -1486,6 +1656,18 @@ pub fn visit_attribute<'ast, V: Visit<'ast> + ?Sized>(
     }
 }
 
+pub fn visit_standard_attribute<'ast, V: Visit<'ast> + ?Sized>(
+    visitor: &mut V,
+    attribute: &'ast StandardAttribute,
+    _span: &'ast Span,
+) {
+    if let Some(ref arguments) = attribute.arguments {
+        for argument in arguments {
+            visitor.visit_expression(&argument.node, &argument.span);
+        }
+    }
+}
+
 pub fn visit_asm_statement<'ast, V: Visit<'ast> + ?Sized>(
     visitor: &mut V,
     asm_statement: &'ast AsmStatement,
@@ -1528,6 +1710,9 @@ pub fn visit_gnu_extended_asm_statement<'ast, V: Visit<'ast> + ?Sized>(
     for clobber in &gnu_extended_asm_statement.clobbers {
         visitor.visit_string_literal(&clobber.node, &clobber.span);
     }
+    for label in &gnu_extended_asm_statement.labels {
+        visitor.visit_identifier(&label.node, &label.span);
+    }
 }
 
 pub fn visit_gnu_asm_operand<'ast, V: Visit<'ast> + ?Sized>(